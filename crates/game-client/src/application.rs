@@ -2,7 +2,7 @@ use crate::{
     chat::{self, CancelChat, OpenChat, SendChat},
     combat,
     configuration::Settings,
-    core::{ActorBundle, PlayerComponent},
+    core::{ActorBundle, GuildComponent, PlayerComponent, ReactionComponent},
     input::{Chatting, EscapePressed, Movement},
     materials,
     movement::{self, PredictionHistory},
@@ -19,9 +19,11 @@ use bevy_renet::{
     renet::ConnectionConfig,
 };
 use game_core::{
+    auras::AuraLibrary,
     character_controller::CharacterVelocityY,
     components::Vitals,
     networking::{NetworkId, NetworkIdMapping},
+    power::Power,
     spells::SpellLibrary,
 };
 use protocol::server::EnterGameResponse;
@@ -57,6 +59,7 @@ pub fn create_authenticated_app(
     );
     app.add_plugins(EnhancedInputPlugin);
     app.add_plugins(RonAssetPlugin::<SpellLibrary>::new(&["spells.ron"]));
+    app.add_plugins(RonAssetPlugin::<AuraLibrary>::new(&["auras.ron"]));
 
     app.add_plugins((
         networking::NetworkingPlugin,
@@ -114,8 +117,10 @@ fn on_enter_game(
         ..default()
     });
 
-    let player_entity = commands.spawn((
+    let network_id = NetworkId(player_actor.id);
+    let mut player_entity = commands.spawn((
         PlayerComponent,
+        network_id,
         PredictionHistory::default(),
         CharacterVelocityY::default(),
         ActorBundle::new(
@@ -159,6 +164,17 @@ fn on_enter_game(
             ),
         ]),
     ));
+    if let protocol::models::ActorAttributes::Player {
+        guild_name: Some(guild_name),
+        ..
+    } = &player_actor.attributes
+    {
+        player_entity.insert(GuildComponent(guild_name.clone()));
+    }
+    player_entity.insert((
+        Power::from(response.power.clone()),
+        ReactionComponent(player_actor.reaction),
+    ));
     let player_entity_id = player_entity.id();
 
     commands.insert_resource(DebugActorMesh(capsule_mesh));
@@ -169,7 +185,6 @@ fn on_enter_game(
         Transform::from_xyz(0.0, 10.0, 12.0).looking_at(transform.translation, Vec3::Y),
     ));
 
-    let network_id = NetworkId(player_actor.id);
     let network_id_mapping = HashMap::from([(network_id, player_entity_id)]);
     commands.insert_resource(NetworkIdMapping(network_id_mapping));
     tracing::info!("spawned player");
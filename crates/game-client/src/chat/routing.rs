@@ -29,6 +29,15 @@ pub fn route_outgoing_chat(
     mut renet_client: ResMut<RenetClient>,
     social_sender: Res<SocialSender>,
 ) {
+    if let Some(action) = guild_command(&event.text) {
+        if let Some(ref sender) = social_sender.0
+            && let Err(e) = sender.try_send(action)
+        {
+            tracing::error!("failed to send guild command: {}", e);
+        }
+        return;
+    }
+
     match &event.channel {
         OutgoingChannel::Say | OutgoingChannel::Yell | OutgoingChannel::Zone => {
             let chat_channel = match &event.channel {
@@ -68,3 +77,18 @@ pub fn route_outgoing_chat(
         }
     }
 }
+
+/// Parses guild management commands typed into the chat, whatever the active channel.
+fn guild_command(text: &str) -> Option<SocialAction> {
+    let mut words = text.split_whitespace();
+    let action = match words.next()? {
+        "/ginvite" => SocialAction::GuildInviteByName {
+            target_name: words.next()?.to_string(),
+        },
+        "/gaccept" => SocialAction::GuildAccept,
+        "/gdecline" => SocialAction::GuildDecline,
+        "/gleave" => SocialAction::GuildLeave,
+        _ => return None,
+    };
+    Some(action)
+}
@@ -1,22 +1,122 @@
-use bevy::prelude::*;
+use avian3d::prelude::SpatialQuery;
+use bevy::{ecs::system::SystemParam, prelude::*, window::PrimaryWindow};
 use bevy_enhanced_input::prelude::ContextActivity;
 use bevy_renet::{RenetClient, renet::DefaultChannel};
 use game_core::{
     networking::NetworkId,
-    spells::{SpellLibrary, SpellLibraryHandle},
+    power::Power,
+    spells::{AreaShape, SpellDef, SpellLibrary, SpellLibraryHandle, SpellTarget},
 };
 use protocol::client::PlayerAction;
+use protocol::models::{CastFailedReason, PowerKind, Reaction};
+use protocol::server::{AbilityCooldown, GLOBAL_COOLDOWN_SPELL_ID};
 use std::collections::HashMap;
+use std::time::Duration;
 
 use super::KnownAbilities;
-use crate::core::PlayerComponent;
+use crate::chat::{ChatLog, ChatMessage, ChatMessageChannel};
+use crate::core::{PlayerComponent, ReactionComponent};
+use crate::networking::{CastFailedMessage, CooldownStartedMessage};
 use crate::theme::palette;
-use crate::world::selection::SelectedTarget;
+use crate::world::selection::{SelectedTarget, raycast_ground};
 
-/// Client-side cooldown tracking per spell_id.
+/// Client-side mirror of the server's cooldowns per spell_id, the global cooldown is stored
+/// under `GLOBAL_COOLDOWN_SPELL_ID`.
 #[derive(Resource, Default)]
 pub struct AbilityCooldowns(pub HashMap<u32, Timer>);
 
+impl AbilityCooldowns {
+    /// Restores cooldowns that were still running when the character logged out.
+    pub fn from_saved(cooldowns: &[AbilityCooldown]) -> Self {
+        let timers = cooldowns
+            .iter()
+            .map(|cooldown| {
+                let mut timer = Timer::from_seconds(cooldown.duration, TimerMode::Once);
+                timer.set_elapsed(Duration::from_secs_f32(
+                    (cooldown.duration - cooldown.remaining).max(0.0),
+                ));
+                (cooldown.spell_id, timer)
+            })
+            .collect();
+        Self(timers)
+    }
+
+    pub fn start(&mut self, spell_id: u32, duration: f32) {
+        self.0
+            .insert(spell_id, Timer::from_seconds(duration, TimerMode::Once));
+    }
+
+    fn is_running(&self, spell_id: u32) -> bool {
+        self.0.get(&spell_id).is_some_and(|t| !t.is_finished())
+    }
+
+    pub fn is_ready(&self, spell_id: u32, library: &SpellLibrary) -> bool {
+        let triggers_gcd = library
+            .spells
+            .get(&spell_id)
+            .is_some_and(|spell| spell.triggers_gcd);
+        !self.is_running(spell_id) && !(triggers_gcd && self.is_running(GLOBAL_COOLDOWN_SPELL_ID))
+    }
+}
+
+/// Everything needed to work out what a spell should be cast at.
+#[derive(SystemParam)]
+pub struct SpellTargeting<'w, 's> {
+    selected: Res<'w, SelectedTarget>,
+    player: Query<'w, 's, (&'static NetworkId, &'static Transform), With<PlayerComponent>>,
+    targets: Query<'w, 's, &'static NetworkId>,
+    windows: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    cameras: Query<'w, 's, (&'static Camera, &'static GlobalTransform)>,
+    spatial_query: SpatialQuery<'w, 's>,
+}
+
+impl SpellTargeting<'_, '_> {
+    fn resolve(&self, spell_id: u32, spell: &SpellDef) -> Option<PlayerAction> {
+        let player = self.player.single().ok();
+        let selected = self
+            .selected
+            .0
+            .and_then(|entity| self.targets.get(entity).ok());
+        let selected_network_id = selected.map(|(network_id, _)| network_id);
+
+        if let Some(area) = spell.area.as_ref() {
+            // Caster-centred areas go off at the player's feet
+            if area.shape.is_caster_centred() {
+                let (_, transform) = player?;
+                return Some(PlayerAction::CastSpellAtPosition {
+                    spell_id,
+                    position: transform.translation,
+                });
+            }
+
+            // Circles without a selected target are placed under the cursor
+            if matches!(area.shape, AreaShape::Circle { .. }) && selected_network_id.is_none() {
+                let window = self.windows.single().ok()?;
+                let position = raycast_ground(window, &self.cameras, &self.spatial_query)?;
+                return Some(PlayerAction::CastSpellAtPosition { spell_id, position });
+            }
+        }
+
+        let player_network_id = player.map(|(network_id, _)| network_id);
+        let target_network_id = match spell.target {
+            SpellTarget::Caster => player_network_id,
+            // Friendly spells fall back to the player when nothing friendly is selected
+            SpellTarget::Friendly => selected
+                .filter(|(_, reaction)| {
+                    reaction.is_some_and(|reaction| reaction.0 == Reaction::Friendly)
+                })
+                .map(|(network_id, _)| network_id)
+                .or(player_network_id),
+            SpellTarget::Hostile => selected_network_id,
+        }?;
+
+        Some(PlayerAction::CastSpell {
+            spell_id,
+            target_network_id: target_network_id.0,
+        })
+    }
+}
+
 /// Marker on the root action bar UI node.
 #[derive(Component)]
 pub struct ActionBar;
@@ -151,12 +251,11 @@ pub fn spawn_action_bar(
 pub fn handle_ability_input(
     keyboard: Res<ButtonInput<KeyCode>>,
     player_activity: Query<&ContextActivity<PlayerComponent>>,
-    selected: Res<SelectedTarget>,
     known: Res<KnownAbilities>,
-    targets: Query<&NetworkId>,
+    targeting: SpellTargeting,
     library_handle: Res<SpellLibraryHandle>,
     libraries: Res<Assets<SpellLibrary>>,
-    mut cooldowns: ResMut<AbilityCooldowns>,
+    cooldowns: Res<AbilityCooldowns>,
     mut client: ResMut<RenetClient>,
 ) {
     let Ok(activity) = player_activity.single() else {
@@ -179,30 +278,20 @@ pub fn handle_ability_input(
             continue;
         };
 
-        if let Some(timer) = cooldowns.0.get(&spell_id) {
-            if !timer.is_finished() {
-                continue;
-            }
+        if !cooldowns.is_ready(spell_id, library) {
+            continue;
         }
 
-        cast_spell(
-            spell_id,
-            &selected,
-            &targets,
-            library,
-            &mut cooldowns,
-            &mut client,
-        );
+        cast_spell(spell_id, &targeting, library, &mut client);
     }
 }
 
 pub fn handle_ability_click(
     slots: Query<(&Interaction, &AbilitySlot), Changed<Interaction>>,
-    selected: Res<SelectedTarget>,
-    targets: Query<&NetworkId>,
+    targeting: SpellTargeting,
     library_handle: Res<SpellLibraryHandle>,
     libraries: Res<Assets<SpellLibrary>>,
-    mut cooldowns: ResMut<AbilityCooldowns>,
+    cooldowns: Res<AbilityCooldowns>,
     mut client: ResMut<RenetClient>,
 ) {
     let Some(library) = libraries.get(&library_handle.0) else {
@@ -214,54 +303,71 @@ pub fn handle_ability_click(
             continue;
         }
 
-        if let Some(timer) = cooldowns.0.get(&slot.spell_id) {
-            if !timer.is_finished() {
-                continue;
-            }
+        if !cooldowns.is_ready(slot.spell_id, library) {
+            continue;
         }
 
-        cast_spell(
-            slot.spell_id,
-            &selected,
-            &targets,
-            library,
-            &mut cooldowns,
-            &mut client,
-        );
+        cast_spell(slot.spell_id, &targeting, library, &mut client);
     }
 }
 
 fn cast_spell(
     spell_id: u32,
-    selected: &SelectedTarget,
-    targets: &Query<&NetworkId>,
+    targeting: &SpellTargeting,
     library: &SpellLibrary,
-    cooldowns: &mut AbilityCooldowns,
     client: &mut RenetClient,
 ) {
-    let Some(target_entity) = selected.0 else {
+    let Some(spell_def) = library.spells.get(&spell_id) else {
         return;
     };
-
-    let Ok(network_id) = targets.get(target_entity) else {
-        tracing::warn!("selected target has no NetworkId");
+    let Some(action) = targeting.resolve(spell_id, spell_def) else {
         return;
     };
 
-    let action = PlayerAction::CastSpell {
-        spell_id,
-        target_network_id: network_id.0,
-    };
     let encoded = bitcode::encode(&action);
     client.send_message(DefaultChannel::ReliableOrdered, encoded);
+}
 
-    if let Some(spell_def) = library.spells.get(&spell_id) {
-        if spell_def.cooldown > 0.0 {
-            cooldowns.0.insert(
-                spell_id,
-                Timer::from_seconds(spell_def.cooldown, TimerMode::Once),
-            );
-        }
+pub fn handle_cooldown_started(
+    mut reader: MessageReader<CooldownStartedMessage>,
+    mut cooldowns: ResMut<AbilityCooldowns>,
+) {
+    for msg in reader.read() {
+        cooldowns.start(msg.spell_id, msg.duration);
+    }
+}
+
+/// Shows why the server rejected a cast in the chat log.
+pub fn handle_cast_failed(
+    mut reader: MessageReader<CastFailedMessage>,
+    q_power: Query<&Power, With<PlayerComponent>>,
+    mut chat_log: ResMut<ChatLog>,
+) {
+    for msg in reader.read() {
+        let text = match msg.reason {
+            CastFailedReason::AlreadyCasting => "You are already casting",
+            CastFailedReason::UnknownSpell => "You don't know that spell",
+            CastFailedReason::OnCooldown => "That ability isn't ready yet",
+            CastFailedReason::InvalidTarget => "Invalid target",
+            CastFailedReason::OutOfRange => "Out of range",
+            CastFailedReason::LineOfSight => "Target not in line of sight",
+            CastFailedReason::Dead => "You can't do that while dead",
+            CastFailedReason::NotEnoughPower => match q_power.single().map(|power| power.kind) {
+                Ok(PowerKind::Energy) => "Not enough energy",
+                Ok(PowerKind::Rage) => "Not enough rage",
+                _ => "Not enough mana",
+            },
+            CastFailedReason::Stunned => "You can't do that while stunned",
+            CastFailedReason::Silenced => "You can't do that while silenced",
+            CastFailedReason::Feared => "You can't do that while feared",
+        };
+        tracing::debug!(spell_id = %msg.spell_id, reason = ?msg.reason, "spell cast failed");
+
+        chat_log.push(ChatMessage {
+            channel: ChatMessageChannel::System,
+            sender: String::new(),
+            text: text.to_string(),
+        });
     }
 }
 
@@ -273,13 +379,16 @@ pub fn tick_cooldowns(time: Res<Time>, mut cooldowns: ResMut<AbilityCooldowns>)
 
 pub fn update_slot_visuals(
     cooldowns: Res<AbilityCooldowns>,
+    library_handle: Res<SpellLibraryHandle>,
+    libraries: Res<Assets<SpellLibrary>>,
     mut slots: Query<(&AbilitySlot, &Interaction, &mut BackgroundColor)>,
 ) {
+    let Some(library) = libraries.get(&library_handle.0) else {
+        return;
+    };
+
     for (slot, interaction, mut bg) in slots.iter_mut() {
-        let on_cooldown = cooldowns
-            .0
-            .get(&slot.spell_id)
-            .is_some_and(|t| !t.is_finished());
+        let on_cooldown = !cooldowns.is_ready(slot.spell_id, library);
 
         if on_cooldown {
             *bg = BackgroundColor(SLOT_COOLDOWN_COLOR);
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use protocol::models::AreaShape;
+use std::f32::consts::FRAC_PI_2;
+
+use crate::networking::AreaEffectMessage;
+use crate::theme::palette;
+
+const AREA_EFFECT_DURATION: f32 = 0.6;
+/// Keeps the decal from z-fighting with the terrain
+const GROUND_OFFSET: f32 = 0.05;
+
+/// Translucent decal showing the area hit by an area spell, fades out over its lifetime.
+#[derive(Component)]
+pub(crate) struct AreaEffectDecal {
+    timer: Timer,
+    material: Handle<StandardMaterial>,
+}
+
+pub(crate) fn spawn_area_effects(
+    mut commands: Commands,
+    mut reader: MessageReader<AreaEffectMessage>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for msg in reader.read() {
+        // 2D primitives are built in the XY plane, so lay them flat on the ground
+        let flat = Quat::from_rotation_x(-FRAC_PI_2);
+        let (mesh, rotation) = match msg.shape {
+            AreaShape::Circle { radius } => (meshes.add(Circle::new(radius)), flat),
+            AreaShape::Cone { radius, angle, yaw } => (
+                meshes.add(CircularSector::new(radius, angle * 0.5)),
+                Quat::from_rotation_y(yaw) * flat,
+            ),
+        };
+
+        let material = materials.add(StandardMaterial {
+            base_color: palette::AREA_EFFECT,
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            double_sided: true,
+            cull_mode: None,
+            ..default()
+        });
+
+        commands.spawn((
+            AreaEffectDecal {
+                timer: Timer::from_seconds(AREA_EFFECT_DURATION, TimerMode::Once),
+                material: material.clone(),
+            },
+            Mesh3d(mesh),
+            MeshMaterial3d(material),
+            Transform::from_translation(msg.center + Vec3::Y * GROUND_OFFSET)
+                .with_rotation(rotation),
+        ));
+    }
+}
+
+pub(crate) fn update_area_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut decals: Query<(Entity, &mut AreaEffectDecal)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (entity, mut decal) in decals.iter_mut() {
+        decal.timer.tick(time.delta());
+        if decal.timer.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        if let Some(material) = materials.get_mut(&decal.material) {
+            let alpha = palette::AREA_EFFECT.alpha() * (1.0 - decal.timer.fraction());
+            material.base_color.set_alpha(alpha);
+        }
+    }
+}
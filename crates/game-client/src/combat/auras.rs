@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use game_core::crowd_control::CrowdControlState;
+use game_core::networking::{NetworkId, NetworkIdMapping};
+
+use crate::core::{AuraInstance, Auras};
+use crate::networking::{ActorDeathMessage, AuraAppliedMessage, AuraRemovedMessage};
+
+pub fn handle_aura_applied(
+    mut reader: MessageReader<AuraAppliedMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    mut q_auras: Query<&mut Auras>,
+) {
+    for msg in reader.read() {
+        let Some(&entity) = network_id_mapping.0.get(&NetworkId(msg.actor_id)) else {
+            continue;
+        };
+        let Ok(mut auras) = q_auras.get_mut(entity) else {
+            continue;
+        };
+
+        let timer = Timer::from_seconds(msg.duration, TimerMode::Once);
+        match auras.0.iter_mut().find(|a| a.aura_id == msg.aura_id) {
+            Some(existing) => {
+                existing.stacks = msg.stacks;
+                existing.timer = timer;
+            }
+            None => auras.0.push(AuraInstance {
+                aura_id: msg.aura_id,
+                stacks: msg.stacks,
+                timer,
+            }),
+        }
+    }
+}
+
+pub fn handle_aura_removed(
+    mut reader: MessageReader<AuraRemovedMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    mut q_auras: Query<&mut Auras>,
+) {
+    for msg in reader.read() {
+        if let Some(&entity) = network_id_mapping.0.get(&NetworkId(msg.actor_id))
+            && let Ok(mut auras) = q_auras.get_mut(entity)
+        {
+            auras.0.retain(|a| a.aura_id != msg.aura_id);
+        }
+    }
+}
+
+/// The server drops all auras, and the crowd control they caused, on death without sending
+/// individual removals.
+pub fn clear_auras_on_death(
+    mut reader: MessageReader<ActorDeathMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    mut q_auras: Query<(&mut Auras, Option<&mut CrowdControlState>)>,
+) {
+    for msg in reader.read() {
+        if let Some(&entity) = network_id_mapping.0.get(&NetworkId(msg.0))
+            && let Ok((mut auras, crowd_control)) = q_auras.get_mut(entity)
+        {
+            auras.0.clear();
+            if let Some(mut crowd_control) = crowd_control {
+                crowd_control.set_if_neq(CrowdControlState::default());
+            }
+        }
+    }
+}
+
+pub fn tick_aura_timers(time: Res<Time>, mut q_auras: Query<&mut Auras>) {
+    for mut auras in q_auras.iter_mut() {
+        for aura in auras.0.iter_mut() {
+            aura.timer.tick(time.delta());
+        }
+    }
+}
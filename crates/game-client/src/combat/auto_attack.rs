@@ -1,4 +1,5 @@
 use super::IsAttacking;
+use crate::core::ReactionComponent;
 use crate::input::EscapePressed;
 use bevy::prelude::*;
 use bevy_enhanced_input::prelude::*;
@@ -12,14 +13,18 @@ pub struct AttackTarget(pub Entity);
 
 pub fn on_attack_target(
     event: On<AttackTarget>,
-    network_ids: Query<&NetworkId>,
+    targets: Query<(&NetworkId, Option<&ReactionComponent>)>,
     mut is_attacking: ResMut<IsAttacking>,
     mut client: ResMut<RenetClient>,
 ) {
-    let Ok(network_id) = network_ids.get(event.0) else {
+    let Ok((network_id, reaction)) = targets.get(event.0) else {
         tracing::warn!("attack target has no NetworkId");
         return;
     };
+    // The server rejects attacks on friendly actors anyway
+    if reaction.is_some_and(|reaction| !reaction.0.can_attack()) {
+        return;
+    }
 
     let action = PlayerAction::StartAttack {
         target_network_id: network_id.0,
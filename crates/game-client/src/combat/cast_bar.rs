@@ -1,5 +1,7 @@
 use bevy::prelude::*;
+use game_core::crowd_control::CrowdControlState;
 
+use crate::core::PlayerComponent;
 use crate::theme::palette;
 use super::action_bar::BAR_BOTTOM;
 
@@ -9,6 +11,8 @@ pub struct ActiveCast {
     pub spell_id: u32,
     pub spell_name: String,
     pub timer: Timer,
+    /// Channel bars drain instead of filling up
+    pub channelled: bool,
 }
 
 #[derive(Component)]
@@ -117,7 +121,11 @@ pub fn update_cast_bar(
 
     cast.timer.tick(time.delta());
 
-    let progress = cast.timer.fraction() * 100.0;
+    let progress = if cast.channelled {
+        cast.timer.fraction_remaining() * 100.0
+    } else {
+        cast.timer.fraction() * 100.0
+    };
     for mut node in fill.iter_mut() {
         node.width = Val::Percent(progress);
     }
@@ -126,3 +134,17 @@ pub fn update_cast_bar(
         commands.remove_resource::<ActiveCast>();
     }
 }
+
+/// The server interrupts casts of stunned, silenced and feared actors without a message.
+pub fn interrupt_cast_on_crowd_control(
+    q_player: Query<&CrowdControlState, (With<PlayerComponent>, Changed<CrowdControlState>)>,
+    active_cast: Option<Res<ActiveCast>>,
+    mut commands: Commands,
+) {
+    if active_cast.is_some()
+        && let Ok(state) = q_player.single()
+        && !state.can_cast()
+    {
+        commands.remove_resource::<ActiveCast>();
+    }
+}
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use game_core::{
+    auras::{AuraKind, AuraLibrary, AuraLibraryHandle},
+    networking::{NetworkId, NetworkIdMapping},
+    spells::{SpellLibrary, SpellLibraryHandle},
+};
+use protocol::{
+    models::{CombatLogEvent, DamageSchool, HitResult},
+    server::AUTO_ATTACK_VISUAL_ID,
+};
+
+use crate::chat::{ChatLog, ChatMessage, ChatMessageChannel};
+use crate::core::NameComponent;
+use crate::networking::CombatLogMessage;
+
+/// Formats the server combat log into the combat chat tab.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn log_combat_events(
+    mut reader: MessageReader<CombatLogMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    q_names: Query<&NameComponent>,
+    spell_library_handle: Res<SpellLibraryHandle>,
+    spell_libraries: Res<Assets<SpellLibrary>>,
+    aura_library_handle: Res<AuraLibraryHandle>,
+    aura_libraries: Res<Assets<AuraLibrary>>,
+    mut chat_log: ResMut<ChatLog>,
+) {
+    let spells = spell_libraries.get(&spell_library_handle.0);
+    let auras = aura_libraries.get(&aura_library_handle.0);
+
+    let actor_name = |actor_id: u32| {
+        network_id_mapping
+            .0
+            .get(&NetworkId(actor_id))
+            .and_then(|&entity| q_names.get(entity).ok())
+            .map_or("Unknown", |name| name.0.as_str())
+    };
+    let spell_name = |spell_id: u32| {
+        if spell_id == AUTO_ATTACK_VISUAL_ID {
+            return "attack";
+        }
+        spells
+            .and_then(|library| library.spells.get(&spell_id))
+            .map_or("unknown spell", |spell| spell.name.as_str())
+    };
+    let aura = |aura_id: u32| auras.and_then(|library| library.auras.get(&aura_id));
+
+    for CombatLogMessage(entry) in reader.read() {
+        let target = actor_name(entry.target_id);
+        let source = entry.source_id.map_or("Unknown", actor_name);
+
+        let text = match entry.event {
+            CombatLogEvent::Damage {
+                spell_id,
+                amount,
+                result,
+                school,
+                absorbed,
+                resisted,
+                periodic,
+            } => {
+                let school = school_prefix(school);
+                let mitigation = mitigation_suffix(resisted, absorbed);
+                let spell = spell_name(spell_id);
+                if periodic {
+                    format!(
+                        "{target} suffers {amount} {school}damage from {source}'s {spell}{mitigation}"
+                    )
+                } else {
+                    let outcome = match result {
+                        HitResult::Critical => " (critical)",
+                        HitResult::Block => " (blocked)",
+                        _ => "",
+                    };
+                    format!(
+                        "{source}'s {spell} hits {target} for {amount} {school}damage{outcome}{mitigation}"
+                    )
+                }
+            }
+            CombatLogEvent::Heal {
+                spell_id,
+                amount,
+                periodic,
+            } => {
+                let spell = spell_name(spell_id);
+                if periodic {
+                    format!("{target} gains {amount} health from {source}'s {spell}")
+                } else {
+                    format!("{source}'s {spell} heals {target} for {amount}")
+                }
+            }
+            CombatLogEvent::Miss { spell_id, result } => {
+                let spell = spell_name(spell_id);
+                match result {
+                    HitResult::Dodge => format!("{target} dodges {source}'s {spell}"),
+                    HitResult::Parry => format!("{target} parries {source}'s {spell}"),
+                    _ => format!("{source}'s {spell} misses {target}"),
+                }
+            }
+            CombatLogEvent::Immune { spell_id } => {
+                let spell = spell_name(spell_id);
+                format!("{target} is immune to {source}'s {spell}")
+            }
+            CombatLogEvent::AuraApplied { aura_id } => match aura(aura_id) {
+                Some(def) if def.kind == AuraKind::Debuff => {
+                    format!("{target} is afflicted by {}", def.name)
+                }
+                Some(def) => format!("{target} gains {}", def.name),
+                None => format!("{target} gains an unknown aura"),
+            },
+            CombatLogEvent::AuraRemoved { aura_id } => {
+                let name = aura(aura_id).map_or("Unknown aura", |def| def.name.as_str());
+                format!("{name} fades from {target}")
+            }
+            // Self-cast and ground-targeted spells are logged against the caster
+            CombatLogEvent::CastStarted { spell_id } => {
+                let spell = spell_name(spell_id);
+                if entry.source_id == Some(entry.target_id) {
+                    format!("{source} begins casting {spell}")
+                } else {
+                    format!("{source} begins casting {spell} on {target}")
+                }
+            }
+            CombatLogEvent::Death => format!("{target} dies"),
+        };
+
+        chat_log.push(ChatMessage {
+            channel: ChatMessageChannel::Combat,
+            sender: String::new(),
+            text,
+        });
+    }
+}
+
+/// School of a damage log line, physical damage is left unnamed.
+fn school_prefix(school: DamageSchool) -> &'static str {
+    match school {
+        DamageSchool::Physical => "",
+        DamageSchool::Fire => "fire ",
+        DamageSchool::Frost => "frost ",
+        DamageSchool::Nature => "nature ",
+        DamageSchool::Shadow => "shadow ",
+        DamageSchool::Holy => "holy ",
+    }
+}
+
+/// Damage negated by resistances and shields, e.g. " (4 resisted, 10 absorbed)".
+fn mitigation_suffix(resisted: i32, absorbed: i32) -> String {
+    let parts: Vec<String> = [(resisted, "resisted"), (absorbed, "absorbed")]
+        .into_iter()
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, label)| format!("{amount} {label}"))
+        .collect();
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", parts.join(", "))
+    }
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use protocol::models::EncounterOutcome;
+
+use super::pvp::push_system_message;
+use crate::chat::ChatLog;
+use crate::networking::{EncounterEndedMessage, EncounterStartedMessage};
+
+pub fn handle_encounter_started(
+    mut reader: MessageReader<EncounterStartedMessage>,
+    mut chat_log: ResMut<ChatLog>,
+) {
+    for msg in reader.read() {
+        push_system_message(&mut chat_log, format!("{} has begun", msg.name));
+    }
+}
+
+pub fn handle_encounter_ended(
+    mut reader: MessageReader<EncounterEndedMessage>,
+    mut chat_log: ResMut<ChatLog>,
+) {
+    for msg in reader.read() {
+        let text = match msg.outcome {
+            EncounterOutcome::Defeated => format!("{} has been defeated", msg.name),
+            EncounterOutcome::Wipe => format!("{} has been wiped", msg.name),
+            EncounterOutcome::Reset => format!("{} has reset", msg.name),
+        };
+        push_system_message(&mut chat_log, text);
+    }
+}
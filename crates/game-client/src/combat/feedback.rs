@@ -1,8 +1,8 @@
 use bevy::prelude::*;
+use game_core::networking::{NetworkId, NetworkIdMapping};
+use protocol::models::HitResult;
 
-use crate::chat::{ChatLog, ChatMessage, ChatMessageChannel};
-use crate::core::NameComponent;
-use crate::networking::CombatHitMessage;
+use crate::networking::{CombatHitKind, CombatHitMessage, ProjectileLaunchedMessage};
 use crate::theme::palette;
 
 const FLOAT_DURATION: f32 = 1.2;
@@ -10,11 +10,24 @@ const FLOAT_DISTANCE: f32 = 60.0;
 const FLASH_DURATION: f32 = 0.15;
 const FLASH_COLOR: LinearRgba = LinearRgba::new(4.0, 0.2, 0.2, 1.0);
 const TEXT_OFFSET_Y: f32 = -40.0;
+const FONT_SIZE: f32 = 22.0;
+const CRIT_FONT_SIZE: f32 = 32.0;
+const PROJECTILE_RADIUS: f32 = 0.25;
+const PROJECTILE_HEIGHT: f32 = 1.5;
 
 #[derive(Component)]
 pub(crate) struct FloatingCombatText {
     target_entity: Entity,
     timer: Timer,
+    color: Color,
+}
+
+/// Visual for a spell projectile, homing on the target over the server's travel time.
+#[derive(Component)]
+pub(crate) struct ProjectileVisual {
+    target_entity: Entity,
+    start: Vec3,
+    timer: Timer,
 }
 
 #[derive(Component)]
@@ -30,15 +43,44 @@ pub(crate) fn handle_combat_hits(
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
     for msg in reader.read() {
+        let (text, color) = match msg.kind {
+            // Hits fully soaked by a shield
+            CombatHitKind::Damage(HitResult::Hit | HitResult::Critical | HitResult::Block)
+                if msg.amount == 0 && msg.absorbed > 0 =>
+            {
+                ("Absorb".to_string(), palette::AVOID_TEXT)
+            }
+            CombatHitKind::Damage(HitResult::Hit) => {
+                (format!("{}", msg.amount), palette::DAMAGE_TEXT)
+            }
+            CombatHitKind::Damage(HitResult::Critical) => {
+                (format!("{}!", msg.amount), palette::DAMAGE_TEXT)
+            }
+            CombatHitKind::Damage(HitResult::Block) => {
+                (format!("{} (Block)", msg.amount), palette::DAMAGE_TEXT)
+            }
+            CombatHitKind::Damage(HitResult::Miss) => ("Miss".to_string(), palette::AVOID_TEXT),
+            CombatHitKind::Damage(HitResult::Dodge) => ("Dodge".to_string(), palette::AVOID_TEXT),
+            CombatHitKind::Damage(HitResult::Parry) => ("Parry".to_string(), palette::AVOID_TEXT),
+            CombatHitKind::Heal => (format!("+{}", msg.amount), palette::HEAL_TEXT),
+            CombatHitKind::Immune => ("Immune".to_string(), palette::AVOID_TEXT),
+        };
+        let font_size = if msg.kind == CombatHitKind::Damage(HitResult::Critical) {
+            CRIT_FONT_SIZE
+        } else {
+            FONT_SIZE
+        };
+
         commands.spawn((
             FloatingCombatText {
                 target_entity: msg.target_entity,
                 timer: Timer::from_seconds(FLOAT_DURATION, TimerMode::Once),
+                color,
             },
-            Text::new(format!("{}", msg.amount)),
-            TextColor(palette::DAMAGE_TEXT),
+            Text::new(text),
+            TextColor(color),
             TextFont {
-                font_size: 22.0,
+                font_size,
                 ..default()
             },
             Node {
@@ -47,7 +89,10 @@ pub(crate) fn handle_combat_hits(
             },
         ));
 
-        if let Ok(material_handle) = q_targets.get(msg.target_entity)
+        // Only flash on damage that landed
+        if matches!(msg.kind, CombatHitKind::Damage(_))
+            && msg.amount > 0
+            && let Ok(material_handle) = q_targets.get(msg.target_entity)
             && let Some(material) = materials.get_mut(&material_handle.0)
         {
             let original_emissive = material.emissive;
@@ -87,7 +132,7 @@ pub(crate) fn update_floating_combat_text(
         } else {
             1.0
         };
-        text_color.0 = Color::srgba(1.0, 0.9, 0.1, alpha);
+        text_color.0 = fct.color.with_alpha(alpha);
 
         let Ok(target_transform) = targets.get(fct.target_entity) else {
             commands.entity(entity).despawn();
@@ -126,21 +171,63 @@ pub(crate) fn update_hit_flash(
     }
 }
 
-pub(crate) fn log_combat_hits(
-    mut reader: MessageReader<CombatHitMessage>,
-    q_names: Query<&NameComponent>,
-    mut chat_log: ResMut<ChatLog>,
+pub(crate) fn spawn_projectiles(
+    mut commands: Commands,
+    mut reader: MessageReader<ProjectileLaunchedMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    q_transforms: Query<&GlobalTransform>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
     for msg in reader.read() {
-        let target_name = q_names
-            .get(msg.target_entity)
-            .map(|n| n.0.as_str())
-            .unwrap_or("Unknown");
-
-        chat_log.push(ChatMessage {
-            channel: ChatMessageChannel::Combat,
-            sender: String::new(),
-            text: format!("{target_name} takes {amount} damage", amount = msg.amount),
-        });
+        let Some(&caster_entity) = network_id_mapping.0.get(&NetworkId(msg.caster_id)) else {
+            continue;
+        };
+        let Some(&target_entity) = network_id_mapping.0.get(&NetworkId(msg.target_id)) else {
+            continue;
+        };
+        let Ok(caster_transform) = q_transforms.get(caster_entity) else {
+            continue;
+        };
+
+        let start = caster_transform.translation() + Vec3::Y * PROJECTILE_HEIGHT;
+        commands.spawn((
+            ProjectileVisual {
+                target_entity,
+                start,
+                timer: Timer::from_seconds(msg.travel_time.max(f32::EPSILON), TimerMode::Once),
+            },
+            Mesh3d(meshes.add(Sphere::new(PROJECTILE_RADIUS))),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: palette::PROJECTILE,
+                emissive: palette::PROJECTILE.to_linear() * 4.0,
+                ..default()
+            })),
+            Transform::from_translation(start),
+        ));
+    }
+}
+
+pub(crate) fn update_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    targets: Query<&GlobalTransform, Without<ProjectileVisual>>,
+    mut projectiles: Query<(Entity, &mut ProjectileVisual, &mut Transform)>,
+) {
+    for (entity, mut projectile, mut transform) in projectiles.iter_mut() {
+        projectile.timer.tick(time.delta());
+
+        let Ok(target_transform) = targets.get(projectile.target_entity) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        if projectile.timer.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        // Follow the target as it moves, the server projectile homes as well
+        let end = target_transform.translation() + Vec3::Y * PROJECTILE_HEIGHT;
+        transform.translation = projectile.start.lerp(end, projectile.timer.fraction());
     }
 }
@@ -1,9 +1,16 @@
+mod auras;
 mod auto_attack;
 pub mod action_bar;
+mod area_effects;
 pub mod cast_bar;
+mod combat_log;
+mod encounter;
 mod feedback;
+mod pvp;
+mod release;
 
 pub use auto_attack::AttackTarget;
+pub use pvp::PvpFlagged;
 
 use bevy::prelude::*;
 
@@ -18,7 +25,10 @@ pub struct KnownAbilities(pub Vec<u32>);
 use crate::application::{AppState, EnterGame};
 use crate::networking::NetworkingSet;
 use action_bar::AbilityCooldowns;
-use game_core::spells::{SpellLibrary, SpellLibraryHandle};
+use game_core::{
+    auras::{AuraLibrary, AuraLibraryHandle},
+    spells::{SpellLibrary, SpellLibraryHandle},
+};
 
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CombatSet {
@@ -31,6 +41,7 @@ pub struct CombatPlugin;
 impl Plugin for CombatPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(IsAttacking::default());
+        app.init_resource::<PvpFlagged>();
 
         app.add_observer(auto_attack::on_attack_target);
         app.add_observer(auto_attack::on_escape);
@@ -42,10 +53,36 @@ impl Plugin for CombatPlugin {
                 action_bar::spawn_action_bar,
                 action_bar::handle_ability_input,
                 action_bar::handle_ability_click,
+                action_bar::handle_cooldown_started,
+                action_bar::handle_cast_failed,
                 action_bar::tick_cooldowns,
                 action_bar::update_slot_visuals,
                 cast_bar::manage_cast_bar,
                 cast_bar::update_cast_bar,
+                cast_bar::interrupt_cast_on_crowd_control,
+                auras::handle_aura_applied,
+                auras::handle_aura_removed,
+                auras::clear_auras_on_death,
+                auras::tick_aura_timers,
+                release::manage_release_dialog,
+            )
+                .in_set(CombatSet::ProcessInput)
+                .after(NetworkingSet::Receive)
+                .run_if(in_state(AppState::InGame)),
+        );
+
+        app.add_systems(
+            Update,
+            (
+                pvp::handle_duel_requested,
+                pvp::dismiss_expired_duel_request
+                    .run_if(resource_exists::<pvp::PendingDuelRequest>),
+                pvp::handle_duel_starting,
+                pvp::handle_duel_ended,
+                pvp::handle_pvp_flag_changed,
+                pvp::stop_attacking_friendly_target,
+                encounter::handle_encounter_started,
+                encounter::handle_encounter_ended,
             )
                 .in_set(CombatSet::ProcessInput)
                 .after(NetworkingSet::Receive)
@@ -58,7 +95,11 @@ impl Plugin for CombatPlugin {
                 feedback::handle_combat_hits,
                 feedback::update_floating_combat_text,
                 feedback::update_hit_flash,
-                feedback::log_combat_hits,
+                combat_log::log_combat_events,
+                feedback::spawn_projectiles,
+                feedback::update_projectiles,
+                area_effects::spawn_area_effects,
+                area_effects::update_area_effects,
             )
                 .in_set(CombatSet::Feedback)
                 .after(CombatSet::ProcessInput)
@@ -70,8 +111,11 @@ impl Plugin for CombatPlugin {
 fn on_enter_game(event: On<EnterGame>, mut commands: Commands, assets: Res<AssetServer>) {
     let response = &event.0;
     commands.insert_resource(KnownAbilities(response.known_abilities.clone()));
-    commands.insert_resource(AbilityCooldowns::default());
+    commands.insert_resource(AbilityCooldowns::from_saved(&response.cooldowns));
 
     let spells_handle = assets.load::<SpellLibrary>("spells.ron");
     commands.insert_resource(SpellLibraryHandle(spells_handle));
+
+    let auras_handle = assets.load::<AuraLibrary>("auras.ron");
+    commands.insert_resource(AuraLibraryHandle(auras_handle));
 }
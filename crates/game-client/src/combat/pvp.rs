@@ -0,0 +1,214 @@
+use bevy::picking::events::{Click, Pointer};
+use bevy::prelude::*;
+use bevy_renet::{RenetClient, renet::DefaultChannel};
+use game_core::networking::{NetworkId, NetworkIdMapping};
+use protocol::client::PlayerAction;
+use protocol::models::DuelEndReason;
+
+use super::IsAttacking;
+use crate::chat::{ChatLog, ChatMessage, ChatMessageChannel};
+use crate::core::NameComponent;
+use crate::networking::{
+    DuelEndedMessage, DuelRequestedMessage, DuelStartingMessage, PvpFlagChangedMessage,
+    ReactionChangedMessage,
+};
+use crate::theme::{palette, widgets};
+use crate::world::selection::SelectedTarget;
+
+/// Matches the server's duel request timeout
+const DUEL_REQUEST_TIMEOUT_SECS: f64 = 30.0;
+
+/// Whether the local player is flagged for world PvP, as reported by the server.
+#[derive(Resource, Default)]
+pub struct PvpFlagged(pub bool);
+
+/// Exists while a duel request is waiting for an answer.
+#[derive(Resource)]
+pub struct PendingDuelRequest {
+    received_at: f64,
+}
+
+/// Marker for the duel request dialog entity.
+#[derive(Component)]
+struct DuelRequestDialog;
+
+fn actor_name(
+    actor_id: u32,
+    network_id_mapping: &NetworkIdMapping,
+    names: &Query<&NameComponent>,
+) -> String {
+    network_id_mapping
+        .0
+        .get(&NetworkId(actor_id))
+        .and_then(|&entity| names.get(entity).ok())
+        .map_or_else(|| "Someone".to_string(), |name| name.0.clone())
+}
+
+pub(super) fn push_system_message(chat_log: &mut ChatLog, text: String) {
+    chat_log.push(ChatMessage {
+        channel: ChatMessageChannel::System,
+        sender: String::new(),
+        text,
+    });
+}
+
+pub fn handle_duel_requested(
+    mut reader: MessageReader<DuelRequestedMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    names: Query<&NameComponent>,
+    mut chat_log: ResMut<ChatLog>,
+    time: Res<Time<Real>>,
+    existing_dialog: Query<Entity, With<DuelRequestDialog>>,
+    mut commands: Commands,
+) {
+    for msg in reader.read() {
+        let name = actor_name(msg.challenger_id, &network_id_mapping, &names);
+        let message = format!("{name} has challenged you to a duel");
+        push_system_message(&mut chat_log, message.clone());
+
+        widgets::despawn_dialog(&mut commands, &existing_dialog);
+        commands.insert_resource(PendingDuelRequest {
+            received_at: time.elapsed_secs_f64(),
+        });
+
+        let dialog = widgets::spawn_dialog(&mut commands, &message);
+        commands.entity(dialog).insert(DuelRequestDialog);
+
+        let row = commands
+            .spawn((
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(12.0),
+                    ..default()
+                },
+                ChildOf(dialog),
+            ))
+            .id();
+
+        let accept = widgets::spawn_dialog_button(
+            &mut commands,
+            row,
+            "Accept",
+            palette::DIALOG_BUTTON_ACCEPT,
+        );
+        let decline = widgets::spawn_dialog_button(
+            &mut commands,
+            row,
+            "Decline",
+            palette::DIALOG_BUTTON_DECLINE,
+        );
+
+        commands.entity(accept).observe(on_accept_click);
+        commands.entity(decline).observe(on_decline_click);
+    }
+}
+
+fn on_accept_click(
+    _event: On<Pointer<Click>>,
+    mut client: ResMut<RenetClient>,
+    dialog_q: Query<Entity, With<DuelRequestDialog>>,
+    mut commands: Commands,
+) {
+    let encoded = bitcode::encode(&PlayerAction::AcceptDuel);
+    client.send_message(DefaultChannel::ReliableOrdered, encoded);
+    commands.remove_resource::<PendingDuelRequest>();
+    widgets::despawn_dialog(&mut commands, &dialog_q);
+}
+
+/// Declined requests are left to expire on the server.
+fn on_decline_click(
+    _event: On<Pointer<Click>>,
+    dialog_q: Query<Entity, With<DuelRequestDialog>>,
+    mut commands: Commands,
+) {
+    commands.remove_resource::<PendingDuelRequest>();
+    widgets::despawn_dialog(&mut commands, &dialog_q);
+}
+
+pub fn dismiss_expired_duel_request(
+    request: Res<PendingDuelRequest>,
+    time: Res<Time<Real>>,
+    dialog_q: Query<Entity, With<DuelRequestDialog>>,
+    mut commands: Commands,
+    mut chat_log: ResMut<ChatLog>,
+) {
+    if time.elapsed_secs_f64() - request.received_at < DUEL_REQUEST_TIMEOUT_SECS {
+        return;
+    }
+
+    push_system_message(&mut chat_log, "Duel request expired".to_string());
+    commands.remove_resource::<PendingDuelRequest>();
+    widgets::despawn_dialog(&mut commands, &dialog_q);
+}
+
+pub fn handle_duel_starting(
+    mut reader: MessageReader<DuelStartingMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    names: Query<&NameComponent>,
+    mut chat_log: ResMut<ChatLog>,
+) {
+    for msg in reader.read() {
+        let name = actor_name(msg.opponent_id, &network_id_mapping, &names);
+        push_system_message(
+            &mut chat_log,
+            format!(
+                "Duel with {name} starts in {} seconds",
+                msg.countdown.round()
+            ),
+        );
+    }
+}
+
+pub fn handle_duel_ended(
+    mut reader: MessageReader<DuelEndedMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    names: Query<&NameComponent>,
+    mut chat_log: ResMut<ChatLog>,
+) {
+    for msg in reader.read() {
+        let winner = actor_name(msg.winner_id, &network_id_mapping, &names);
+        let loser = actor_name(msg.loser_id, &network_id_mapping, &names);
+        let text = match msg.reason {
+            DuelEndReason::Defeated => format!("{winner} has defeated {loser} in a duel"),
+            DuelEndReason::LeftBoundary => format!("{loser} has fled from {winner} in a duel"),
+            DuelEndReason::Forfeited => format!("{loser} has forfeited a duel against {winner}"),
+        };
+        push_system_message(&mut chat_log, text);
+    }
+}
+
+pub fn handle_pvp_flag_changed(
+    mut reader: MessageReader<PvpFlagChangedMessage>,
+    mut flagged: ResMut<PvpFlagged>,
+    mut chat_log: ResMut<ChatLog>,
+) {
+    for msg in reader.read() {
+        flagged.0 = msg.flagged;
+        let text = match (msg.flagged, msg.expires_in) {
+            (true, None) => "You are now flagged for PvP".to_string(),
+            (true, Some(seconds)) => {
+                format!("Your PvP flag drops in {} seconds", seconds.round())
+            }
+            (false, _) => "You are no longer flagged for PvP".to_string(),
+        };
+        push_system_message(&mut chat_log, text);
+    }
+}
+
+/// Stops auto-attacking the selected target once it turns friendly, e.g. when a duel ends.
+/// The server has already stopped the swings.
+pub fn stop_attacking_friendly_target(
+    mut reader: MessageReader<ReactionChangedMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    selected: Res<SelectedTarget>,
+    mut is_attacking: ResMut<IsAttacking>,
+) {
+    for msg in reader.read() {
+        if !msg.reaction.can_attack()
+            && selected.0.is_some()
+            && network_id_mapping.0.get(&NetworkId(msg.actor_id)) == selected.0.as_ref()
+        {
+            is_attacking.0 = false;
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use bevy::picking::events::{Click, Pointer};
+use bevy::prelude::*;
+use bevy_renet::{RenetClient, renet::DefaultChannel};
+use game_core::components::Vitals;
+use protocol::client::PlayerAction;
+
+use crate::core::PlayerComponent;
+use crate::theme::{palette, widgets};
+
+#[derive(Component)]
+pub struct ReleaseSpiritDialog;
+
+/// Shows the release dialog while the local player is dead, and removes it once they are
+/// revived by releasing or a resurrection.
+pub fn manage_release_dialog(
+    q_player: Query<&Vitals, (With<PlayerComponent>, Changed<Vitals>)>,
+    dialog_q: Query<Entity, With<ReleaseSpiritDialog>>,
+    mut commands: Commands,
+) {
+    let Ok(vitals) = q_player.single() else {
+        return;
+    };
+
+    if vitals.hp > 0 {
+        widgets::despawn_dialog(&mut commands, &dialog_q);
+        return;
+    }
+    if !dialog_q.is_empty() {
+        return;
+    }
+
+    let dialog = widgets::spawn_dialog(&mut commands, "You have died");
+    commands.entity(dialog).insert(ReleaseSpiritDialog);
+
+    let release = widgets::spawn_dialog_button(
+        &mut commands,
+        dialog,
+        "Release Spirit",
+        palette::DIALOG_BUTTON_DECLINE,
+    );
+    commands.entity(release).observe(on_release_click);
+}
+
+fn on_release_click(
+    _event: On<Pointer<Click>>,
+    mut client: ResMut<RenetClient>,
+    dialog_q: Query<Entity, With<ReleaseSpiritDialog>>,
+    mut commands: Commands,
+) {
+    let encoded = bitcode::encode(&PlayerAction::ReleaseSpirit);
+    client.send_message(DefaultChannel::ReliableOrdered, encoded);
+    widgets::despawn_dialog(&mut commands, &dialog_q);
+}
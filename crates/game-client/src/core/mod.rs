@@ -12,6 +12,47 @@ pub struct PlayerComponent;
 #[derive(Component)]
 pub struct NameComponent(pub String);
 
+#[derive(Component)]
+pub struct GuildComponent(pub String);
+
+/// Reaction of the local player's character towards the actor, as sent by the server.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ReactionComponent(pub protocol::models::Reaction);
+
+/// Network id of the player that summoned the pet.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct PetOwner(pub game_core::networking::NetworkId);
+
+/// Present while the server reports the actor as in combat.
+#[derive(Component)]
+pub struct InCombat;
+
+#[derive(Debug, Clone)]
+pub struct AuraInstance {
+    pub aura_id: u32,
+    pub stacks: u8,
+    pub timer: Timer,
+}
+
+/// Auras on an actor as replicated by the server, timers only drive the UI.
+#[derive(Component, Debug, Clone, Default)]
+pub struct Auras(pub Vec<AuraInstance>);
+
+impl From<&[protocol::models::ActorAura]> for Auras {
+    fn from(value: &[protocol::models::ActorAura]) -> Self {
+        Self(
+            value
+                .iter()
+                .map(|aura| AuraInstance {
+                    aura_id: aura.aura_id,
+                    stacks: aura.stacks,
+                    timer: Timer::from_seconds(aura.remaining, TimerMode::Once),
+                })
+                .collect(),
+        )
+    }
+}
+
 #[derive(Bundle)]
 pub struct ActorBundle {
     name: NameComponent,
@@ -19,6 +60,7 @@ pub struct ActorBundle {
     vitals: Vitals,
     movement_speed: MovementSpeedComponent,
     level: LevelComponent,
+    auras: Auras,
     body: RigidBody,
     collider: Collider,
     collision_layers: CollisionLayers,
@@ -32,6 +74,7 @@ impl ActorBundle {
             vitals,
             movement_speed: MovementSpeedComponent(BASE_MOVEMENT_SPEED),
             level: LevelComponent(level),
+            auras: Auras::default(),
             body: RigidBody::Kinematic,
             collider: Collider::capsule(ACTOR_COLLIDER_RADIUS, ACTOR_COLLIDER_LENGTH),
             collision_layers: CollisionLayers::new(
@@ -7,6 +7,7 @@ use bevy::prelude::*;
 use bevy_enhanced_input::prelude::*;
 use game_core::character_controller::{self, CharacterVelocityY, FIXED_DT_SECS};
 use game_core::components::{GroundedComponent, MovementSpeedComponent};
+use game_core::crowd_control::CrowdControlState;
 use game_core::movement::MoveInput;
 use protocol::client::MoveAction;
 use std::collections::VecDeque;
@@ -105,6 +106,7 @@ pub fn predict_player_movement(
             &MovementSpeedComponent,
             &mut CharacterVelocityY,
             &mut PredictionHistory,
+            Option<&CrowdControlState>,
         ),
         With<PlayerComponent>,
     >,
@@ -112,7 +114,7 @@ pub fn predict_player_movement(
 ) {
     let current_tick = tick_sync.tick;
 
-    let Ok((entity, mut transform, collider, speed, mut vel_y, mut history)) =
+    let Ok((entity, mut transform, collider, speed, mut vel_y, mut history, crowd_control)) =
         q_player.single_mut()
     else {
         return;
@@ -129,6 +131,11 @@ pub fn predict_player_movement(
         movement_value.y = movement_value.y.max(1.0);
     }
 
+    // Stunned, rooted and feared players can't move themselves, the server moves feared ones
+    if crowd_control.is_some_and(|state| !state.can_move()) {
+        movement_value = Vec2::ZERO;
+    }
+
     let yaw_rad = cam
         .map(|c| c.yaw)
         .unwrap_or_else(|| transform.rotation.to_euler(EulerRot::YXZ).1);
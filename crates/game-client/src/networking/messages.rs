@@ -1,11 +1,24 @@
 use bevy::prelude::*;
 use game_core::networking::NetworkId;
+use protocol::models::{DamageSchool, HitResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombatHitKind {
+    Damage(HitResult),
+    Heal,
+    Immune,
+}
 
 /// Internal message for combat hit feedback, emitted by network event handling.
 #[derive(Message)]
 pub struct CombatHitMessage {
     pub target_entity: Entity,
+    pub kind: CombatHitKind,
     pub amount: i32,
+    /// Physical for heals and immunities
+    pub school: DamageSchool,
+    pub absorbed: i32,
+    pub resisted: i32,
 }
 
 #[derive(Message)]
@@ -18,18 +31,154 @@ pub struct ActorDespawnMessage(pub NetworkId);
 pub struct SpellImpactMessage {
     pub target_id: u32,
     pub spell_id: u32,
-    pub impact_amount: i32,
+    pub impact: protocol::models::Impact,
+}
+
+#[derive(Message)]
+pub struct CastFailedMessage {
+    pub spell_id: u32,
+    pub reason: protocol::models::CastFailedReason,
+}
+
+#[derive(Message)]
+pub struct ProjectileLaunchedMessage {
+    pub caster_id: u32,
+    pub target_id: u32,
+    pub spell_id: u32,
+    pub travel_time: f32,
+}
+
+#[derive(Message)]
+pub struct AreaEffectMessage {
+    pub caster_id: u32,
+    pub spell_id: u32,
+    pub center: Vec3,
+    pub shape: protocol::models::AreaShape,
 }
 
 #[derive(Message)]
 pub struct ActorDeathMessage(pub u32);
 
+#[derive(Message)]
+pub struct ActorRevivedMessage {
+    pub actor_id: u32,
+    pub vitals: protocol::models::Vitals,
+}
+
+#[derive(Message)]
+pub struct ActorGuildChangedMessage {
+    pub actor_id: u32,
+    pub guild_name: Option<String>,
+}
+
 #[derive(Message)]
 pub struct StartCastingMessage {
     pub actor_id: u32,
     pub spell_id: u32,
+    pub duration: f32,
+}
+
+#[derive(Message)]
+pub struct CastPushbackMessage {
+    pub actor_id: u32,
+    pub remaining: f32,
 }
 
+#[derive(Message)]
+pub struct CooldownStartedMessage {
+    pub spell_id: u32,
+    pub duration: f32,
+}
+
+#[derive(Message)]
+pub struct AuraAppliedMessage {
+    pub actor_id: u32,
+    pub aura_id: u32,
+    pub stacks: u8,
+    pub duration: f32,
+}
+
+#[derive(Message)]
+pub struct AuraRemovedMessage {
+    pub actor_id: u32,
+    pub aura_id: u32,
+}
+
+#[derive(Message)]
+pub struct MovementSpeedChangedMessage {
+    pub actor_id: u32,
+    pub movement_speed: f32,
+}
+
+#[derive(Message)]
+pub struct VitalsChangedMessage {
+    pub actor_id: u32,
+    pub vitals: protocol::models::Vitals,
+}
+
+#[derive(Message)]
+pub struct PowerChangedMessage {
+    pub actor_id: u32,
+    pub power: protocol::models::Power,
+}
+
+#[derive(Message)]
+pub struct CombatStateChangedMessage {
+    pub actor_id: u32,
+    pub in_combat: bool,
+}
+
+#[derive(Message)]
+pub struct CrowdControlChangedMessage {
+    pub actor_id: u32,
+    pub state: protocol::models::CrowdControlState,
+}
+
+#[derive(Message)]
+pub struct ReactionChangedMessage {
+    pub actor_id: u32,
+    pub reaction: protocol::models::Reaction,
+}
+
+#[derive(Message)]
+pub struct DuelRequestedMessage {
+    pub challenger_id: u32,
+}
+
+#[derive(Message)]
+pub struct DuelStartingMessage {
+    pub opponent_id: u32,
+    pub countdown: f32,
+}
+
+#[derive(Message)]
+pub struct DuelEndedMessage {
+    pub winner_id: u32,
+    pub loser_id: u32,
+    pub reason: protocol::models::DuelEndReason,
+}
+
+#[derive(Message)]
+pub struct EncounterStartedMessage {
+    pub name: String,
+}
+
+#[derive(Message)]
+pub struct EncounterEndedMessage {
+    pub name: String,
+    pub outcome: protocol::models::EncounterOutcome,
+}
+
+#[derive(Message)]
+pub struct PvpFlagChangedMessage {
+    pub flagged: bool,
+    pub expires_in: Option<f32>,
+}
+
+/// One server combat log entry, the server batches them per tick.
+#[derive(Message)]
+pub struct CombatLogMessage(pub protocol::models::CombatLogEntry);
+
 #[derive(Message)]
 pub struct KillRewardMessage {
     pub victim_id: u32,
@@ -23,8 +23,30 @@ impl Plugin for NetworkingPlugin {
         app.add_message::<ActorDespawnMessage>();
         app.add_message::<CombatHitMessage>();
         app.add_message::<SpellImpactMessage>();
+        app.add_message::<CastFailedMessage>();
+        app.add_message::<ProjectileLaunchedMessage>();
+        app.add_message::<AreaEffectMessage>();
         app.add_message::<ActorDeathMessage>();
+        app.add_message::<ActorRevivedMessage>();
+        app.add_message::<ActorGuildChangedMessage>();
         app.add_message::<StartCastingMessage>();
+        app.add_message::<CastPushbackMessage>();
+        app.add_message::<CooldownStartedMessage>();
+        app.add_message::<AuraAppliedMessage>();
+        app.add_message::<AuraRemovedMessage>();
+        app.add_message::<MovementSpeedChangedMessage>();
+        app.add_message::<VitalsChangedMessage>();
+        app.add_message::<PowerChangedMessage>();
+        app.add_message::<CombatStateChangedMessage>();
+        app.add_message::<CrowdControlChangedMessage>();
+        app.add_message::<ReactionChangedMessage>();
+        app.add_message::<DuelRequestedMessage>();
+        app.add_message::<DuelStartingMessage>();
+        app.add_message::<DuelEndedMessage>();
+        app.add_message::<PvpFlagChangedMessage>();
+        app.add_message::<EncounterStartedMessage>();
+        app.add_message::<EncounterEndedMessage>();
+        app.add_message::<CombatLogMessage>();
         app.add_message::<KillRewardMessage>();
         app.add_message::<ServerChatMessage>();
 
@@ -48,6 +70,7 @@ impl Plugin for NetworkingPlugin {
                 receive::handle_spell_impacts,
                 receive::handle_actor_deaths,
                 receive::handle_start_casting,
+                receive::handle_cast_pushback,
                 receive::handle_kill_rewards,
                 receive::handle_server_chat,
             )
@@ -13,15 +13,40 @@ use game_core::{
     networking::{NetworkId, NetworkIdMapping},
     spells::{SpellLibrary, SpellLibraryHandle},
 };
-use protocol::server::{EnterGameResponse, ServerEvent};
+use protocol::{
+    models::{DamageSchool, Impact},
+    server::{EnterGameResponse, ServerEvent},
+};
 
 #[derive(SystemParam)]
 pub struct NetworkMessageWriters<'w> {
     pub spawns: MessageWriter<'w, ActorSpawnMessage>,
     pub despawns: MessageWriter<'w, ActorDespawnMessage>,
     pub spell_impacts: MessageWriter<'w, SpellImpactMessage>,
+    pub cast_failures: MessageWriter<'w, CastFailedMessage>,
+    pub projectiles: MessageWriter<'w, ProjectileLaunchedMessage>,
+    pub area_effects: MessageWriter<'w, AreaEffectMessage>,
     pub deaths: MessageWriter<'w, ActorDeathMessage>,
+    pub revivals: MessageWriter<'w, ActorRevivedMessage>,
+    pub guild_changes: MessageWriter<'w, ActorGuildChangedMessage>,
     pub casts: MessageWriter<'w, StartCastingMessage>,
+    pub cast_pushbacks: MessageWriter<'w, CastPushbackMessage>,
+    pub cooldowns: MessageWriter<'w, CooldownStartedMessage>,
+    pub auras_applied: MessageWriter<'w, AuraAppliedMessage>,
+    pub auras_removed: MessageWriter<'w, AuraRemovedMessage>,
+    pub speed_changes: MessageWriter<'w, MovementSpeedChangedMessage>,
+    pub vitals_changes: MessageWriter<'w, VitalsChangedMessage>,
+    pub power_changes: MessageWriter<'w, PowerChangedMessage>,
+    pub combat_state_changes: MessageWriter<'w, CombatStateChangedMessage>,
+    pub crowd_control_changes: MessageWriter<'w, CrowdControlChangedMessage>,
+    pub reaction_changes: MessageWriter<'w, ReactionChangedMessage>,
+    pub duel_requests: MessageWriter<'w, DuelRequestedMessage>,
+    pub duel_starts: MessageWriter<'w, DuelStartingMessage>,
+    pub duel_ends: MessageWriter<'w, DuelEndedMessage>,
+    pub pvp_flag_changes: MessageWriter<'w, PvpFlagChangedMessage>,
+    pub encounter_starts: MessageWriter<'w, EncounterStartedMessage>,
+    pub encounter_ends: MessageWriter<'w, EncounterEndedMessage>,
+    pub combat_log: MessageWriter<'w, CombatLogMessage>,
     pub kill_rewards: MessageWriter<'w, KillRewardMessage>,
     pub chats: MessageWriter<'w, ServerChatMessage>,
 }
@@ -62,21 +87,196 @@ pub fn receive_server_events(
                 ServerEvent::SpellImpact {
                     target_id,
                     spell_id,
-                    impact_amount,
+                    impact,
                 } => {
                     writers.spell_impacts.write(SpellImpactMessage {
                         target_id,
                         spell_id,
-                        impact_amount,
+                        impact,
+                    });
+                }
+                ServerEvent::CastFailed { spell_id, reason } => {
+                    writers
+                        .cast_failures
+                        .write(CastFailedMessage { spell_id, reason });
+                }
+                ServerEvent::ProjectileLaunched {
+                    caster_id,
+                    target_id,
+                    spell_id,
+                    travel_time,
+                } => {
+                    writers.projectiles.write(ProjectileLaunchedMessage {
+                        caster_id,
+                        target_id,
+                        spell_id,
+                        travel_time,
+                    });
+                }
+                ServerEvent::AreaEffect {
+                    caster_id,
+                    spell_id,
+                    center,
+                    shape,
+                } => {
+                    writers.area_effects.write(AreaEffectMessage {
+                        caster_id,
+                        spell_id,
+                        center,
+                        shape,
                     });
                 }
                 ServerEvent::ActorDeath(id) => {
                     writers.deaths.write(ActorDeathMessage(id));
                 }
-                ServerEvent::StartCasting { actor_id, spell_id } => {
+                ServerEvent::ActorRevived { actor_id, vitals } => {
+                    writers
+                        .revivals
+                        .write(ActorRevivedMessage { actor_id, vitals });
+                }
+                ServerEvent::ActorGuildChanged {
+                    actor_id,
+                    guild_name,
+                } => {
+                    writers.guild_changes.write(ActorGuildChangedMessage {
+                        actor_id,
+                        guild_name,
+                    });
+                }
+                ServerEvent::StartCasting {
+                    actor_id,
+                    spell_id,
+                    duration,
+                } => {
+                    writers.casts.write(StartCastingMessage {
+                        actor_id,
+                        spell_id,
+                        duration,
+                    });
+                }
+                ServerEvent::CastPushback {
+                    actor_id,
+                    remaining,
+                } => {
+                    writers.cast_pushbacks.write(CastPushbackMessage {
+                        actor_id,
+                        remaining,
+                    });
+                }
+                ServerEvent::CooldownStarted { spell_id, duration } => {
+                    writers
+                        .cooldowns
+                        .write(CooldownStartedMessage { spell_id, duration });
+                }
+                ServerEvent::AuraApplied {
+                    actor_id,
+                    aura_id,
+                    stacks,
+                    duration,
+                } => {
+                    writers.auras_applied.write(AuraAppliedMessage {
+                        actor_id,
+                        aura_id,
+                        stacks,
+                        duration,
+                    });
+                }
+                ServerEvent::AuraRemoved { actor_id, aura_id } => {
+                    writers
+                        .auras_removed
+                        .write(AuraRemovedMessage { actor_id, aura_id });
+                }
+                ServerEvent::MovementSpeedChanged {
+                    actor_id,
+                    movement_speed,
+                } => {
+                    writers.speed_changes.write(MovementSpeedChangedMessage {
+                        actor_id,
+                        movement_speed: movement_speed.into(),
+                    });
+                }
+                ServerEvent::VitalsChanged { actor_id, vitals } => {
+                    writers
+                        .vitals_changes
+                        .write(VitalsChangedMessage { actor_id, vitals });
+                }
+                ServerEvent::PowerChanged { actor_id, power } => {
+                    writers
+                        .power_changes
+                        .write(PowerChangedMessage { actor_id, power });
+                }
+                ServerEvent::CombatStateChanged {
+                    actor_id,
+                    in_combat,
+                } => {
+                    writers
+                        .combat_state_changes
+                        .write(CombatStateChangedMessage {
+                            actor_id,
+                            in_combat,
+                        });
+                }
+                ServerEvent::CrowdControlChanged { actor_id, state } => {
+                    writers
+                        .crowd_control_changes
+                        .write(CrowdControlChangedMessage { actor_id, state });
+                }
+                ServerEvent::ReactionChanged { actor_id, reaction } => {
+                    writers
+                        .reaction_changes
+                        .write(ReactionChangedMessage { actor_id, reaction });
+                }
+                ServerEvent::DuelRequested { challenger_id } => {
+                    writers
+                        .duel_requests
+                        .write(DuelRequestedMessage { challenger_id });
+                }
+                // The boundary is not drawn yet, leaving it is announced through `DuelEnded`
+                ServerEvent::DuelStarting {
+                    opponent_id,
+                    countdown,
+                    ..
+                } => {
+                    writers.duel_starts.write(DuelStartingMessage {
+                        opponent_id,
+                        countdown,
+                    });
+                }
+                ServerEvent::DuelEnded {
+                    winner_id,
+                    loser_id,
+                    reason,
+                } => {
+                    writers.duel_ends.write(DuelEndedMessage {
+                        winner_id,
+                        loser_id,
+                        reason,
+                    });
+                }
+                ServerEvent::PvpFlagChanged {
+                    flagged,
+                    expires_in,
+                } => {
+                    writers.pvp_flag_changes.write(PvpFlagChangedMessage {
+                        flagged,
+                        expires_in,
+                    });
+                }
+                // Boss frames are not drawn yet, the encounter is announced in the chat
+                ServerEvent::EncounterStarted { name, .. } => {
+                    writers
+                        .encounter_starts
+                        .write(EncounterStartedMessage { name });
+                }
+                ServerEvent::EncounterEnded { name, outcome, .. } => {
+                    writers
+                        .encounter_ends
+                        .write(EncounterEndedMessage { name, outcome });
+                }
+                ServerEvent::CombatLog(entries) => {
                     writers
-                        .casts
-                        .write(StartCastingMessage { actor_id, spell_id });
+                        .combat_log
+                        .write_batch(entries.into_iter().map(CombatLogMessage));
                 }
                 ServerEvent::KillReward { victim_id, loot } => {
                     writers
@@ -121,14 +321,57 @@ pub fn handle_spell_impacts(
     mut q_vitals: Query<&mut Vitals>,
 ) {
     for msg in reader.read() {
-        if let Some(&entity) = network_id_mapping.0.get(&NetworkId(msg.target_id))
-            && let Ok(mut vitals) = q_vitals.get_mut(entity)
-        {
-            vitals.hp -= msg.impact_amount;
-            combat_hits.write(CombatHitMessage {
-                target_entity: entity,
-                amount: msg.impact_amount,
-            });
+        let Some(&entity) = network_id_mapping.0.get(&NetworkId(msg.target_id)) else {
+            continue;
+        };
+        let Ok(mut vitals) = q_vitals.get_mut(entity) else {
+            continue;
+        };
+
+        match msg.impact {
+            Impact::Damage {
+                amount,
+                result,
+                school,
+                absorbed,
+                resisted,
+            } => {
+                vitals.hp -= amount;
+                combat_hits.write(CombatHitMessage {
+                    target_entity: entity,
+                    kind: CombatHitKind::Damage(result),
+                    amount,
+                    school,
+                    absorbed,
+                    resisted,
+                });
+            }
+            Impact::Heal { amount } => {
+                vitals.hp = (vitals.hp + amount).min(vitals.max_hp);
+                combat_hits.write(CombatHitMessage {
+                    target_entity: entity,
+                    kind: CombatHitKind::Heal,
+                    amount,
+                    school: DamageSchool::Physical,
+                    absorbed: 0,
+                    resisted: 0,
+                });
+            }
+            Impact::Immune => {
+                combat_hits.write(CombatHitMessage {
+                    target_entity: entity,
+                    kind: CombatHitKind::Immune,
+                    amount: 0,
+                    school: DamageSchool::Physical,
+                    absorbed: 0,
+                    resisted: 0,
+                });
+            }
+            // Displacement arrives through movement updates, aura state through aura events
+            Impact::AuraApplied { .. }
+            | Impact::Dispel { .. }
+            | Impact::Teleport
+            | Impact::Knockback => {}
         }
     }
 }
@@ -160,17 +403,36 @@ pub fn handle_start_casting(
             && q_player.get(entity).is_ok()
             && let Some(library) = spell_libraries.get(&spell_library_handle.0)
             && let Some(spell_def) = library.spells.get(&msg.spell_id)
-            && spell_def.casting_duration > 0.0
+            && msg.duration > 0.0
         {
             commands.insert_resource(ActiveCast {
                 spell_id: msg.spell_id,
                 spell_name: spell_def.name.clone(),
-                timer: Timer::from_seconds(spell_def.casting_duration, TimerMode::Once),
+                timer: Timer::from_seconds(msg.duration, TimerMode::Once),
+                channelled: spell_def.channel.is_some(),
             });
         }
     }
 }
 
+pub fn handle_cast_pushback(
+    mut reader: MessageReader<CastPushbackMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    q_player: Query<&PlayerComponent>,
+    mut active_cast: Option<ResMut<ActiveCast>>,
+) {
+    for msg in reader.read() {
+        if let Some(&entity) = network_id_mapping.0.get(&NetworkId(msg.actor_id))
+            && q_player.get(entity).is_ok()
+            && let Some(cast) = active_cast.as_mut()
+        {
+            let elapsed = (cast.timer.duration().as_secs_f32() - msg.remaining).max(0.0);
+            cast.timer
+                .set_elapsed(std::time::Duration::from_secs_f32(elapsed));
+        }
+    }
+}
+
 pub fn handle_kill_rewards(mut reader: MessageReader<KillRewardMessage>) {
     for _msg in reader.read() {
         // TODO: Show loot notification
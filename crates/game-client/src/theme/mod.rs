@@ -14,7 +14,14 @@ impl Plugin for ThemePlugin {
 
         app.add_systems(
             Update,
-            widgets::update_unit_frames.run_if(in_state(AppState::InGame)),
+            (
+                widgets::update_unit_frames,
+                widgets::update_unit_frame_power,
+                widgets::update_unit_frame_combat_tint,
+                widgets::update_unit_frame_status,
+                widgets::update_unit_frame_name_color,
+            )
+                .run_if(in_state(AppState::InGame)),
         );
     }
 }
@@ -4,7 +4,15 @@ use bevy::prelude::Color;
 pub const HP_GREEN: Color = Color::srgb(0.1, 0.7, 0.1);
 pub const HP_BG: Color = Color::srgb(0.2, 0.0, 0.0);
 pub const FRAME_BG: Color = Color::srgba(0.1, 0.1, 0.1, 0.85);
+pub const FRAME_BG_COMBAT: Color = Color::srgba(0.35, 0.08, 0.08, 0.85);
 pub const LEVEL_COLOR: Color = Color::srgb(0.8, 0.8, 0.2);
+pub const NAME_HOSTILE: Color = Color::srgb(0.9, 0.25, 0.2);
+pub const NAME_NEUTRAL: Color = Color::srgb(0.95, 0.85, 0.2);
+pub const NAME_FRIENDLY: Color = Color::srgb(0.3, 0.85, 0.3);
+pub const CROWD_CONTROL_TEXT: Color = Color::srgb(1.0, 0.55, 0.2);
+pub const MANA_BLUE: Color = Color::srgb(0.15, 0.35, 0.85);
+pub const ENERGY_YELLOW: Color = Color::srgb(0.85, 0.8, 0.15);
+pub const RAGE_RED: Color = Color::srgb(0.8, 0.1, 0.1);
 
 // Panels and overlays
 pub const PANEL_BG: Color = Color::srgba(0.0, 0.0, 0.0, 0.4);
@@ -22,6 +30,10 @@ pub const CAST_BAR_FILL: Color = Color::srgba(0.8, 0.6, 0.1, 0.8);
 
 // Combat feedback
 pub const DAMAGE_TEXT: Color = Color::srgba(1.0, 0.9, 0.1, 1.0);
+pub const HEAL_TEXT: Color = Color::srgba(0.2, 1.0, 0.3, 1.0);
+pub const AVOID_TEXT: Color = Color::srgba(0.8, 0.8, 0.8, 1.0);
+pub const PROJECTILE: Color = Color::srgb(1.0, 0.5, 0.1);
+pub const AREA_EFFECT: Color = Color::srgba(1.0, 0.45, 0.1, 0.35);
 
 // Dialog
 pub const DIALOG_BG: Color = Color::srgba(0.12, 0.12, 0.12, 0.95);
@@ -3,8 +3,11 @@
 use bevy::picking::events::{Out, Over, Pointer};
 use bevy::prelude::*;
 
-use crate::core::NameComponent;
+use crate::core::{InCombat, NameComponent, ReactionComponent};
 use game_core::components::{LevelComponent, Vitals};
+use game_core::crowd_control::CrowdControlState;
+use game_core::power::Power;
+use protocol::models::{PowerKind, Reaction};
 
 use super::palette;
 
@@ -26,10 +29,22 @@ pub(crate) struct UnitFrameName;
 #[derive(Component)]
 pub(crate) struct UnitFrameLevel;
 
+/// Marker for the crowd-control status text within a unit frame.
+#[derive(Component)]
+pub(crate) struct UnitFrameStatus;
+
 /// Marker for the health bar fill node within a unit frame.
 #[derive(Component)]
 pub(crate) struct UnitFrameHealthBar;
 
+/// Marker for the power bar background, hidden for actors without a known power pool.
+#[derive(Component)]
+pub(crate) struct UnitFramePowerBar;
+
+/// Marker for the power bar fill node within a unit frame.
+#[derive(Component)]
+pub(crate) struct UnitFramePowerFill;
+
 /// Positioning and styling options for spawning a unit frame.
 pub struct UnitFrameConfig {
     pub tracked_entity: Entity,
@@ -97,7 +112,7 @@ pub fn spawn_unit_frame(
                 right: config.right,
                 margin: config.margin,
                 width: Val::Px(config.width),
-                height: Val::Px(60.0),
+                height: Val::Px(70.0),
                 flex_direction: FlexDirection::Column,
                 padding: UiRect::all(Val::Px(8.0)),
                 border_radius: BorderRadius::all(Val::Px(4.0)),
@@ -124,6 +139,15 @@ pub fn spawn_unit_frame(
                             ..default()
                         },
                     ));
+                    row.spawn((
+                        UnitFrameStatus,
+                        Text::new(""),
+                        TextColor(palette::CROWD_CONTROL_TEXT),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                    ));
                     row.spawn((
                         UnitFrameLevel,
                         Text::new(format!("Lv. {}", level)),
@@ -158,10 +182,161 @@ pub fn spawn_unit_frame(
                         BackgroundColor(palette::HP_GREEN),
                     ));
                 });
+
+            // Power bar, shown once the tracked entity's power is known
+            parent
+                .spawn((
+                    UnitFramePowerBar,
+                    Node {
+                        display: Display::None,
+                        width: Val::Percent(100.0),
+                        height: Val::Px(6.0),
+                        margin: UiRect::top(Val::Px(4.0)),
+                        border_radius: BorderRadius::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    BackgroundColor(palette::PANEL_BG_DARK),
+                ))
+                .with_children(|bar_bg| {
+                    bar_bg.spawn((
+                        UnitFramePowerFill,
+                        Node {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            border_radius: BorderRadius::all(Val::Px(2.0)),
+                            ..default()
+                        },
+                        BackgroundColor(palette::MANA_BLUE),
+                    ));
+                });
         })
         .id()
 }
 
+/// Fills the power bar of unit frames whose tracked entity has a power pool.
+pub fn update_unit_frame_power(
+    frames: Query<(&UnitFrame, &Children)>,
+    powers: Query<&Power>,
+    mut bars: Query<(&mut Node, &Children), With<UnitFramePowerBar>>,
+    mut fills: Query<
+        (&mut Node, &mut BackgroundColor),
+        (With<UnitFramePowerFill>, Without<UnitFramePowerBar>),
+    >,
+) {
+    for (frame, frame_children) in frames.iter() {
+        let power = powers.get(frame.tracked_entity).ok();
+
+        for child in frame_children.iter() {
+            let Ok((mut bar_node, bar_children)) = bars.get_mut(child) else {
+                continue;
+            };
+            bar_node.display = if power.is_some() {
+                Display::Flex
+            } else {
+                Display::None
+            };
+            let Some(power) = power else {
+                continue;
+            };
+
+            let power_pct = if power.max > 0 {
+                (power.current as f32 / power.max as f32) * 100.0
+            } else {
+                0.0
+            };
+            let color = match power.kind {
+                PowerKind::Mana => palette::MANA_BLUE,
+                PowerKind::Energy => palette::ENERGY_YELLOW,
+                PowerKind::Rage => palette::RAGE_RED,
+            };
+            for fill in bar_children.iter() {
+                if let Ok((mut fill_node, mut fill_color)) = fills.get_mut(fill) {
+                    fill_node.width = Val::Percent(power_pct);
+                    fill_color.0 = color;
+                }
+            }
+        }
+    }
+}
+
+/// Names the strongest crowd-control effect on the tracked entity.
+pub fn update_unit_frame_status(
+    frames: Query<(&UnitFrame, &Children)>,
+    states: Query<&CrowdControlState>,
+    children_query: Query<&Children>,
+    mut status_texts: Query<&mut Text, With<UnitFrameStatus>>,
+) {
+    for (frame, frame_children) in frames.iter() {
+        let status = match states.get(frame.tracked_entity) {
+            Ok(state) if state.stunned => "Stunned",
+            Ok(state) if state.feared => "Feared",
+            Ok(state) if state.rooted => "Rooted",
+            Ok(state) if state.silenced => "Silenced",
+            _ => "",
+        };
+
+        for child in frame_children.iter() {
+            let Ok(grandchildren) = children_query.get(child) else {
+                continue;
+            };
+            for grandchild in grandchildren.iter() {
+                if let Ok(mut text) = status_texts.get_mut(grandchild)
+                    && text.0 != status
+                {
+                    **text = status.to_string();
+                }
+            }
+        }
+    }
+}
+
+/// Colours unit frame names by how the player regards the tracked entity.
+pub fn update_unit_frame_name_color(
+    frames: Query<(&UnitFrame, &Children)>,
+    reactions: Query<&ReactionComponent>,
+    children_query: Query<&Children>,
+    mut name_colors: Query<&mut TextColor, With<UnitFrameName>>,
+) {
+    for (frame, frame_children) in frames.iter() {
+        let color = match reactions.get(frame.tracked_entity) {
+            Ok(ReactionComponent(Reaction::Hostile)) => palette::NAME_HOSTILE,
+            Ok(ReactionComponent(Reaction::Neutral)) => palette::NAME_NEUTRAL,
+            Ok(ReactionComponent(Reaction::Friendly)) => palette::NAME_FRIENDLY,
+            Err(_) => Color::WHITE,
+        };
+
+        for child in frame_children.iter() {
+            let Ok(grandchildren) = children_query.get(child) else {
+                continue;
+            };
+            for grandchild in grandchildren.iter() {
+                if let Ok(mut text_color) = name_colors.get_mut(grandchild)
+                    && text_color.0 != color
+                {
+                    text_color.0 = color;
+                }
+            }
+        }
+    }
+}
+
+/// Tints the background of unit frames whose tracked entity is in combat.
+pub fn update_unit_frame_combat_tint(
+    mut frames: Query<(&UnitFrame, &mut BackgroundColor)>,
+    in_combat: Query<(), With<InCombat>>,
+) {
+    for (frame, mut background) in frames.iter_mut() {
+        let color = if in_combat.contains(frame.tracked_entity) {
+            palette::FRAME_BG_COMBAT
+        } else {
+            palette::FRAME_BG
+        };
+        if background.0 != color {
+            background.0 = color;
+        }
+    }
+}
+
 /// Updates all unit frames to reflect their tracked entity's current stats.
 /// Despawns frames whose tracked entity no longer exists.
 pub fn update_unit_frames(
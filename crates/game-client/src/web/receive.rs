@@ -83,6 +83,17 @@ pub fn receive_social_events(
             web_client::SocialEvent::PartyDisbanded => {
                 writers.party_disbanded.write(PartyDisbandedMessage);
             }
+            web_client::SocialEvent::GuildInvite {
+                from_name,
+                guild_name,
+                ..
+            } => {
+                // No guild invite dialog yet, the invite is answered with /gaccept or /gdecline
+                let text = format!("{from_name} has invited you to join {guild_name}");
+                writers
+                    .system_notifications
+                    .write(SystemNotificationMessage { text });
+            }
         }
     }
 }
@@ -1,12 +1,20 @@
 use avian3d::prelude::*;
 use bevy::prelude::*;
-use game_core::components::Vitals;
+use game_core::components::{MovementSpeedComponent, Vitals};
+use game_core::constants::BASE_MOVEMENT_SPEED;
+use game_core::crowd_control::CrowdControlState;
 use game_core::networking::{NetworkId, NetworkIdMapping};
+use game_core::power::Power;
+use protocol::models::Reaction;
 
 use super::DebugActorMesh;
-use crate::core::ActorBundle;
+use crate::core::{ActorBundle, Auras, GuildComponent, InCombat, PetOwner, ReactionComponent};
 use crate::movement::RemoteInterpolation;
-use crate::networking::{ActorDespawnMessage, ActorSpawnMessage};
+use crate::networking::{
+    ActorDespawnMessage, ActorGuildChangedMessage, ActorRevivedMessage, ActorSpawnMessage,
+    CombatStateChangedMessage, CrowdControlChangedMessage, MovementSpeedChangedMessage,
+    PowerChangedMessage, ReactionChangedMessage, VitalsChangedMessage,
+};
 
 pub fn handle_actor_spawn_messages(
     mut reader: MessageReader<ActorSpawnMessage>,
@@ -20,16 +28,17 @@ pub fn handle_actor_spawn_messages(
         let transform = Transform::from_translation(actor.transform.position)
             .with_rotation(actor.transform.get_quat());
 
-        let base_color = match &actor.attributes {
-            protocol::models::ActorAttributes::Player { .. } => Color::srgb(0.2, 0.4, 0.8),
-            protocol::models::ActorAttributes::Npc { .. } => Color::srgb(0.7, 0.2, 0.2),
+        let base_color = match actor.reaction {
+            Reaction::Hostile => Color::srgb(0.7, 0.2, 0.2),
+            Reaction::Neutral => Color::srgb(0.7, 0.6, 0.2),
+            Reaction::Friendly => Color::srgb(0.2, 0.4, 0.8),
         };
         let remote_material = materials.add(StandardMaterial {
             base_color,
             ..default()
         });
 
-        let entity = commands.spawn((
+        let mut entity = commands.spawn((
             RemoteInterpolation::default(),
             NoTransformEasing,
             NetworkId(actor.id),
@@ -42,6 +51,25 @@ pub fn handle_actor_spawn_messages(
             Mesh3d(debug_mesh.0.clone()),
             MeshMaterial3d(remote_material),
         ));
+        entity.insert((
+            MovementSpeedComponent(actor.movement_speed.into()),
+            Auras::from(actor.auras.as_slice()),
+            CrowdControlState::from(actor.crowd_control),
+            ReactionComponent(actor.reaction),
+        ));
+        if let protocol::models::ActorAttributes::Player {
+            guild_name: Some(guild_name),
+            ..
+        } = &actor.attributes
+        {
+            entity.insert(GuildComponent(guild_name.clone()));
+        }
+        if actor.in_combat {
+            entity.insert(InCombat);
+        }
+        if let Some(owner_id) = actor.owner_id {
+            entity.insert(PetOwner(NetworkId(owner_id)));
+        }
         network_id_mapping
             .0
             .insert(NetworkId(actor.id), entity.id());
@@ -63,3 +91,132 @@ pub fn handle_actor_despawn_messages(
         network_id_mapping.0.remove(&message.0);
     }
 }
+
+pub fn handle_actor_guild_changed_messages(
+    mut reader: MessageReader<ActorGuildChangedMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    mut commands: Commands,
+) {
+    for message in reader.read() {
+        let Some(&entity) = network_id_mapping.0.get(&NetworkId(message.actor_id)) else {
+            continue;
+        };
+
+        match &message.guild_name {
+            Some(guild_name) => {
+                commands
+                    .entity(entity)
+                    .insert(GuildComponent(guild_name.clone()));
+            }
+            None => {
+                commands.entity(entity).remove::<GuildComponent>();
+            }
+        }
+    }
+}
+
+pub fn handle_movement_speed_changed_messages(
+    mut reader: MessageReader<MovementSpeedChangedMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    mut q_speed: Query<&mut MovementSpeedComponent>,
+) {
+    for message in reader.read() {
+        if let Some(&entity) = network_id_mapping.0.get(&NetworkId(message.actor_id))
+            && let Ok(mut movement_speed) = q_speed.get_mut(entity)
+        {
+            movement_speed.0 = message.movement_speed;
+        }
+    }
+}
+
+pub fn handle_vitals_changed_messages(
+    mut reader: MessageReader<VitalsChangedMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    mut q_vitals: Query<&mut Vitals>,
+) {
+    for message in reader.read() {
+        if let Some(&entity) = network_id_mapping.0.get(&NetworkId(message.actor_id))
+            && let Ok(mut vitals) = q_vitals.get_mut(entity)
+        {
+            *vitals = message.vitals.clone().into();
+        }
+    }
+}
+
+/// Power is only replicated for the local player and party members, so the component is
+/// inserted on the first update.
+pub fn handle_power_changed_messages(
+    mut reader: MessageReader<PowerChangedMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    mut commands: Commands,
+) {
+    for message in reader.read() {
+        if let Some(&entity) = network_id_mapping.0.get(&NetworkId(message.actor_id)) {
+            commands
+                .entity(entity)
+                .try_insert(Power::from(message.power.clone()));
+        }
+    }
+}
+
+pub fn handle_combat_state_changed_messages(
+    mut reader: MessageReader<CombatStateChangedMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    mut commands: Commands,
+) {
+    for message in reader.read() {
+        let Some(&entity) = network_id_mapping.0.get(&NetworkId(message.actor_id)) else {
+            continue;
+        };
+
+        if message.in_combat {
+            commands.entity(entity).try_insert(InCombat);
+        } else {
+            commands.entity(entity).try_remove::<InCombat>();
+        }
+    }
+}
+
+pub fn handle_reaction_changed_messages(
+    mut reader: MessageReader<ReactionChangedMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    mut commands: Commands,
+) {
+    for message in reader.read() {
+        if let Some(&entity) = network_id_mapping.0.get(&NetworkId(message.actor_id)) {
+            commands
+                .entity(entity)
+                .try_insert(ReactionComponent(message.reaction));
+        }
+    }
+}
+
+pub fn handle_crowd_control_changed_messages(
+    mut reader: MessageReader<CrowdControlChangedMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    mut commands: Commands,
+) {
+    for message in reader.read() {
+        if let Some(&entity) = network_id_mapping.0.get(&NetworkId(message.actor_id)) {
+            commands
+                .entity(entity)
+                .try_insert(CrowdControlState::from(message.state));
+        }
+    }
+}
+
+/// Revived actors come back at base movement speed, their auras were dropped on death.
+pub fn handle_actor_revived_messages(
+    mut reader: MessageReader<ActorRevivedMessage>,
+    network_id_mapping: Res<NetworkIdMapping>,
+    mut q_actors: Query<(&mut Vitals, &mut MovementSpeedComponent)>,
+) {
+    for message in reader.read() {
+        if let Some(&entity) = network_id_mapping.0.get(&NetworkId(message.actor_id))
+            && let Ok((mut vitals, mut movement_speed)) = q_actors.get_mut(entity)
+        {
+            *vitals = message.vitals.clone().into();
+            movement_speed.0 = BASE_MOVEMENT_SPEED;
+        }
+    }
+}
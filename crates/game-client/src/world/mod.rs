@@ -55,6 +55,14 @@ impl Plugin for WorldPlugin {
             (
                 actors::handle_actor_spawn_messages,
                 actors::handle_actor_despawn_messages,
+                actors::handle_actor_guild_changed_messages,
+                actors::handle_movement_speed_changed_messages,
+                actors::handle_vitals_changed_messages,
+                actors::handle_actor_revived_messages,
+                actors::handle_power_changed_messages,
+                actors::handle_combat_state_changed_messages,
+                actors::handle_crowd_control_changed_messages,
+                actors::handle_reaction_changed_messages,
             )
                 .after(NetworkingSet::Receive)
                 .run_if(in_state(AppState::InGame)),
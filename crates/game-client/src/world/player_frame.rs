@@ -1,7 +1,10 @@
 use bevy::picking::events::{Click, Pointer};
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
+use bevy_renet::{RenetClient, renet::DefaultChannel};
+use protocol::client::PlayerAction;
 
+use crate::combat::PvpFlagged;
 use crate::core::{NameComponent, PlayerComponent};
 use crate::web::SocialSender;
 use crate::theme::widgets::{self, ContextMenu, UnitFrameConfig};
@@ -40,6 +43,7 @@ pub(crate) fn handle_player_context_menu(
     windows: Query<&Window, With<PrimaryWindow>>,
     unit_frame_interaction: Query<&Interaction, With<PlayerUnitFrame>>,
     existing_menu: Query<Entity, With<ContextMenu>>,
+    pvp_flagged: Res<PvpFlagged>,
     mut commands: Commands,
 ) {
     if !mouse_button.just_pressed(MouseButton::Right) {
@@ -68,6 +72,14 @@ pub(crate) fn handle_player_context_menu(
         widgets::spawn_context_menu_button(&mut commands, menu_entity, "Leave Party");
     commands.entity(leave_party_btn).observe(on_leave_party_click);
 
+    let pvp_label = if pvp_flagged.0 {
+        "Disable PvP"
+    } else {
+        "Enable PvP"
+    };
+    let pvp_btn = widgets::spawn_context_menu_button(&mut commands, menu_entity, pvp_label);
+    commands.entity(pvp_btn).observe(on_toggle_pvp_click);
+
     let logout_btn = widgets::spawn_context_menu_button(&mut commands, menu_entity, "Logout");
     commands.entity(logout_btn).observe(on_logout_click);
 }
@@ -89,6 +101,21 @@ fn on_leave_party_click(
     widgets::despawn_context_menu(&mut commands, &context_menu_q);
 }
 
+fn on_toggle_pvp_click(
+    _event: On<Pointer<Click>>,
+    pvp_flagged: Res<PvpFlagged>,
+    mut client: ResMut<RenetClient>,
+    context_menu_q: Query<Entity, With<ContextMenu>>,
+    mut commands: Commands,
+) {
+    let action = PlayerAction::SetPvpFlag {
+        enabled: !pvp_flagged.0,
+    };
+    client.send_message(DefaultChannel::ReliableOrdered, bitcode::encode(&action));
+
+    widgets::despawn_context_menu(&mut commands, &context_menu_q);
+}
+
 fn on_logout_click(
     _event: On<Pointer<Click>>,
     context_menu_q: Query<Entity, With<ContextMenu>>,
@@ -1,6 +1,7 @@
-use avian3d::prelude::SpatialQuery;
+use avian3d::prelude::{SpatialQuery, SpatialQueryFilter};
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
+use game_core::collision::GameLayer;
 
 use crate::combat::AttackTarget;
 use crate::combat::IsAttacking;
@@ -130,6 +131,23 @@ fn raycast_remote_actor(
     remote_actors.contains(hit.entity).then_some(hit.entity)
 }
 
+/// World position of the terrain under the cursor, used for ground-targeted spells.
+pub fn raycast_ground(
+    window: &Window,
+    cameras: &Query<(&Camera, &GlobalTransform)>,
+    spatial_query: &SpatialQuery,
+) -> Option<Vec3> {
+    let cursor_pos = window.cursor_position()?;
+    let (camera, camera_transform) = cameras.single().ok()?;
+    let ray = camera
+        .viewport_to_world(camera_transform, cursor_pos)
+        .ok()?;
+
+    let filter = SpatialQueryFilter::default().with_mask([GameLayer::Default, GameLayer::Ground]);
+    let hit = spatial_query.cast_ray(ray.origin, ray.direction, 100.0, true, &filter)?;
+    Some(ray.get_point(hit.distance))
+}
+
 pub fn clear_despawned_target(
     mut selected: ResMut<SelectedTarget>,
     mut is_attacking: ResMut<IsAttacking>,
@@ -1,12 +1,16 @@
 use bevy::picking::events::{Click, Pointer};
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
+use bevy_renet::{RenetClient, renet::DefaultChannel};
+use protocol::client::PlayerAction;
+use protocol::models::PetStance;
 
-use crate::core::NameComponent;
+use crate::core::{NameComponent, PetOwner, PlayerComponent};
 use crate::web::SocialSender;
 use crate::theme::widgets::{self, ContextMenu, UnitFrame, UnitFrameConfig};
 use super::selection::SelectedTarget;
 use game_core::components::{LevelComponent, Vitals};
+use game_core::networking::NetworkId;
 
 #[derive(Component)]
 pub(crate) struct TargetUnitFrame;
@@ -68,12 +72,15 @@ pub(crate) fn sync_target_unit_frame(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn handle_target_context_menu(
     mouse_button: Res<ButtonInput<MouseButton>>,
     windows: Query<&Window, With<PrimaryWindow>>,
     selected: Res<SelectedTarget>,
     unit_frame_interaction: Query<&Interaction, With<TargetUnitFrame>>,
     existing_menu: Query<Entity, With<ContextMenu>>,
+    player: Query<&NetworkId, With<PlayerComponent>>,
+    pets: Query<&PetOwner>,
     mut commands: Commands,
 ) {
     if !mouse_button.just_pressed(MouseButton::Right) {
@@ -82,9 +89,9 @@ pub(crate) fn handle_target_context_menu(
 
     widgets::despawn_context_menu(&mut commands, &existing_menu);
 
-    if selected.0.is_none() {
+    let Some(target_entity) = selected.0 else {
         return;
-    }
+    };
 
     let is_hovering = unit_frame_interaction
         .iter()
@@ -100,10 +107,38 @@ pub(crate) fn handle_target_context_menu(
         return;
     };
 
+    let player_id = player.single().ok().copied();
+    let owns_pet = pets.iter().any(|owner| Some(owner.0) == player_id);
     let menu_entity = widgets::spawn_context_menu(&mut commands, cursor_pos);
-    let button =
-        widgets::spawn_context_menu_button(&mut commands, menu_entity, "Invite to Party");
+
+    // Right-clicking your own pet opens its controls instead
+    if let Ok(owner) = pets.get(target_entity)
+        && Some(owner.0) == player_id
+    {
+        let passive = widgets::spawn_context_menu_button(&mut commands, menu_entity, "Passive");
+        commands.entity(passive).observe(on_pet_passive_click);
+        let defensive = widgets::spawn_context_menu_button(&mut commands, menu_entity, "Defensive");
+        commands.entity(defensive).observe(on_pet_defensive_click);
+        let aggressive =
+            widgets::spawn_context_menu_button(&mut commands, menu_entity, "Aggressive");
+        commands.entity(aggressive).observe(on_pet_aggressive_click);
+        let dismiss = widgets::spawn_context_menu_button(&mut commands, menu_entity, "Dismiss");
+        commands.entity(dismiss).observe(on_pet_dismiss_click);
+        return;
+    }
+
+    let button = widgets::spawn_context_menu_button(&mut commands, menu_entity, "Invite to Party");
     commands.entity(button).observe(on_invite_click);
+
+    let duel_button =
+        widgets::spawn_context_menu_button(&mut commands, menu_entity, "Challenge to Duel");
+    commands.entity(duel_button).observe(on_duel_click);
+
+    if owns_pet {
+        let attack_button =
+            widgets::spawn_context_menu_button(&mut commands, menu_entity, "Pet Attack");
+        commands.entity(attack_button).observe(on_pet_attack_click);
+    }
 }
 
 fn on_invite_click(
@@ -131,3 +166,115 @@ fn on_invite_click(
 
     widgets::despawn_context_menu(&mut commands, &context_menu_q);
 }
+
+fn on_duel_click(
+    _event: On<Pointer<Click>>,
+    selected: Res<SelectedTarget>,
+    targets: Query<&NetworkId>,
+    mut client: ResMut<RenetClient>,
+    context_menu_q: Query<Entity, With<ContextMenu>>,
+    mut commands: Commands,
+) {
+    if let Some(target_entity) = selected.0
+        && let Ok(network_id) = targets.get(target_entity)
+    {
+        let action = PlayerAction::RequestDuel {
+            target_network_id: network_id.0,
+        };
+        client.send_message(DefaultChannel::ReliableOrdered, bitcode::encode(&action));
+    }
+
+    widgets::despawn_context_menu(&mut commands, &context_menu_q);
+}
+
+fn on_pet_attack_click(
+    _event: On<Pointer<Click>>,
+    selected: Res<SelectedTarget>,
+    targets: Query<&NetworkId>,
+    mut client: ResMut<RenetClient>,
+    context_menu_q: Query<Entity, With<ContextMenu>>,
+    mut commands: Commands,
+) {
+    if let Some(target_entity) = selected.0
+        && let Ok(network_id) = targets.get(target_entity)
+    {
+        let action = PlayerAction::PetAttack {
+            target_network_id: network_id.0,
+        };
+        client.send_message(DefaultChannel::ReliableOrdered, bitcode::encode(&action));
+    }
+
+    widgets::despawn_context_menu(&mut commands, &context_menu_q);
+}
+
+fn on_pet_passive_click(
+    _event: On<Pointer<Click>>,
+    client: ResMut<RenetClient>,
+    context_menu_q: Query<Entity, With<ContextMenu>>,
+    mut commands: Commands,
+) {
+    send_pet_action(
+        client,
+        PlayerAction::SetPetStance {
+            stance: PetStance::Passive,
+        },
+        &context_menu_q,
+        &mut commands,
+    );
+}
+
+fn on_pet_defensive_click(
+    _event: On<Pointer<Click>>,
+    client: ResMut<RenetClient>,
+    context_menu_q: Query<Entity, With<ContextMenu>>,
+    mut commands: Commands,
+) {
+    send_pet_action(
+        client,
+        PlayerAction::SetPetStance {
+            stance: PetStance::Defensive,
+        },
+        &context_menu_q,
+        &mut commands,
+    );
+}
+
+fn on_pet_aggressive_click(
+    _event: On<Pointer<Click>>,
+    client: ResMut<RenetClient>,
+    context_menu_q: Query<Entity, With<ContextMenu>>,
+    mut commands: Commands,
+) {
+    send_pet_action(
+        client,
+        PlayerAction::SetPetStance {
+            stance: PetStance::Aggressive,
+        },
+        &context_menu_q,
+        &mut commands,
+    );
+}
+
+fn on_pet_dismiss_click(
+    _event: On<Pointer<Click>>,
+    client: ResMut<RenetClient>,
+    context_menu_q: Query<Entity, With<ContextMenu>>,
+    mut commands: Commands,
+) {
+    send_pet_action(
+        client,
+        PlayerAction::DismissPet,
+        &context_menu_q,
+        &mut commands,
+    );
+}
+
+fn send_pet_action(
+    mut client: ResMut<RenetClient>,
+    action: PlayerAction,
+    context_menu_q: &Query<Entity, With<ContextMenu>>,
+    commands: &mut Commands,
+) {
+    client.send_message(DefaultChannel::ReliableOrdered, bitcode::encode(&action));
+    widgets::despawn_context_menu(commands, context_menu_q);
+}
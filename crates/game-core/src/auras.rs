@@ -0,0 +1,84 @@
+use crate::{crowd_control::CrowdControl, stats::Stats};
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use protocol::models::DamageSchool;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuraKind {
+    Buff,
+    Debuff,
+}
+
+/// Stat modifiers, applied once per stack.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum AuraModifier {
+    MovementSpeed(f32),
+    DamageTaken(f32),
+    /// Values above 1.0 shorten cast times
+    CastSpeed(f32),
+    /// Flat attribute bonus, added to the character's stats
+    Stats(Stats),
+    /// Negates all damage of the school
+    Immunity(DamageSchool),
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum PeriodicEffect {
+    Damage {
+        amount: i32,
+        #[serde(default)]
+        school: DamageSchool,
+    },
+    Heal {
+        amount: i32,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PeriodicDef {
+    pub interval: f32,
+    /// Amount is multiplied by the number of stacks on every tick
+    pub effect: PeriodicEffect,
+}
+
+/// Shield that soaks incoming damage until it is used up, which removes the aura.
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct AbsorbDef {
+    pub amount: i32,
+    /// Only damage of this school is soaked, shields without one soak every school
+    #[serde(default)]
+    pub school: Option<DamageSchool>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AuraDef {
+    pub name: String,
+    pub kind: AuraKind,
+    pub duration: f32,
+    #[serde(default = "default_max_stacks")]
+    pub max_stacks: u32,
+    #[serde(default)]
+    pub periodic: Option<PeriodicDef>,
+    #[serde(default)]
+    pub modifiers: Vec<AuraModifier>,
+    /// Duration is shortened by diminishing returns on repeated applications
+    #[serde(default)]
+    pub crowd_control: Option<CrowdControl>,
+    #[serde(default)]
+    pub absorb: Option<AbsorbDef>,
+    pub visual_id: u32,
+}
+
+fn default_max_stacks() -> u32 {
+    1
+}
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct AuraLibrary {
+    pub auras: HashMap<u32, AuraDef>,
+}
+
+#[derive(Resource)]
+pub struct AuraLibraryHandle(pub Handle<AuraLibrary>);
@@ -13,3 +13,6 @@ pub const ACTOR_COLLIDER_LENGTH: f32 = 2.0;
 /// Used to convert between ground-level positions (DB, spawn points) and
 /// physics-center positions (Transform).
 pub const ACTOR_HALF_HEIGHT: f32 = ACTOR_COLLIDER_LENGTH / 2.0 + ACTOR_COLLIDER_RADIUS;
+
+/// Height of an actor's eyes above the capsule center, where line of sight checks start.
+pub const ACTOR_EYE_OFFSET: f32 = 1.5;
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use protocol::models::CrowdControlState as NetCrowdControlState;
+use serde::Deserialize;
+
+/// Crowd-control effect of an aura. Each kind is its own diminishing returns category.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrowdControl {
+    /// Blocks movement, spells and attacks, and interrupts casts
+    Stun,
+    /// Blocks movement
+    Root,
+    /// Blocks spells and interrupts casts
+    Silence,
+    /// Takes over movement and blocks spells and attacks
+    Fear,
+}
+
+/// Crowd-control effects currently on an actor. Derived from auras on the server and
+/// replicated to clients, which lock their input accordingly.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CrowdControlState {
+    pub stunned: bool,
+    pub rooted: bool,
+    pub silenced: bool,
+    pub feared: bool,
+}
+
+impl CrowdControlState {
+    pub fn add(&mut self, effect: CrowdControl) {
+        match effect {
+            CrowdControl::Stun => self.stunned = true,
+            CrowdControl::Root => self.rooted = true,
+            CrowdControl::Silence => self.silenced = true,
+            CrowdControl::Fear => self.feared = true,
+        }
+    }
+
+    /// Whether the actor controls its own movement.
+    pub fn can_move(&self) -> bool {
+        !(self.stunned || self.rooted || self.feared)
+    }
+
+    pub fn can_cast(&self) -> bool {
+        !(self.stunned || self.silenced || self.feared)
+    }
+
+    pub fn can_attack(&self) -> bool {
+        !(self.stunned || self.feared)
+    }
+}
+
+impl From<CrowdControlState> for NetCrowdControlState {
+    fn from(value: CrowdControlState) -> Self {
+        Self {
+            stunned: value.stunned,
+            rooted: value.rooted,
+            silenced: value.silenced,
+            feared: value.feared,
+        }
+    }
+}
+
+impl From<NetCrowdControlState> for CrowdControlState {
+    fn from(value: NetCrowdControlState) -> Self {
+        Self {
+            stunned: value.stunned,
+            rooted: value.rooted,
+            silenced: value.silenced,
+            feared: value.feared,
+        }
+    }
+}
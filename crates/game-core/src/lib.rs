@@ -1,10 +1,14 @@
+pub mod auras;
 pub mod character_controller;
 pub mod collision;
 pub mod components;
 pub mod constants;
+pub mod crowd_control;
 pub mod lod;
 pub mod movement;
 pub mod networking;
+pub mod power;
 pub mod props;
 pub mod spells;
+pub mod stats;
 pub mod zone;
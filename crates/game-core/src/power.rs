@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use protocol::models::{Power as NetPower, PowerKind};
+
+/// Mana, energy or rage pool spent by abilities. Regeneration rules depend on the kind and
+/// are applied by the server.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Power {
+    pub kind: PowerKind,
+    pub current: i32,
+    pub max: i32,
+}
+
+impl Power {
+    /// A full pool, except for rage which starts empty and is built up in combat.
+    pub fn new(kind: PowerKind, max: i32) -> Self {
+        let current = match kind {
+            PowerKind::Rage => 0,
+            PowerKind::Mana | PowerKind::Energy => max,
+        };
+        Self { kind, current, max }
+    }
+
+    /// Takes `cost` from the pool. Returns false and leaves the pool untouched if it is too low.
+    pub fn spend(&mut self, cost: i32) -> bool {
+        if self.current < cost {
+            return false;
+        }
+        self.current -= cost.max(0);
+        true
+    }
+
+    /// Adds up to `amount` without exceeding the maximum. Returns the amount gained.
+    pub fn gain(&mut self, amount: i32) -> i32 {
+        let gained = amount.min(self.max - self.current).max(0);
+        self.current += gained;
+        gained
+    }
+
+    /// Removes up to `amount` without going below zero. Returns the amount lost.
+    pub fn lose(&mut self, amount: i32) -> i32 {
+        let lost = amount.min(self.current).max(0);
+        self.current -= lost;
+        lost
+    }
+
+    /// Changes the maximum, keeping the current value within it.
+    pub fn set_max(&mut self, max: i32) {
+        self.max = max.max(0);
+        self.current = self.current.min(self.max);
+    }
+}
+
+impl From<Power> for NetPower {
+    fn from(value: Power) -> Self {
+        Self {
+            kind: value.kind,
+            current: value.current,
+            max: value.max,
+        }
+    }
+}
+
+impl From<NetPower> for Power {
+    fn from(value: NetPower) -> Self {
+        Self {
+            kind: value.kind,
+            current: value.current,
+            max: value.max,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rage_starts_empty() {
+        assert_eq!(Power::new(PowerKind::Mana, 200).current, 200);
+        assert_eq!(Power::new(PowerKind::Energy, 100).current, 100);
+        assert_eq!(Power::new(PowerKind::Rage, 100).current, 0);
+    }
+
+    #[test]
+    fn spending_fails_without_enough_power() {
+        let mut power = Power::new(PowerKind::Energy, 100);
+        assert!(power.spend(60));
+        assert!(!power.spend(60));
+        assert_eq!(power.current, 40);
+    }
+
+    #[test]
+    fn gains_and_losses_are_clamped() {
+        let mut power = Power::new(PowerKind::Rage, 100);
+        assert_eq!(power.lose(10), 0);
+        assert_eq!(power.gain(130), 100);
+        assert_eq!(power.gain(5), 0);
+
+        power.set_max(50);
+        assert_eq!(power.current, 50);
+    }
+}
@@ -1,20 +1,166 @@
 use bevy::prelude::*;
 use bevy::reflect::TypePath;
+use protocol::models::DamageSchool;
 use serde::Deserialize;
 use std::collections::HashMap;
 
+/// Duration of the global cooldown shared by all spells with `triggers_gcd`.
+pub const GLOBAL_COOLDOWN_SECS: f32 = 1.5;
+
+/// Who a spell may be cast on.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpellTarget {
+    /// Always lands on the caster, the selected target is ignored.
+    Caster,
+    Friendly,
+    #[default]
+    Hostile,
+}
+
+/// A single effect applied to the spell target when the cast completes.
+/// Spells apply their effects in the order they are listed.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum SpellEffect {
+    Damage {
+        amount: i32,
+        #[serde(default)]
+        school: DamageSchool,
+    },
+    Heal {
+        amount: i32,
+    },
+    ApplyAura {
+        aura_id: u32,
+    },
+    /// Removes up to `count` auras from the target
+    Dispel {
+        count: u32,
+    },
+    /// Moves the target forward along its facing direction
+    Teleport {
+        distance: f32,
+    },
+    /// Pushes the target away from the caster
+    Knockback {
+        distance: f32,
+    },
+    /// Brings a dead player back to life with a share of their maximum health.
+    /// Spells with this effect can only be cast on dead players.
+    Resurrect {
+        health_fraction: f32,
+    },
+    /// Forces a mob to attack the caster for `duration` seconds, and raises the caster's
+    /// threat to that of the mob's current top target
+    Taunt {
+        duration: f32,
+    },
+    /// Reduces the caster's threat on every mob by `fraction`. A fraction of 1 removes the
+    /// caster from all threat tables, like feign death or vanish
+    DropThreat {
+        fraction: f32,
+    },
+    /// Summons the monster with this key in `monsters.ron` as the caster's pet, replacing the
+    /// pet the caster already has
+    SummonPet {
+        monster: String,
+    },
+}
+
+/// Shape of the area hit by an area-of-effect spell.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum AreaShape {
+    /// Circle around the target actor or a ground position
+    Circle { radius: f32 },
+    /// Cone in front of the caster, `angle` is the full opening angle in degrees
+    Cone { radius: f32, angle: f32 },
+    /// Circle centred on the caster, the selected target is ignored
+    SelfRadius { radius: f32 },
+}
+
+impl AreaShape {
+    pub fn radius(&self) -> f32 {
+        match *self {
+            Self::Circle { radius } | Self::Cone { radius, .. } | Self::SelfRadius { radius } => {
+                radius
+            }
+        }
+    }
+
+    /// Whether the area is anchored to the caster instead of the cast target.
+    pub fn is_caster_centred(&self) -> bool {
+        matches!(self, Self::Cone { .. } | Self::SelfRadius { .. })
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct AreaDef {
+    pub shape: AreaShape,
+    /// Closest actors are picked first when more than `max_targets` are in the area
+    pub max_targets: u32,
+}
+
+/// Channelled spells apply their effects every `interval` seconds for `duration` seconds while
+/// the caster keeps still, instead of once when the cast completes.
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct ChannelDef {
+    pub duration: f32,
+    pub interval: f32,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct SpellDef {
     pub name: String,
-    pub damage: i32,
+    #[serde(default)]
+    pub target: SpellTarget,
+    pub effects: Vec<SpellEffect>,
+    /// Applies the effects to every matching actor in the area instead of a single target
+    #[serde(default)]
+    pub area: Option<AreaDef>,
     pub range: f32,
+    /// Travel speed of the spell's projectile in units per second, spells without one land
+    /// as soon as the cast completes
+    #[serde(default)]
+    pub projectile_speed: Option<f32>,
     pub cooldown: f32,
+    /// Power spent when the cast starts, in whatever resource the caster uses, and refunded
+    /// if the cast is interrupted. Casters without a power pool ignore it
+    #[serde(default)]
+    pub cost: i32,
+    /// Whether casting this spell starts the global cooldown, and is blocked by it
+    #[serde(default)]
+    pub triggers_gcd: bool,
     pub casting_duration: f32,
     #[serde(default)]
     pub castable_while_moving: bool,
+    /// Channels the spell instead of casting it, `casting_duration` is ignored. Moving always
+    /// breaks a channel
+    #[serde(default)]
+    pub channel: Option<ChannelDef>,
+    /// Queues the spell to replace the caster's next auto-attack swing instead of casting it
+    #[serde(default)]
+    pub on_next_swing: bool,
+    /// Scales the threat generated by the spell's damage and healing
+    #[serde(default = "default_threat_multiplier")]
+    pub threat_multiplier: f32,
+    /// Flat threat added on the target mob whenever the spell lands, even if it deals no damage
+    #[serde(default)]
+    pub bonus_threat: f32,
     pub visual_id: u32,
 }
 
+fn default_threat_multiplier() -> f32 {
+    1.0
+}
+
+impl SpellDef {
+    /// Whether the spell targets dead players instead of living actors.
+    pub fn resurrects(&self) -> bool {
+        self.effects
+            .iter()
+            .any(|effect| matches!(effect, SpellEffect::Resurrect { .. }))
+    }
+}
+
 #[derive(Asset, TypePath, Deserialize, Debug)]
 pub struct SpellLibrary {
     pub spells: HashMap<u32, SpellDef>,
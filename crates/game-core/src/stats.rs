@@ -0,0 +1,296 @@
+use bevy::prelude::*;
+use protocol::models::{DamageSchool, PowerKind};
+use serde::Deserialize;
+use std::ops::{Add, AddAssign, Mul};
+
+const BASE_HP: i32 = 50;
+const HP_PER_STAMINA: i32 = 5;
+const BASE_MANA: i32 = 50;
+const MANA_PER_INTELLECT: i32 = 15;
+const MAX_ENERGY: i32 = 100;
+const MAX_RAGE: i32 = 100;
+const BASE_PRIMARY_STAT: i32 = 10;
+const PRIMARY_STAT_PER_LEVEL: i32 = 2;
+const ARMOR_PER_LEVEL: i32 = 20;
+const ATTACK_POWER_PER_STRENGTH: i32 = 2;
+const SPELL_POWER_PER_INTELLECT: i32 = 1;
+/// Attack power needed for one extra point of damage per second
+const ATTACK_POWER_PER_DPS: f32 = 14.0;
+/// Share of spell power added to each damage or heal effect
+const SPELL_POWER_COEFFICIENT: f32 = 0.5;
+const MAX_ARMOR_REDUCTION: f32 = 0.75;
+/// Strength needed for one percentage point of melee critical strike chance
+const STRENGTH_PER_CRIT: f32 = 20.0;
+/// Intellect needed for one percentage point of spell critical strike chance
+const INTELLECT_PER_CRIT: f32 = 20.0;
+/// Resistance needed per attacker level to resist half of the damage
+const RESISTANCE_PER_LEVEL: f32 = 10.0;
+const MAX_RESISTANCE_REDUCTION: f32 = 0.75;
+
+/// Character attributes. Used both for an actor's total stats and for flat bonuses
+/// granted by equipment and auras.
+#[derive(Component, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct Stats {
+    pub stamina: i32,
+    pub strength: i32,
+    pub intellect: i32,
+    pub armor: i32,
+    pub spell_power: i32,
+    pub attack_power: i32,
+    /// Percentage, 10 haste shortens cast times by a factor of 1.1
+    pub haste: i32,
+    /// Percentage points taken off the chance to miss
+    pub hit: i32,
+    /// Percentage points added to the chance to critically strike
+    pub crit: i32,
+    /// Percentage points added to the chance to dodge melee attacks
+    pub dodge: i32,
+    pub fire_resistance: i32,
+    pub frost_resistance: i32,
+    pub nature_resistance: i32,
+    pub shadow_resistance: i32,
+    pub holy_resistance: i32,
+}
+
+impl Stats {
+    /// Base stats of a character at the given level, before equipment and auras.
+    pub fn base(level: i32) -> Self {
+        let levels_gained = level.max(1) - 1;
+        let primary = BASE_PRIMARY_STAT + levels_gained * PRIMARY_STAT_PER_LEVEL;
+        Self {
+            stamina: primary,
+            strength: primary,
+            intellect: primary,
+            armor: level.max(1) * ARMOR_PER_LEVEL,
+            ..default()
+        }
+    }
+
+    pub fn max_hp(&self) -> i32 {
+        (BASE_HP + self.stamina * HP_PER_STAMINA).max(1)
+    }
+
+    /// Size of the power pool. Only mana grows with stats, energy and rage are fixed.
+    pub fn max_power(&self, kind: PowerKind) -> i32 {
+        match kind {
+            PowerKind::Mana => (BASE_MANA + self.intellect * MANA_PER_INTELLECT).max(1),
+            PowerKind::Energy => MAX_ENERGY,
+            PowerKind::Rage => MAX_RAGE,
+        }
+    }
+
+    pub fn total_attack_power(&self) -> i32 {
+        (self.attack_power + self.strength * ATTACK_POWER_PER_STRENGTH).max(0)
+    }
+
+    pub fn total_spell_power(&self) -> i32 {
+        (self.spell_power + self.intellect * SPELL_POWER_PER_INTELLECT).max(0)
+    }
+
+    /// Weapon damage plus the attack power bonus for a swing of the given speed.
+    pub fn melee_damage(&self, base_damage: i32, swing_speed: f32) -> i32 {
+        base_damage
+            + (self.total_attack_power() as f32 / ATTACK_POWER_PER_DPS * swing_speed).round() as i32
+    }
+
+    /// Spell damage or healing with the spell power bonus added.
+    pub fn spell_amount(&self, base_amount: i32) -> i32 {
+        base_amount + (self.total_spell_power() as f32 * SPELL_POWER_COEFFICIENT).round() as i32
+    }
+
+    /// Values above 1.0 shorten cast times.
+    pub fn cast_speed_multiplier(&self) -> f32 {
+        (1.0 + self.haste as f32 / 100.0).max(0.1)
+    }
+
+    /// Melee critical strike chance in percentage points, added to the base chance.
+    pub fn melee_crit_chance(&self) -> f32 {
+        (self.crit as f32 + self.strength.max(0) as f32 / STRENGTH_PER_CRIT).max(0.0)
+    }
+
+    /// Spell critical strike chance in percentage points, added to the base chance.
+    pub fn spell_crit_chance(&self) -> f32 {
+        (self.crit as f32 + self.intellect.max(0) as f32 / INTELLECT_PER_CRIT).max(0.0)
+    }
+
+    /// Percentage points taken off the chance to miss.
+    pub fn hit_chance(&self) -> f32 {
+        self.hit.max(0) as f32
+    }
+
+    /// Dodge chance in percentage points, added to the base chance.
+    pub fn dodge_chance(&self) -> f32 {
+        self.dodge.max(0) as f32
+    }
+
+    /// Share of physical damage from an attacker of the given level that armor absorbs.
+    pub fn armor_reduction(&self, attacker_level: i32) -> f32 {
+        let armor = self.armor.max(0) as f32;
+        let reduction = armor / (armor + 400.0 + 85.0 * attacker_level.max(1) as f32);
+        reduction.min(MAX_ARMOR_REDUCTION)
+    }
+
+    /// Resistance against a magic school, physical damage is mitigated by armor instead.
+    pub fn resistance(&self, school: DamageSchool) -> i32 {
+        match school {
+            DamageSchool::Physical => 0,
+            DamageSchool::Fire => self.fire_resistance,
+            DamageSchool::Frost => self.frost_resistance,
+            DamageSchool::Nature => self.nature_resistance,
+            DamageSchool::Shadow => self.shadow_resistance,
+            DamageSchool::Holy => self.holy_resistance,
+        }
+    }
+
+    /// Share of magic damage of the given school from an attacker of the given level that
+    /// resistances negate.
+    pub fn resistance_reduction(&self, school: DamageSchool, attacker_level: i32) -> f32 {
+        let resistance = self.resistance(school).max(0) as f32;
+        let reduction =
+            resistance / (resistance + RESISTANCE_PER_LEVEL * attacker_level.max(1) as f32);
+        reduction.min(MAX_RESISTANCE_REDUCTION)
+    }
+}
+
+impl Add for Stats {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            stamina: self.stamina + rhs.stamina,
+            strength: self.strength + rhs.strength,
+            intellect: self.intellect + rhs.intellect,
+            armor: self.armor + rhs.armor,
+            spell_power: self.spell_power + rhs.spell_power,
+            attack_power: self.attack_power + rhs.attack_power,
+            haste: self.haste + rhs.haste,
+            hit: self.hit + rhs.hit,
+            crit: self.crit + rhs.crit,
+            dodge: self.dodge + rhs.dodge,
+            fire_resistance: self.fire_resistance + rhs.fire_resistance,
+            frost_resistance: self.frost_resistance + rhs.frost_resistance,
+            nature_resistance: self.nature_resistance + rhs.nature_resistance,
+            shadow_resistance: self.shadow_resistance + rhs.shadow_resistance,
+            holy_resistance: self.holy_resistance + rhs.holy_resistance,
+        }
+    }
+}
+
+impl AddAssign for Stats {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Mul<i32> for Stats {
+    type Output = Self;
+
+    fn mul(self, rhs: i32) -> Self {
+        Self {
+            stamina: self.stamina * rhs,
+            strength: self.strength * rhs,
+            intellect: self.intellect * rhs,
+            armor: self.armor * rhs,
+            spell_power: self.spell_power * rhs,
+            attack_power: self.attack_power * rhs,
+            haste: self.haste * rhs,
+            hit: self.hit * rhs,
+            crit: self.crit * rhs,
+            dodge: self.dodge * rhs,
+            fire_resistance: self.fire_resistance * rhs,
+            frost_resistance: self.frost_resistance * rhs,
+            nature_resistance: self.nature_resistance * rhs,
+            shadow_resistance: self.shadow_resistance * rhs,
+            holy_resistance: self.holy_resistance * rhs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_stats_grow_with_level() {
+        let first = Stats::base(1);
+        let tenth = Stats::base(10);
+        assert_eq!(first.stamina, BASE_PRIMARY_STAT);
+        assert!(tenth.stamina > first.stamina);
+        assert!(tenth.max_hp() > first.max_hp());
+        assert!(tenth.max_power(PowerKind::Mana) > first.max_power(PowerKind::Mana));
+        assert_eq!(
+            tenth.max_power(PowerKind::Energy),
+            first.max_power(PowerKind::Energy)
+        );
+        assert_eq!(Stats::base(0), first);
+    }
+
+    #[test]
+    fn bonuses_add_up() {
+        let bonus = Stats {
+            stamina: 4,
+            haste: 10,
+            ..default()
+        };
+        let total = Stats::base(1) + bonus * 2;
+        assert_eq!(total.stamina, BASE_PRIMARY_STAT + 8);
+        assert_eq!(total.max_hp(), Stats::base(1).max_hp() + 8 * HP_PER_STAMINA);
+        assert!((total.cast_speed_multiplier() - 1.2).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn derived_damage_scales_with_power() {
+        let weak = Stats::default();
+        let strong = Stats {
+            strength: 35,
+            spell_power: 20,
+            ..default()
+        };
+        assert_eq!(weak.melee_damage(5, 2.0), 5);
+        assert_eq!(strong.melee_damage(5, 2.0), 15);
+        assert_eq!(weak.spell_amount(10), 10);
+        assert_eq!(strong.spell_amount(10), 20);
+    }
+
+    #[test]
+    fn crit_scales_with_the_primary_stat_of_the_attack() {
+        let stats = Stats {
+            strength: 40,
+            intellect: 20,
+            crit: 3,
+            ..default()
+        };
+        assert_eq!(stats.melee_crit_chance(), 5.0);
+        assert_eq!(stats.spell_crit_chance(), 4.0);
+        assert_eq!(Stats::default().melee_crit_chance(), 0.0);
+    }
+
+    #[test]
+    fn armor_reduction_is_capped() {
+        assert_eq!(Stats::default().armor_reduction(10), 0.0);
+        let armored = Stats {
+            armor: 100_000,
+            ..default()
+        };
+        assert_eq!(armored.armor_reduction(1), MAX_ARMOR_REDUCTION);
+    }
+
+    #[test]
+    fn resistances_only_mitigate_their_school() {
+        let resistant = Stats {
+            fire_resistance: 50,
+            ..default()
+        };
+        assert_eq!(resistant.resistance_reduction(DamageSchool::Fire, 5), 0.5);
+        assert_eq!(resistant.resistance_reduction(DamageSchool::Frost, 5), 0.0);
+        assert_eq!(
+            resistant.resistance_reduction(DamageSchool::Physical, 5),
+            0.0
+        );
+        assert!(
+            resistant.resistance_reduction(DamageSchool::Fire, 10)
+                < resistant.resistance_reduction(DamageSchool::Fire, 5)
+        );
+    }
+}
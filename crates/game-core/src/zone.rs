@@ -25,6 +25,50 @@ impl PropInstance {
     }
 }
 
+/// How a patrol continues after its last waypoint.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PatrolMode {
+    /// Walks from the last waypoint back to the first
+    #[default]
+    Loop,
+    /// Walks the waypoints back in reverse order
+    PingPong,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PatrolWaypoint {
+    pub position: [f32; 3],
+    /// Seconds the patrol waits at the waypoint
+    #[serde(default)]
+    pub pause_secs: f32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PatrolPath {
+    pub id: String,
+    pub waypoints: Vec<PatrolWaypoint>,
+    #[serde(default)]
+    pub mode: PatrolMode,
+}
+
+/// Arrangement of a patrol group behind its leader.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Formation {
+    /// Single file behind the leader
+    Column,
+    /// Pairs fanning out behind the leader
+    Wedge,
+}
+
+/// Links the mobs of several spawn points into one pack, pulling one member pulls the whole
+/// pack.
+#[derive(Deserialize, Debug, Clone)]
+pub struct MobPack {
+    pub id: String,
+    /// Ids of the spawn points whose mobs belong to the pack
+    pub spawn_points: Vec<String>,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct SpawnPoint {
     pub id: String,
@@ -34,6 +78,12 @@ pub struct SpawnPoint {
     pub max_count: usize,
     pub level_range: (i32, i32),
     pub respawn_secs: f32,
+    /// Id of the patrol path the spawned mobs walk instead of wandering
+    #[serde(default)]
+    pub patrol: Option<String>,
+    /// Spawned mobs patrol as one group in this formation, otherwise each patrols on its own
+    #[serde(default)]
+    pub formation: Option<Formation>,
 }
 
 #[derive(Asset, TypePath, Deserialize, Debug)]
@@ -48,6 +98,31 @@ pub struct ZoneDef {
     pub props: Vec<PropInstance>,
     #[serde(default)]
     pub spawn_points: Vec<SpawnPoint>,
+    #[serde(default)]
+    pub patrol_paths: Vec<PatrolPath>,
+    #[serde(default)]
+    pub packs: Vec<MobPack>,
+    /// Ground positions where released players respawn, the player spawn is used if empty
+    #[serde(default)]
+    pub graveyards: Vec<[f32; 3]>,
+}
+
+impl ZoneDef {
+    pub fn patrol_path(&self, id: &str) -> Option<&PatrolPath> {
+        self.patrol_paths.iter().find(|path| path.id == id)
+    }
+
+    /// Graveyard closest to `position`, as a ground-level position.
+    pub fn nearest_graveyard(&self, position: Vec3) -> Vec3 {
+        self.graveyards
+            .iter()
+            .map(|&graveyard| Vec3::from(graveyard))
+            .min_by(|a, b| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            })
+            .unwrap_or(Vec3::from(self.player_spawn))
+    }
 }
 
 #[derive(Resource)]
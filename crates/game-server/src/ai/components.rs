@@ -1,5 +1,17 @@
+use crate::assets::{EncounterDef, EncounterPhaseDef};
 use bevy::prelude::*;
-use std::collections::HashMap;
+use bevy_renet::renet::ClientId;
+use game_core::{
+    constants::ACTOR_HALF_HEIGHT,
+    networking::NetworkId,
+    zone::{Formation, PatrolMode, PatrolPath},
+};
+use protocol::models::PetStance;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum AiBehavior {
@@ -22,6 +34,15 @@ pub enum AiState {
     },
     Returning,
     Evading,
+    /// Walking a patrol route, see [`Patrol`]
+    Patrol,
+}
+
+impl AiState {
+    /// Idle and patrolling AI is out of combat, it aggroes on players and regenerates.
+    pub fn is_at_rest(&self) -> bool {
+        matches!(self, AiState::Idle | AiState::Patrol)
+    }
 }
 
 #[derive(Component, Debug, Default)]
@@ -36,9 +57,22 @@ pub struct ThreatEntry {
     pub threat: f32,
 }
 
+/// Threat a new target needs over the current target's to pull aggro while in melee range.
+pub const MELEE_PULL_THRESHOLD: f32 = 1.1;
+/// Threat a new target needs over the current target's to pull aggro from outside melee range.
+pub const RANGED_PULL_THRESHOLD: f32 = 1.3;
+
+/// Forces the mob onto the taunting entity until the timer runs out.
+#[derive(Debug)]
+pub struct Taunt {
+    pub entity: Entity,
+    pub timer: Timer,
+}
+
 #[derive(Component, Debug, Default)]
 pub struct ThreatTable {
     pub entries: Vec<ThreatEntry>,
+    pub taunt: Option<Taunt>,
 }
 
 impl ThreatTable {
@@ -53,6 +87,13 @@ impl ThreatTable {
         }
     }
 
+    pub fn threat_of(&self, entity: Entity) -> Option<f32> {
+        self.entries
+            .iter()
+            .find(|e| e.entity == entity)
+            .map(|e| e.threat)
+    }
+
     pub fn highest_threat(&self) -> Option<&ThreatEntry> {
         self.entries.iter().max_by(|a, b| {
             a.threat
@@ -61,18 +102,116 @@ impl ThreatTable {
         })
     }
 
+    /// Picks the entity the mob should attack. A taunt overrides threat, otherwise the current
+    /// target is kept until another entry exceeds its threat by the melee or ranged pull
+    /// threshold, depending on whether that entry is in melee range of the mob.
+    pub fn select_target(
+        &self,
+        current: Option<Entity>,
+        is_in_melee_range: impl Fn(Entity) -> bool,
+    ) -> Option<Entity> {
+        if let Some(taunt) = &self.taunt
+            && self.threat_of(taunt.entity).is_some()
+        {
+            return Some(taunt.entity);
+        }
+
+        let Some(current_threat) = current.and_then(|entity| self.threat_of(entity)) else {
+            return self.highest_threat().map(|entry| entry.entity);
+        };
+
+        self.entries
+            .iter()
+            .filter(|entry| {
+                let threshold = if is_in_melee_range(entry.entity) {
+                    MELEE_PULL_THRESHOLD
+                } else {
+                    RANGED_PULL_THRESHOLD
+                };
+                Some(entry.entity) != current && entry.threat > current_threat * threshold
+            })
+            .max_by(|a, b| {
+                a.threat
+                    .partial_cmp(&b.threat)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|entry| entry.entity)
+            .or(current)
+    }
+
+    /// Forces the mob onto `entity` for `duration` seconds, matching the top threat so the mob
+    /// stays on the taunter once the taunt runs out.
+    pub fn taunt(&mut self, entity: Entity, duration: f32) {
+        let top_threat = self.highest_threat().map_or(0.0, |entry| entry.threat);
+        let threat = self.threat_of(entity).unwrap_or(0.0);
+        self.add_threat(entity, (top_threat - threat).max(0.0));
+        self.taunt = Some(Taunt {
+            entity,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        });
+    }
+
+    /// Multiplies the entity's threat by `factor`, removing it from the table at 0.
+    pub fn scale_threat(&mut self, entity: Entity, factor: f32) {
+        if factor <= 0.0 {
+            self.remove_entity(entity);
+        } else if let Some(entry) = self.entries.iter_mut().find(|e| e.entity == entity) {
+            entry.threat *= factor;
+        }
+    }
+
     pub fn clear(&mut self) {
         self.entries.clear();
+        self.taunt = None;
     }
 
     pub fn remove_entity(&mut self, entity: Entity) {
         self.entries.retain(|e| e.entity != entity);
+        if self
+            .taunt
+            .as_ref()
+            .is_some_and(|taunt| taunt.entity == entity)
+        {
+            self.taunt = None;
+        }
     }
 }
 
 #[derive(Component, Debug)]
 pub struct AggroRadius(pub f32);
 
+/// Idle mobs of the same faction within this radius join the fight when the mob is pulled.
+#[derive(Component, Debug)]
+pub struct CallForHelpRadius(pub f32);
+
+/// Linked group of mobs from one of the zone's packs. Pulling one member pulls the whole pack,
+/// and the pack evades as a unit once one member leashes.
+#[derive(Component, Debug)]
+pub struct Pack {
+    pub id: String,
+}
+
+#[derive(Component, Debug)]
+pub struct PackMember {
+    pub pack: Entity,
+}
+
+/// Pets further than this from their owner stop fighting and run back to them.
+pub const PET_LEASH_RANGE: f32 = 30.0;
+
+/// Summoned companion of a player or mob. Pets follow their owner, fight through `AiBrain`
+/// according to their stance, and despawn with their owner.
+#[derive(Component, Debug)]
+pub struct Pet {
+    pub owner: Entity,
+    pub owner_id: NetworkId,
+    /// Client credited with the pet's taps and kills, `None` for pets of mobs
+    pub owner_client_id: Option<ClientId>,
+    /// Spell that summoned the pet, saved to resummon it on the next login
+    pub spell_id: u32,
+    pub stance: PetStance,
+}
+
 #[derive(Component, Debug)]
 pub struct LeashAnchor {
     pub position: Vec3,
@@ -102,6 +241,146 @@ impl Default for AiMovement {
     }
 }
 
+/// Paths are queried again once the movement target is this far from the goal of the cached path
+pub const REPATH_DISTANCE: f32 = 3.0;
+/// Waypoints count as reached once the mob is this close to them horizontally
+const WAYPOINT_REACHED_DISTANCE: f32 = 0.75;
+
+/// Navmesh path towards the AI's movement target, queried off the main thread and cached until
+/// the target moves away from the goal it was queried for.
+#[derive(Component, Debug, Default)]
+pub struct AiPath {
+    /// Movement target the path leads to
+    pub goal: Option<Vec3>,
+    /// Remaining corners of the path, the last waypoint is the goal
+    pub waypoints: VecDeque<Vec3>,
+    /// Identifies the latest query, results of older queries are dropped
+    pub request: u32,
+    /// No path leads to the goal
+    pub unreachable: bool,
+}
+
+impl AiPath {
+    /// Forgets the path and drops the result of a running query.
+    pub fn clear(&mut self) {
+        self.goal = None;
+        self.waypoints.clear();
+        self.request = self.request.wrapping_add(1);
+        self.unreachable = false;
+    }
+
+    /// Corner to steer towards from `position`, skipping the ones already reached. Returns
+    /// `None` once only the goal is left, the AI then steers at its live movement target.
+    pub fn next_waypoint(&mut self, position: Vec3) -> Option<Vec3> {
+        while self.waypoints.len() > 1
+            && self.waypoints.front().is_some_and(|waypoint| {
+                position.xz().distance(waypoint.xz()) <= WAYPOINT_REACHED_DISTANCE
+            })
+        {
+            self.waypoints.pop_front();
+        }
+        (self.waypoints.len() > 1).then(|| self.waypoints[0])
+    }
+}
+
+/// Waypoint of a patrol route at capsule height.
+#[derive(Debug, Clone, Copy)]
+pub struct PatrolStop {
+    pub position: Vec3,
+    pub pause_secs: f32,
+}
+
+/// Patrol path of a zone in world space, shared by every mob of the spawn point.
+#[derive(Debug)]
+pub struct PatrolRoute {
+    pub stops: Vec<PatrolStop>,
+    pub mode: PatrolMode,
+}
+
+impl From<&PatrolPath> for PatrolRoute {
+    fn from(path: &PatrolPath) -> Self {
+        Self {
+            stops: path
+                .waypoints
+                .iter()
+                .map(|waypoint| PatrolStop {
+                    // Waypoints are ground-level; offset Y to capsule center
+                    position: Vec3::from(waypoint.position) + Vec3::Y * ACTOR_HALF_HEIGHT,
+                    pause_secs: waypoint.pause_secs,
+                })
+                .collect(),
+            mode: path.mode,
+        }
+    }
+}
+
+/// Walks the mob along a patrol route while in [`AiState::Patrol`]. Mobs of a spawn point
+/// with a formation patrol as a group, the oldest mob leads and the others follow.
+#[derive(Component, Debug)]
+pub struct Patrol {
+    pub route: Arc<PatrolRoute>,
+    pub formation: Option<Formation>,
+    /// Stop the mob is walking to or waiting at
+    pub index: usize,
+    /// Walking a ping-pong route backwards
+    pub reverse: bool,
+    /// Runs while the mob waits at a stop
+    pub pause: Option<Timer>,
+    /// Walking to a formation slot behind the group's leader
+    pub following: bool,
+}
+
+impl Patrol {
+    /// Starts the patrol at the stop closest to `position`.
+    pub fn new(route: Arc<PatrolRoute>, formation: Option<Formation>, position: Vec3) -> Self {
+        let index = route
+            .stops
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                a.position
+                    .distance_squared(position)
+                    .total_cmp(&b.position.distance_squared(position))
+            })
+            .map_or(0, |(index, _)| index);
+        Self {
+            route,
+            formation,
+            index,
+            reverse: false,
+            pause: None,
+            following: false,
+        }
+    }
+
+    pub fn current_stop(&self) -> Option<&PatrolStop> {
+        self.route.stops.get(self.index)
+    }
+
+    /// Moves on to the next stop of the route.
+    pub fn advance(&mut self) {
+        let count = self.route.stops.len();
+        if count < 2 {
+            return;
+        }
+        match self.route.mode {
+            PatrolMode::Loop => self.index = (self.index + 1) % count,
+            PatrolMode::PingPong => {
+                if self.reverse && self.index == 0 {
+                    self.reverse = false;
+                } else if !self.reverse && self.index == count - 1 {
+                    self.reverse = true;
+                }
+                if self.reverse {
+                    self.index -= 1;
+                } else {
+                    self.index += 1;
+                }
+            }
+        }
+    }
+}
+
 /// Wander behavior for idle mobs. Picks random points near the spawn
 /// location, walks to them, pauses, and repeats.
 #[derive(Component, Debug)]
@@ -130,3 +409,260 @@ impl Wander {
         }
     }
 }
+
+/// Boss fight of a mob with an encounter in `encounters.ron`. The fight state only exists
+/// while the boss is engaged, so a wipe or reset starts the next pull from scratch.
+#[derive(Component, Debug)]
+pub struct Encounter {
+    pub def: Arc<EncounterDef>,
+    /// Center of the encounter boundary, the boss' spawn position
+    pub center: Vec3,
+    pub fight: Option<EncounterFight>,
+}
+
+/// State of an engaged encounter.
+#[derive(Debug, Default)]
+pub struct EncounterFight {
+    /// Number of phases started so far, the last one started is the current phase
+    pub phases_started: usize,
+    pub elapsed: Duration,
+    /// Timer of each rotation ability of the current phase, abilities are due once it finished
+    pub rotation: Vec<Timer>,
+    pub enraged: bool,
+    /// Adds spawned during the fight, despawned on a wipe or reset
+    pub adds: Vec<Entity>,
+}
+
+/// Something the boss has to do as its encounter advances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncounterEvent {
+    /// The phase with this index started, its adds spawn
+    PhaseStarted(usize),
+    /// The enrage timer ran out, the boss applies the spell to itself
+    Enraged(u32),
+}
+
+impl Encounter {
+    pub fn new(def: Arc<EncounterDef>, center: Vec3) -> Self {
+        Self {
+            def,
+            center,
+            fight: None,
+        }
+    }
+
+    pub fn engage(&mut self) {
+        self.fight = Some(EncounterFight::default());
+    }
+
+    /// Ends the fight and returns the adds spawned during it.
+    pub fn end(&mut self) -> Vec<Entity> {
+        self.fight
+            .take()
+            .map(|fight| fight.adds)
+            .unwrap_or_default()
+    }
+
+    /// Advances the fight by `delta` with the boss at `health` share of its maximum health.
+    /// Starts every phase whose threshold the boss has reached in order, and enrages the boss
+    /// once the enrage timer runs out.
+    pub fn advance(&mut self, delta: Duration, health: f32) -> Vec<EncounterEvent> {
+        let Some(fight) = &mut self.fight else {
+            return Vec::new();
+        };
+        let mut events = Vec::new();
+
+        fight.elapsed += delta;
+        for timer in &mut fight.rotation {
+            timer.tick(delta);
+        }
+
+        while let Some(phase) = self.def.phases.get(fight.phases_started)
+            && health <= phase.health_below
+        {
+            fight.rotation = phase
+                .rotation
+                .iter()
+                .map(|ability| {
+                    let delay = ability.delay.unwrap_or(ability.interval);
+                    Timer::from_seconds(delay, TimerMode::Once)
+                })
+                .collect();
+            events.push(EncounterEvent::PhaseStarted(fight.phases_started));
+            fight.phases_started += 1;
+        }
+
+        if let Some(enrage) = &self.def.enrage
+            && !fight.enraged
+            && fight.elapsed.as_secs_f32() >= enrage.after
+        {
+            fight.enraged = true;
+            events.push(EncounterEvent::Enraged(enrage.spell_id));
+        }
+
+        events
+    }
+
+    pub fn phase(&self) -> Option<&EncounterPhaseDef> {
+        let fight = self.fight.as_ref()?;
+        self.def.phases.get(fight.phases_started.checked_sub(1)?)
+    }
+
+    /// Index and spell of the current phase's rotation abilities that are due.
+    pub fn due_abilities(&self) -> impl Iterator<Item = (usize, u32)> + '_ {
+        self.fight
+            .as_ref()
+            .zip(self.phase())
+            .into_iter()
+            .flat_map(|(fight, phase)| {
+                fight
+                    .rotation
+                    .iter()
+                    .zip(&phase.rotation)
+                    .enumerate()
+                    .filter(|(_, (timer, _))| timer.is_finished())
+                    .map(|(index, (_, ability))| (index, ability.spell_id))
+            })
+    }
+
+    /// Restarts the timer of a rotation ability once the boss casts it.
+    pub fn ability_cast(&mut self, index: usize) {
+        let Some(interval) = self
+            .phase()
+            .and_then(|phase| phase.rotation.get(index))
+            .map(|ability| ability.interval)
+        else {
+            return;
+        };
+        if let Some(timer) = self
+            .fight
+            .as_mut()
+            .and_then(|fight| fight.rotation.get_mut(index))
+        {
+            *timer = Timer::from_seconds(interval, TimerMode::Once);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn threat_table(entries: &[(Entity, f32)]) -> ThreatTable {
+        let mut table = ThreatTable::default();
+        for &(entity, threat) in entries {
+            table.add_threat(entity, threat);
+        }
+        table
+    }
+
+    #[test]
+    fn melee_pulls_aggro_above_110_percent() {
+        let (tank, warrior) = (Entity::from_bits(1), Entity::from_bits(2));
+        let table = threat_table(&[(tank, 100.0), (warrior, 109.0)]);
+        assert_eq!(table.select_target(Some(tank), |_| true), Some(tank));
+
+        let table = threat_table(&[(tank, 100.0), (warrior, 111.0)]);
+        assert_eq!(table.select_target(Some(tank), |_| true), Some(warrior));
+    }
+
+    #[test]
+    fn ranged_pulls_aggro_above_130_percent() {
+        let (tank, mage) = (Entity::from_bits(1), Entity::from_bits(2));
+        let is_in_melee_range = |entity| entity == tank;
+        let table = threat_table(&[(tank, 100.0), (mage, 125.0)]);
+        assert_eq!(
+            table.select_target(Some(tank), is_in_melee_range),
+            Some(tank)
+        );
+
+        let table = threat_table(&[(tank, 100.0), (mage, 131.0)]);
+        assert_eq!(
+            table.select_target(Some(tank), is_in_melee_range),
+            Some(mage)
+        );
+    }
+
+    #[test]
+    fn taunt_forces_target_and_matches_top_threat() {
+        let (tank, mage) = (Entity::from_bits(1), Entity::from_bits(2));
+        let mut table = threat_table(&[(tank, 50.0), (mage, 200.0)]);
+        table.taunt(tank, 3.0);
+        assert_eq!(table.threat_of(tank), Some(200.0));
+        assert_eq!(table.select_target(Some(mage), |_| false), Some(tank));
+
+        // Once the taunt ends, the mage has to pull aggro again
+        table.taunt = None;
+        table.add_threat(mage, 10.0);
+        assert_eq!(table.select_target(Some(tank), |_| false), Some(tank));
+    }
+
+    #[test]
+    fn reached_waypoints_are_skipped() {
+        let mut path = AiPath {
+            goal: Some(Vec3::new(10.0, 0.0, 10.0)),
+            waypoints: VecDeque::from([
+                Vec3::new(5.0, 0.0, 0.0),
+                Vec3::new(5.0, 0.0, 5.0),
+                Vec3::new(10.0, 0.0, 10.0),
+            ]),
+            ..default()
+        };
+
+        assert_eq!(
+            path.next_waypoint(Vec3::ZERO),
+            Some(Vec3::new(5.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            path.next_waypoint(Vec3::new(4.9, 1.0, 0.0)),
+            Some(Vec3::new(5.0, 0.0, 5.0))
+        );
+        // The goal is left to the live movement target
+        assert_eq!(path.next_waypoint(Vec3::new(5.0, 0.0, 5.0)), None);
+        assert_eq!(path.waypoints.len(), 1);
+    }
+
+    fn patrol(mode: PatrolMode) -> Patrol {
+        let stops = (0..3)
+            .map(|i| PatrolStop {
+                position: Vec3::new(i as f32 * 10.0, 0.0, 0.0),
+                pause_secs: 0.0,
+            })
+            .collect();
+        Patrol::new(Arc::new(PatrolRoute { stops, mode }), None, Vec3::ZERO)
+    }
+
+    #[test]
+    fn loop_patrols_wrap_around() {
+        let mut patrol = patrol(PatrolMode::Loop);
+        let mut visited = vec![patrol.index];
+        for _ in 0..4 {
+            patrol.advance();
+            visited.push(patrol.index);
+        }
+        assert_eq!(visited, [0, 1, 2, 0, 1]);
+    }
+
+    #[test]
+    fn ping_pong_patrols_turn_around() {
+        let mut patrol = patrol(PatrolMode::PingPong);
+        let mut visited = vec![patrol.index];
+        for _ in 0..6 {
+            patrol.advance();
+            visited.push(patrol.index);
+        }
+        assert_eq!(visited, [0, 1, 2, 1, 0, 1, 2]);
+    }
+
+    #[test]
+    fn dropping_all_threat_removes_the_entry() {
+        let (rogue, tank) = (Entity::from_bits(1), Entity::from_bits(2));
+        let mut table = threat_table(&[(rogue, 100.0), (tank, 50.0)]);
+        table.scale_threat(rogue, 0.5);
+        assert_eq!(table.threat_of(rogue), Some(50.0));
+
+        table.scale_threat(rogue, 0.0);
+        assert_eq!(table.threat_of(rogue), None);
+        assert_eq!(table.select_target(Some(rogue), |_| true), Some(tank));
+    }
+}
@@ -1,36 +1,49 @@
-use super::components::{AiAbilityConfig, AiBrain, AiState};
-use crate::combat::{Abilities, CastSpellActionMessage, Casting};
+use super::components::{AiAbilityConfig, AiBrain, AiState, Encounter};
+use crate::combat::{
+    Abilities, Ability, AutoAttack, CastSpellActionMessage, CastTarget, Casting,
+    StartAttackMessage, StopAttackMessage, Weapon, has_line_of_sight, is_in_cone,
+};
+use avian3d::prelude::SpatialQuery;
 use bevy::prelude::*;
-use game_core::spells::{SpellLibrary, SpellLibraryHandle};
+use game_core::{
+    crowd_control::CrowdControlState,
+    spells::{AreaShape, SpellDef, SpellLibrary, SpellLibraryHandle, SpellTarget},
+};
 
-/// AI selects and casts the best available ability against its current target.
+/// AI selects and casts the best available ability against its current target. Bosses cast
+/// the due abilities of their encounter rotation first, in rotation order.
 #[allow(clippy::type_complexity)]
 pub fn ai_select_ability(
-    q_mobs: Query<(
+    mut q_mobs: Query<(
         Entity,
         &AiBrain,
         &Transform,
         &Abilities,
         &AiAbilityConfig,
         Option<&Casting>,
+        &CrowdControlState,
+        Option<&mut Encounter>,
     )>,
-    q_targets: Query<&Transform, Without<AiBrain>>,
+    q_targets: Query<&Transform>,
     library_handle: Res<SpellLibraryHandle>,
     assets: Res<Assets<SpellLibrary>>,
+    spatial_query: SpatialQuery,
     mut writer: MessageWriter<CastSpellActionMessage>,
 ) {
     let Some(library) = assets.get(&library_handle.0) else {
         return;
     };
 
-    for (entity, brain, transform, abilities, config, casting) in q_mobs.iter() {
+    for (entity, brain, transform, abilities, config, casting, crowd_control, mut encounter) in
+        q_mobs.iter_mut()
+    {
         // Only act in Combat state
         let AiState::Combat { target } = &brain.state else {
             continue;
         };
 
         // Already casting — respect the same "one cast at a time" rule as players
-        if casting.is_some() {
+        if casting.is_some() || !crowd_control.can_cast() {
             continue;
         }
 
@@ -38,27 +51,116 @@ pub fn ai_select_ability(
             continue;
         };
 
-        let distance = transform.translation.distance(target_transform.translation);
+        // Don't shoot through walls, hostile spells need line of sight to the target
+        let in_sight = has_line_of_sight(
+            &spatial_query,
+            transform.translation,
+            target_transform.translation,
+        );
 
-        // Pick best available ability: off cooldown, in range, highest priority
-        let best = abilities
-            .known
-            .iter()
-            .filter(|a| a.cooldown.is_finished())
-            .filter(|a| {
-                library
-                    .spells
-                    .get(&a.spell_id)
-                    .is_some_and(|spell| distance <= spell.range)
+        let is_castable = |a: &Ability| {
+            a.cooldown.is_finished()
+                && library.spells.get(&a.spell_id).is_some_and(|spell| {
+                    (!spell.triggers_gcd || abilities.global_cooldown.is_finished())
+                        && (spell.target != SpellTarget::Hostile
+                            || (in_sight
+                                && is_in_reach(spell, transform, target_transform.translation)))
+                })
+        };
+
+        // A due rotation ability waits until it can be cast rather than being skipped
+        let rotation = encounter.as_deref().and_then(|encounter| {
+            encounter.due_abilities().find_map(|(index, spell_id)| {
+                abilities
+                    .known
+                    .iter()
+                    .find(|a| a.spell_id == spell_id)
+                    .filter(|a| is_castable(a))
+                    .map(|a| (index, a))
             })
-            .max_by_key(|a| config.priorities.get(&a.spell_id).copied().unwrap_or(0));
+        });
+
+        // Pick best available ability: off cooldown, in range and sight, highest priority.
+        // Rotation spells are only cast on their timer.
+        let best = match rotation {
+            Some((index, ability)) => {
+                if let Some(encounter) = encounter.as_deref_mut() {
+                    encounter.ability_cast(index);
+                }
+                Some(ability)
+            }
+            None => abilities
+                .known
+                .iter()
+                .filter(|a| is_castable(a))
+                .filter(|a| {
+                    encounter
+                        .as_deref()
+                        .is_none_or(|encounter| !encounter.def.is_rotation_spell(a.spell_id))
+                })
+                .max_by_key(|a| config.priorities.get(&a.spell_id).copied().unwrap_or(0)),
+        };
 
         if let Some(ability) = best {
+            // Beneficial spells are cast on the mob itself
+            let target_entity = match library.spells.get(&ability.spell_id) {
+                Some(spell) if spell.target != SpellTarget::Hostile => entity,
+                _ => *target,
+            };
+
             writer.write(CastSpellActionMessage {
                 caster_entity: entity,
-                target_entity: *target,
+                target: CastTarget::Entity(target_entity),
                 spell_id: ability.spell_id,
             });
         }
     }
 }
+
+/// Armed mobs auto-attack their combat target, abilities are cast in between swings.
+/// The swing pauses while chasing and stops once the mob leaves combat.
+pub fn ai_auto_attack(
+    q_mobs: Query<(Entity, &AiBrain, Option<&AutoAttack>), With<Weapon>>,
+    mut start_writer: MessageWriter<StartAttackMessage>,
+    mut stop_writer: MessageWriter<StopAttackMessage>,
+) {
+    for (entity, brain, auto_attack) in q_mobs.iter() {
+        let attacking = auto_attack.map(|attack| attack.target);
+        match brain.state {
+            AiState::Combat { target } if attacking != Some(target) => {
+                start_writer.write(StartAttackMessage {
+                    attacker_entity: entity,
+                    target_entity: target,
+                });
+            }
+            AiState::Combat { .. } => {}
+            // Keep swinging at the target being chased, it resumes once back in range
+            AiState::Chase { target } if attacking.is_none_or(|current| current == target) => {}
+            _ if attacking.is_some() => {
+                stop_writer.write(StopAttackMessage {
+                    attacker_entity: entity,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether a hostile spell cast right now would reach a target at `target_position`.
+/// Caster-centred area spells are limited by their radius instead of the spell range.
+fn is_in_reach(spell: &SpellDef, caster: &Transform, target_position: Vec3) -> bool {
+    let distance = caster.translation.distance(target_position);
+    match spell.area.as_ref().map(|area| area.shape) {
+        Some(AreaShape::SelfRadius { radius }) => distance <= radius,
+        Some(AreaShape::Cone { radius, angle }) => {
+            distance <= radius
+                && is_in_cone(
+                    caster.translation,
+                    *caster.forward(),
+                    angle.to_radians(),
+                    target_position,
+                )
+        }
+        Some(AreaShape::Circle { .. }) | None => distance <= spell.range,
+    }
+}
@@ -0,0 +1,596 @@
+use super::components::{
+    AiBrain, AiMovement, AiState, Encounter, EncounterEvent, LeashAnchor, ThreatTable,
+};
+use crate::{
+    assets::{MonsterLibrary, MonsterLibraryHandle},
+    combat::{ApplySpellEffectMessage, Auras},
+    core::{Dead, InterestedClients, NetworkIdCounter},
+    networking::{OutgoingMessage, OutgoingMessageData},
+    telemetry::{AI_EVADES_TOTAL_METRIC, AI_STATE_TRANSITIONS_TOTAL_METRIC},
+    world::{insert_monster_ai, spawn_monster},
+};
+use bevy::prelude::*;
+use game_core::{
+    components::{LevelComponent, Vitals},
+    networking::{NetworkId, NetworkIdMapping},
+    spells::{SpellLibrary, SpellLibraryHandle},
+};
+use protocol::models::EncounterOutcome;
+use std::f32::consts::TAU;
+
+/// Adds spawn on a circle of this radius around the boss
+const ADD_SPAWN_DISTANCE: f32 = 4.0;
+
+/// Advances engaged encounters. Keeps the fight inside the encounter boundary, starts phases
+/// as the boss loses health, spawns their adds and enrages the boss once its timer runs out.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn tick_encounters(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_bosses: Query<
+        (
+            Entity,
+            &Transform,
+            &Vitals,
+            &LevelComponent,
+            &LeashAnchor,
+            &mut AiBrain,
+            &mut AiMovement,
+            &mut ThreatTable,
+            &mut Encounter,
+        ),
+        Without<Dead>,
+    >,
+    q_targets: Query<&Transform>,
+    monster_library_handle: Res<MonsterLibraryHandle>,
+    monster_assets: Res<Assets<MonsterLibrary>>,
+    spell_library_handle: Res<SpellLibraryHandle>,
+    spell_assets: Res<Assets<SpellLibrary>>,
+    mut net_id_counter: ResMut<NetworkIdCounter>,
+    mut net_entity_map: ResMut<NetworkIdMapping>,
+    mut apply_writer: MessageWriter<ApplySpellEffectMessage>,
+) {
+    let monster_library = monster_assets.get(&monster_library_handle.0);
+    let spell_library = spell_assets.get(&spell_library_handle.0);
+
+    for (
+        entity,
+        transform,
+        vitals,
+        level,
+        leash,
+        mut brain,
+        mut movement,
+        mut threat_table,
+        mut encounter,
+    ) in q_bosses.iter_mut()
+    {
+        if encounter.fight.is_none() {
+            continue;
+        }
+        let center = encounter.center;
+        let boundary_sq = encounter.def.boundary_radius * encounter.def.boundary_radius;
+
+        // Pulling the boss out of its room resets the encounter
+        if transform.translation.distance_squared(center) > boundary_sq {
+            let from = match brain.state {
+                AiState::Chase { .. } => "chase",
+                AiState::Combat { .. } => "combat",
+                _ => continue,
+            };
+            brain.state = AiState::Evading;
+            movement.target_position = Some(leash.position);
+            threat_table.clear();
+            metrics::counter!(AI_STATE_TRANSITIONS_TOTAL_METRIC, "from" => from, "to" => "evading")
+                .increment(1);
+            metrics::counter!(AI_EVADES_TOTAL_METRIC).increment(1);
+            tracing::debug!("ai transition: {from} -> evading (left encounter boundary)");
+            continue;
+        }
+
+        // Players outside the boundary drop out of the fight
+        threat_table.entries.retain(|entry| {
+            q_targets
+                .get(entry.entity)
+                .is_ok_and(|target| target.translation.distance_squared(center) <= boundary_sq)
+        });
+
+        let health = vitals.hp as f32 / vitals.max_hp.max(1) as f32;
+        for event in encounter.advance(time.delta(), health) {
+            match event {
+                EncounterEvent::PhaseStarted(phase) => {
+                    tracing::info!(boss = ?entity, encounter = %encounter.def.name, %phase, "encounter phase started");
+                    let Some(monster_library) = monster_library else {
+                        continue;
+                    };
+
+                    let def = encounter.def.clone();
+                    for add in &def.phases[phase].adds {
+                        let Some(blueprint) = monster_library.types.get(&add.monster_id) else {
+                            tracing::warn!(encounter = %def.name, monster_id = ?add.monster_id, "encounter add is not in monsters.ron");
+                            continue;
+                        };
+
+                        for index in 0..add.count {
+                            let angle = TAU * index as f32 / add.count as f32;
+                            let offset = Vec3::new(angle.cos(), 0.0, angle.sin());
+                            let add_transform = Transform::from_translation(
+                                transform.translation + offset * ADD_SPAWN_DISTANCE,
+                            );
+                            let add_entity = spawn_monster(
+                                &mut commands,
+                                &add.monster_id,
+                                blueprint,
+                                add_transform,
+                                level.0,
+                                spell_library,
+                                &mut net_id_counter,
+                                &mut net_entity_map,
+                            );
+
+                            if let Some(ai_def) = &blueprint.ai {
+                                let mut entity_commands = commands.entity(add_entity);
+                                insert_monster_ai(
+                                    &mut entity_commands,
+                                    ai_def,
+                                    &add_transform,
+                                    None,
+                                );
+                                // Adds go for everyone the boss is fighting
+                                let mut add_threat = ThreatTable::default();
+                                for entry in &threat_table.entries {
+                                    add_threat.add_threat(entry.entity, 0.0);
+                                }
+                                entity_commands.insert(add_threat);
+                            }
+
+                            if let Some(fight) = encounter.fight.as_mut() {
+                                fight.adds.push(add_entity);
+                            }
+                        }
+                    }
+                }
+                EncounterEvent::Enraged(spell_id) => {
+                    tracing::info!(boss = ?entity, encounter = %encounter.def.name, "boss enraged");
+                    apply_writer.write(ApplySpellEffectMessage {
+                        caster_entity: entity,
+                        caster_client_id: None,
+                        target_entity: entity,
+                        spell_id,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Starts an encounter once its boss is pulled, and ends it once the boss dies, runs out of
+/// targets or evades. A wipe or reset despawns the adds and removes the auras the boss put on
+/// itself, like its enrage. The boss heals up on its way home like any other mob.
+#[allow(clippy::type_complexity)]
+pub fn update_encounters(
+    mut commands: Commands,
+    mut q_bosses: Query<(
+        Entity,
+        &NetworkId,
+        &AiBrain,
+        &mut Encounter,
+        &InterestedClients,
+        Option<&mut Auras>,
+        Has<Dead>,
+    )>,
+    q_adds: Query<(&NetworkId, &InterestedClients), Without<Encounter>>,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    for (entity, network_id, brain, mut encounter, interested, auras, dead) in q_bosses.iter_mut() {
+        let recipients: Vec<_> = interested.clients.iter().copied().collect();
+        let in_fight = matches!(brain.state, AiState::Chase { .. } | AiState::Combat { .. });
+
+        if encounter.fight.is_none() {
+            if dead || !in_fight {
+                continue;
+            }
+            encounter.engage();
+            tracing::info!(boss = ?entity, encounter = %encounter.def.name, "encounter started");
+            writer.write(OutgoingMessage::new(
+                recipients,
+                OutgoingMessageData::EncounterStarted {
+                    boss_network_id: *network_id,
+                    name: encounter.def.name.clone(),
+                },
+            ));
+            continue;
+        }
+
+        let outcome = if dead {
+            EncounterOutcome::Defeated
+        } else if in_fight {
+            continue;
+        } else if brain.state == AiState::Evading {
+            EncounterOutcome::Reset
+        } else {
+            EncounterOutcome::Wipe
+        };
+
+        let adds = encounter.end();
+        if outcome != EncounterOutcome::Defeated {
+            for add in adds {
+                if let Ok((add_network_id, add_interested)) = q_adds.get(add) {
+                    writer.write(OutgoingMessage::new(
+                        add_interested.clients.iter().copied().collect(),
+                        OutgoingMessageData::Despawn(*add_network_id),
+                    ));
+                    commands.entity(add).despawn();
+                }
+            }
+            if let Some(mut auras) = auras {
+                auras.expire_from(entity);
+            }
+        }
+
+        tracing::info!(boss = ?entity, encounter = %encounter.def.name, ?outcome, "encounter ended");
+        writer.write(OutgoingMessage::new(
+            recipients,
+            OutgoingMessageData::EncounterEnded {
+                boss_network_id: *network_id,
+                name: encounter.def.name.clone(),
+                outcome,
+            },
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ai::{
+            components::AiPath,
+            state::ai_state_transitions,
+            threat::{cleanup_threat_tables, update_threat_on_damage},
+        },
+        assets::{EncounterDef, EncounterPhaseDef, EnrageDef, RotationAbilityDef},
+        combat::{
+            Abilities, CombatLogMessage, DamageDealtMessage, on_entity_death, on_vitals_changed,
+        },
+    };
+    use game_core::spells::{SpellDef, SpellTarget};
+    use protocol::server::AUTO_ATTACK_VISUAL_ID;
+    use std::{collections::HashMap, sync::Arc, time::Duration};
+
+    const STEP: Duration = Duration::from_millis(100);
+    const CLEAVE_SPELL: u32 = 104;
+    const ENRAGE_SPELL: u32 = 105;
+
+    #[derive(Resource, Default)]
+    struct Recorded {
+        outgoing: Vec<OutgoingMessageData>,
+        applied: Vec<u32>,
+    }
+
+    fn record(
+        mut outgoing: MessageReader<OutgoingMessage>,
+        mut applied: MessageReader<ApplySpellEffectMessage>,
+        mut recorded: ResMut<Recorded>,
+    ) {
+        recorded
+            .outgoing
+            .extend(outgoing.read().map(|message| message.data.clone()));
+        recorded
+            .applied
+            .extend(applied.read().map(|message| message.spell_id));
+    }
+
+    /// Runs an encounter headless. Fake players are actors the test scripts hitting and dying,
+    /// threat, the boss' state transitions and deaths run through the real systems.
+    struct Harness {
+        app: App,
+        boss: Entity,
+    }
+
+    impl Harness {
+        fn new() -> Self {
+            let def = EncounterDef {
+                name: "Skeleton Lord".to_string(),
+                boundary_radius: 20.0,
+                phases: vec![
+                    EncounterPhaseDef {
+                        health_below: 1.0,
+                        rotation: vec![RotationAbilityDef {
+                            spell_id: CLEAVE_SPELL,
+                            interval: 10.0,
+                            delay: Some(2.0),
+                        }],
+                        adds: Vec::new(),
+                    },
+                    EncounterPhaseDef {
+                        health_below: 0.5,
+                        rotation: Vec::new(),
+                        adds: Vec::new(),
+                    },
+                ],
+                enrage: Some(EnrageDef {
+                    after: 5.0,
+                    spell_id: ENRAGE_SPELL,
+                }),
+            };
+
+            let mut app = App::new();
+            app.add_message::<ApplySpellEffectMessage>()
+                .add_message::<DamageDealtMessage>()
+                .add_message::<CombatLogMessage>()
+                .add_message::<OutgoingMessage>()
+                .init_resource::<Time>()
+                .init_resource::<Recorded>()
+                .init_resource::<Assets<MonsterLibrary>>()
+                .init_resource::<Assets<SpellLibrary>>()
+                .insert_resource(MonsterLibraryHandle(Handle::default()))
+                .init_resource::<NetworkIdCounter>()
+                .init_resource::<NetworkIdMapping>()
+                .add_observer(on_entity_death)
+                .add_systems(
+                    Update,
+                    (
+                        on_vitals_changed,
+                        update_threat_on_damage,
+                        cleanup_threat_tables,
+                        ai_state_transitions,
+                        tick_encounters,
+                        update_encounters,
+                        record,
+                    )
+                        .chain(),
+                );
+
+            let cleave = SpellDef {
+                name: "Cleave".to_string(),
+                target: SpellTarget::default(),
+                effects: Vec::new(),
+                area: None,
+                range: 5.0,
+                projectile_speed: None,
+                cooldown: 10.0,
+                cost: 0,
+                triggers_gcd: false,
+                casting_duration: 0.0,
+                castable_while_moving: false,
+                channel: None,
+                on_next_swing: false,
+                threat_multiplier: 1.0,
+                bonus_threat: 0.0,
+                visual_id: CLEAVE_SPELL,
+            };
+            let library =
+                app.world_mut()
+                    .resource_mut::<Assets<SpellLibrary>>()
+                    .add(SpellLibrary {
+                        spells: HashMap::from([(CLEAVE_SPELL, cleave)]),
+                    });
+            app.insert_resource(SpellLibraryHandle(library));
+
+            let boss = app
+                .world_mut()
+                .spawn((
+                    Transform::default(),
+                    Vitals {
+                        hp: 1000,
+                        max_hp: 1000,
+                    },
+                    LevelComponent(10),
+                    LeashAnchor {
+                        position: Vec3::ZERO,
+                        max_range: 40.0,
+                    },
+                    AiBrain::default(),
+                    AiMovement::default(),
+                    AiPath::default(),
+                    Abilities::new(&[CLEAVE_SPELL], &HashMap::new()),
+                    ThreatTable::default(),
+                    Encounter::new(Arc::new(def), Vec3::ZERO),
+                    NetworkId(1),
+                    InterestedClients::default(),
+                    Auras::default(),
+                ))
+                .id();
+
+            Self { app, boss }
+        }
+
+        fn spawn_player(&mut self, position: Vec3) -> Entity {
+            let network_id = self
+                .app
+                .world_mut()
+                .resource_mut::<NetworkIdCounter>()
+                .allocate();
+            self.app
+                .world_mut()
+                .spawn((
+                    Transform::from_translation(position),
+                    Vitals {
+                        hp: 100,
+                        max_hp: 100,
+                    },
+                    network_id,
+                    InterestedClients::default(),
+                ))
+                .id()
+        }
+
+        /// The player hits the boss, which puts them on its threat table.
+        fn attack(&mut self, player: Entity, amount: i32) {
+            let world = self.app.world_mut();
+            world.get_mut::<Vitals>(self.boss).unwrap().hp -= amount;
+            world.write_message(DamageDealtMessage {
+                attacker_entity: player,
+                target_entity: self.boss,
+                spell_id: AUTO_ATTACK_VISUAL_ID,
+                amount,
+            });
+        }
+
+        fn kill(&mut self, player: Entity) {
+            self.app.world_mut().get_mut::<Vitals>(player).unwrap().hp = 0;
+        }
+
+        /// Stands in for the health the boss regenerates on its way home.
+        fn heal_boss(&mut self) {
+            let mut vitals = self.app.world_mut().get_mut::<Vitals>(self.boss).unwrap();
+            vitals.hp = vitals.max_hp;
+        }
+
+        fn run(&mut self, duration: Duration) {
+            for _ in 0..duration.as_millis() / STEP.as_millis() {
+                self.app.world_mut().resource_mut::<Time>().advance_by(STEP);
+                self.app.update();
+            }
+        }
+
+        fn brain(&self) -> &AiBrain {
+            self.app.world().get::<AiBrain>(self.boss).unwrap()
+        }
+
+        fn encounter(&self) -> &Encounter {
+            self.app.world().get::<Encounter>(self.boss).unwrap()
+        }
+
+        fn recorded(&self) -> &Recorded {
+            self.app.world().resource::<Recorded>()
+        }
+
+        fn outcome(&self) -> Option<EncounterOutcome> {
+            self.recorded().outgoing.iter().find_map(|data| match data {
+                OutgoingMessageData::EncounterEnded { outcome, .. } => Some(*outcome),
+                _ => None,
+            })
+        }
+    }
+
+    #[test]
+    fn phases_start_as_the_boss_loses_health_and_it_enrages_once() {
+        let mut harness = Harness::new();
+        let player = harness.spawn_player(Vec3::new(3.0, 0.0, 0.0));
+        harness.attack(player, 10);
+        harness.run(Duration::from_secs(1));
+
+        assert_eq!(harness.brain().state, AiState::Combat { target: player });
+        assert!(
+            harness
+                .recorded()
+                .outgoing
+                .iter()
+                .any(|data| matches!(data, OutgoingMessageData::EncounterStarted { .. }))
+        );
+        assert_eq!(
+            harness.encounter().fight.as_ref().unwrap().phases_started,
+            1
+        );
+
+        harness.run(Duration::from_secs(2));
+        assert_eq!(
+            harness.encounter().due_abilities().collect::<Vec<_>>(),
+            [(0, CLEAVE_SPELL)]
+        );
+
+        harness.attack(player, 590);
+        harness.run(Duration::from_secs(1));
+        assert_eq!(
+            harness.encounter().fight.as_ref().unwrap().phases_started,
+            2
+        );
+        assert!(harness.recorded().applied.is_empty());
+
+        harness.run(Duration::from_secs(5));
+        assert_eq!(harness.recorded().applied, [ENRAGE_SPELL]);
+        assert_eq!(harness.outcome(), None);
+    }
+
+    #[test]
+    fn the_encounter_wipes_once_everyone_is_dead() {
+        let mut harness = Harness::new();
+        let tank = harness.spawn_player(Vec3::new(3.0, 0.0, 0.0));
+        let healer = harness.spawn_player(Vec3::new(8.0, 0.0, 0.0));
+        harness.attack(tank, 300);
+        harness.attack(healer, 300);
+        harness.run(Duration::from_secs(1));
+
+        // The boss turns to the next player on its table, the fight goes on
+        harness.kill(tank);
+        harness.run(Duration::from_secs(1));
+        assert!(matches!(
+            harness.brain().state,
+            AiState::Chase { target } | AiState::Combat { target } if target == healer
+        ));
+        assert_eq!(harness.outcome(), None);
+
+        // Nobody is left to fight, the boss walks home
+        harness.kill(healer);
+        harness.run(Duration::from_secs(1));
+        assert_eq!(harness.outcome(), Some(EncounterOutcome::Wipe));
+        assert!(harness.encounter().fight.is_none());
+        assert!(
+            harness
+                .app
+                .world()
+                .get::<ThreatTable>(harness.boss)
+                .unwrap()
+                .entries
+                .is_empty()
+        );
+
+        // The next pull starts from the first phase
+        harness.heal_boss();
+        let player = harness.spawn_player(Vec3::new(3.0, 0.0, 0.0));
+        harness.attack(player, 10);
+        harness.run(Duration::from_secs(1));
+        assert_eq!(
+            harness.encounter().fight.as_ref().unwrap().phases_started,
+            1
+        );
+    }
+
+    #[test]
+    fn pulling_the_boss_out_of_its_boundary_resets_the_encounter() {
+        let mut harness = Harness::new();
+        let tank = harness.spawn_player(Vec3::new(3.0, 0.0, 0.0));
+        let kiter = harness.spawn_player(Vec3::new(30.0, 0.0, 0.0));
+        harness.attack(tank, 10);
+        harness.run(Duration::from_secs(1));
+        harness.attack(kiter, 50);
+        harness.run(Duration::from_secs(1));
+
+        // Players outside the boundary can't hold the boss' attention
+        let threat_table = harness
+            .app
+            .world()
+            .get::<ThreatTable>(harness.boss)
+            .unwrap();
+        assert_eq!(threat_table.threat_of(kiter), None);
+        assert_eq!(threat_table.threat_of(tank), Some(10.0));
+        assert_eq!(harness.brain().state, AiState::Combat { target: tank });
+
+        harness
+            .app
+            .world_mut()
+            .get_mut::<Transform>(harness.boss)
+            .unwrap()
+            .translation = Vec3::new(25.0, 0.0, 0.0);
+        harness.run(Duration::from_secs(1));
+
+        assert_eq!(harness.brain().state, AiState::Evading);
+        assert_eq!(harness.outcome(), Some(EncounterOutcome::Reset));
+    }
+
+    #[test]
+    fn killing_the_boss_defeats_the_encounter() {
+        let mut harness = Harness::new();
+        let player = harness.spawn_player(Vec3::new(3.0, 0.0, 0.0));
+        harness.attack(player, 10);
+        harness.run(Duration::from_secs(1));
+
+        harness.attack(player, 990);
+        harness.run(Duration::from_secs(1));
+
+        assert!(harness.app.world().get::<Dead>(harness.boss).is_some());
+        assert_eq!(harness.outcome(), Some(EncounterOutcome::Defeated));
+    }
+}
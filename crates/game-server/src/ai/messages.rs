@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+use protocol::models::PetStance;
+
+/// Written when a summon spell lands or a player logs in with a saved pet.
+#[derive(Message, Debug)]
+pub struct SummonPetMessage {
+    pub owner_entity: Entity,
+    pub spell_id: u32,
+}
+
+#[derive(Message, Debug)]
+pub struct SetPetStanceMessage {
+    pub owner_entity: Entity,
+    pub stance: PetStance,
+}
+
+#[derive(Message, Debug)]
+pub struct PetAttackMessage {
+    pub owner_entity: Entity,
+    pub target_entity: Entity,
+}
+
+#[derive(Message, Debug)]
+pub struct DismissPetMessage {
+    pub owner_entity: Entity,
+}
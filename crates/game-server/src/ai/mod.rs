@@ -1,7 +1,13 @@
 pub mod components;
 mod decision;
+mod encounter;
 mod leash;
+pub mod messages;
 mod movement;
+mod pathfinding;
+mod patrol;
+mod pet;
+mod social;
 mod state;
 mod threat;
 mod wander;
@@ -9,22 +15,50 @@ mod wander;
 use bevy::prelude::*;
 
 pub use components::*;
+pub use messages::*;
 
 pub struct AiPlugin;
 
 impl Plugin for AiPlugin {
     fn build(&self, app: &mut App) {
+        app.add_message::<SummonPetMessage>();
+        app.add_message::<SetPetStanceMessage>();
+        app.add_message::<PetAttackMessage>();
+        app.add_message::<DismissPetMessage>();
+
         app.add_systems(
             FixedUpdate,
             (
                 (
                     threat::detect_players,
                     threat::update_threat_on_damage,
+                    threat::update_threat_on_healing,
+                    threat::apply_spell_threat,
+                    threat::tick_taunts,
                     threat::cleanup_threat_tables,
+                    social::call_for_help,
                 )
                     .chain(),
-                state::ai_state_transitions,
-                (wander::wander, decision::ai_select_ability),
+                (
+                    pet::summon_pets,
+                    pet::set_pet_stances,
+                    pet::dismiss_pets,
+                    pet::despawn_orphaned_pets,
+                    pet::order_pet_attacks,
+                    pet::assist_owners,
+                    pet::detect_hostiles_for_pets,
+                )
+                    .chain(),
+                (state::ai_state_transitions, pet::pet_state_transitions),
+                social::evade_packs,
+                (encounter::tick_encounters, encounter::update_encounters).chain(),
+                (
+                    wander::wander,
+                    patrol::patrol,
+                    decision::ai_select_ability,
+                    decision::ai_auto_attack,
+                ),
+                pathfinding::request_ai_paths,
                 (movement::apply_ai_movement, leash::reset_evading_mobs),
             )
                 .chain(),
@@ -1,10 +1,11 @@
-use super::components::{AiBrain, AiMovement, AiState};
+use super::components::{AiBrain, AiMovement, AiPath, AiState, Patrol, Pet};
 use crate::combat::Casting;
 use avian3d::prelude::{Collider, SpatialQuery};
 use bevy::prelude::*;
 use game_core::{
     character_controller::{self, CharacterVelocityY},
     components::{GroundedComponent, MovementSpeedComponent},
+    crowd_control::CrowdControlState,
 };
 
 /// Computes and applies AI movement using the same character_move_step as players.
@@ -21,11 +22,32 @@ pub fn apply_ai_movement(
         &MovementSpeedComponent,
         &mut CharacterVelocityY,
         Option<&Casting>,
+        &CrowdControlState,
+        Has<Pet>,
+        Option<&mut AiPath>,
+        Option<&Patrol>,
     )>,
 ) {
-    for (entity, brain, movement, mut transform, collider, speed, mut vel_y, casting) in
-        q_mobs.iter_mut()
+    for (
+        entity,
+        brain,
+        movement,
+        mut transform,
+        collider,
+        speed,
+        mut vel_y,
+        casting,
+        crowd_control,
+        is_pet,
+        path,
+        patrol,
+    ) in q_mobs.iter_mut()
     {
+        // Stunned and rooted mobs stay put, feared mobs are moved by `move_feared_actors`
+        if !crowd_control.can_move() {
+            continue;
+        }
+
         // Don't move while casting a non-movable spell
         if let Some(cast) = casting
             && !cast.castable_while_moving
@@ -47,13 +69,20 @@ pub fn apply_ai_movement(
             continue;
         }
 
-        // Calculate direction toward target
-        let direction = Vec3::new(to_target.x, 0.0, to_target.z).normalize_or_zero();
+        // Steer along the navmesh path, the last stretch heads straight at the target
+        let steer_pos = path
+            .and_then(|mut path| path.next_waypoint(mob_pos))
+            .unwrap_or(target_pos);
+        let to_steer = steer_pos - mob_pos;
+        let direction = Vec3::new(to_steer.x, 0.0, to_steer.z).normalize_or_zero();
         let yaw = (-direction.x).atan2(-direction.z);
 
-        // Speed varies by state: wander is slower, evade is faster
+        // Speed varies by state: wander and patrol are slower, evade is faster, pets keep up with
+        // their owner and patrol followers catch up with their leader
         let move_speed = match brain.state {
-            AiState::Idle => speed.0 * 0.4,
+            AiState::Idle if !is_pet => speed.0 * 0.4,
+            AiState::Patrol if patrol.is_some_and(|patrol| patrol.following) => speed.0 * 0.6,
+            AiState::Patrol => speed.0 * 0.4,
             AiState::Evading => speed.0 * 2.0,
             _ => speed.0,
         };
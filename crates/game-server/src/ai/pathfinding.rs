@@ -0,0 +1,59 @@
+use super::components::{AiMovement, AiPath, REPATH_DISTANCE};
+use crate::world::ZoneNavMesh;
+use bevy::prelude::*;
+use bevy_tokio_tasks::TokioTasksRuntime;
+
+/// Queries a navmesh path whenever an AI's movement target is new or has moved too far from the
+/// goal of its cached path. The A* search runs on the tokio runtime, the AI walks straight at
+/// its target until the result arrives. Without a navmesh all AI moves in straight lines.
+pub fn request_ai_paths(
+    mut q_mobs: Query<(Entity, &Transform, &AiMovement, &mut AiPath)>,
+    navmesh: Option<Res<ZoneNavMesh>>,
+    runtime: Res<TokioTasksRuntime>,
+) {
+    let Some(navmesh) = navmesh else {
+        return;
+    };
+
+    for (entity, transform, movement, mut path) in q_mobs.iter_mut() {
+        let Some(goal) = movement.target_position else {
+            if path.goal.is_some() {
+                path.clear();
+            }
+            continue;
+        };
+
+        if path.goal.is_some_and(|cached| {
+            cached.distance_squared(goal) <= REPATH_DISTANCE * REPATH_DISTANCE
+        }) {
+            continue;
+        }
+
+        path.clear();
+        path.goal = Some(goal);
+        let request = path.request;
+        let start = transform.translation;
+        let navmesh = navmesh.0.clone();
+
+        runtime.spawn_background_task(async move |mut ctx| {
+            let waypoints = navmesh.find_path(start, goal);
+            ctx.run_on_main_thread(move |ctx| {
+                // The mob may have despawned or asked for another path in the meantime
+                let Some(mut path) = ctx.world.get_mut::<AiPath>(entity) else {
+                    return;
+                };
+                if path.request != request {
+                    return;
+                }
+                match waypoints {
+                    Some(waypoints) => path.waypoints = waypoints.into(),
+                    None => {
+                        tracing::debug!(?entity, ?goal, "no path to movement target");
+                        path.unreachable = true;
+                    }
+                }
+            })
+            .await;
+        });
+    }
+}
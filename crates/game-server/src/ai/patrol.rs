@@ -0,0 +1,132 @@
+use super::components::{AiBrain, AiMovement, AiState, LeashAnchor, Patrol};
+use crate::world::Spawned;
+use bevy::{platform::collections::HashMap, prelude::*};
+use game_core::{networking::NetworkId, zone::Formation};
+
+/// Patrollers count a stop or formation slot as reached this close to it
+const PATROL_STOP_DISTANCE: f32 = 1.0;
+/// Distance between the members of a patrol formation
+const FORMATION_SPACING: f32 = 2.5;
+
+/// Snapshot of a formation leader, taken before the followers are moved.
+struct Leader {
+    transform: Transform,
+    index: usize,
+    reverse: bool,
+    patrolling: bool,
+}
+
+/// Walks patrolling mobs from stop to stop and waits at stops with a pause. Followers of a
+/// patrol group walk to their formation slot behind the leader and keep track of the leader's
+/// progress, so the next oldest mob picks up the route if the leader dies.
+#[allow(clippy::type_complexity)]
+pub fn patrol(
+    time: Res<Time>,
+    mut q_mobs: Query<(
+        Entity,
+        &NetworkId,
+        &AiBrain,
+        &Transform,
+        &mut Patrol,
+        &mut AiMovement,
+        &mut LeashAnchor,
+        Option<&Spawned>,
+    )>,
+) {
+    // Mobs of a spawn point with a formation form one group, the oldest mob leads
+    let mut groups: HashMap<Entity, Vec<(NetworkId, Entity)>> = HashMap::new();
+    for (entity, network_id, _, _, patrol, _, _, spawned) in q_mobs.iter() {
+        if patrol.formation.is_some()
+            && let Some(spawned) = spawned
+        {
+            groups
+                .entry(spawned.spawner)
+                .or_default()
+                .push((*network_id, entity));
+        }
+    }
+
+    let mut leaders: HashMap<Entity, Leader> = HashMap::new();
+    let mut slots: HashMap<Entity, (Entity, usize)> = HashMap::new();
+    for members in groups.values_mut() {
+        members.sort_unstable();
+        let leader = members[0].1;
+        let Ok((_, _, brain, transform, patrol, ..)) = q_mobs.get(leader) else {
+            continue;
+        };
+        leaders.insert(
+            leader,
+            Leader {
+                transform: *transform,
+                index: patrol.index,
+                reverse: patrol.reverse,
+                patrolling: brain.state == AiState::Patrol,
+            },
+        );
+        for (slot, &(_, follower)) in members.iter().enumerate().skip(1) {
+            slots.insert(follower, (leader, slot));
+        }
+    }
+
+    for (entity, _, brain, transform, mut patrol, mut movement, mut leash, _) in q_mobs.iter_mut() {
+        if brain.state != AiState::Patrol {
+            continue;
+        }
+
+        // Patrollers leash to and return to the spot where they left their route
+        leash.position = transform.translation;
+        movement.stop_distance = PATROL_STOP_DISTANCE;
+
+        if let Some((leader, slot)) = slots.get(&entity)
+            && let Some(leader) = leaders.get(leader)
+        {
+            patrol.index = leader.index;
+            patrol.reverse = leader.reverse;
+            patrol.pause = None;
+            patrol.following = true;
+            // Followers hold their position while the leader fights
+            let offset = formation_offset(patrol.formation.unwrap_or(Formation::Column), *slot);
+            movement.target_position = leader
+                .patrolling
+                .then(|| leader.transform.translation + leader.transform.rotation * offset);
+            continue;
+        }
+        patrol.following = false;
+
+        let Some(stop) = patrol.current_stop().copied() else {
+            continue;
+        };
+
+        if let Some(timer) = &mut patrol.pause {
+            movement.target_position = None;
+            if timer.tick(time.delta()).is_finished() {
+                patrol.pause = None;
+                patrol.advance();
+            }
+            continue;
+        }
+
+        movement.target_position = Some(stop.position);
+        if transform.translation.xz().distance(stop.position.xz()) <= PATROL_STOP_DISTANCE {
+            if stop.pause_secs > 0.0 {
+                patrol.pause = Some(Timer::from_seconds(stop.pause_secs, TimerMode::Once));
+                movement.target_position = None;
+            } else {
+                patrol.advance();
+            }
+        }
+    }
+}
+
+/// Offset of a follower's slot from the leader in the leader's local space, where -Z is
+/// forward. Slots count from 1, the leader holds slot 0.
+fn formation_offset(formation: Formation, slot: usize) -> Vec3 {
+    match formation {
+        Formation::Column => Vec3::Z * slot as f32 * FORMATION_SPACING,
+        Formation::Wedge => {
+            let row = slot.div_ceil(2) as f32;
+            let side = if slot % 2 == 1 { -1.0 } else { 1.0 };
+            Vec3::new(side * row, 0.0, row) * FORMATION_SPACING
+        }
+    }
+}
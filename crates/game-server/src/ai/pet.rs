@@ -0,0 +1,372 @@
+use super::{
+    components::{
+        AggroRadius, AiAbilityConfig, AiBrain, AiMovement, AiPath, AiState, PET_LEASH_RANGE, Pet,
+    },
+    messages::{DismissPetMessage, PetAttackMessage, SetPetStanceMessage, SummonPetMessage},
+};
+use crate::{
+    assets::{ContentId, MonsterLibrary, MonsterLibraryHandle, Reactions},
+    combat::{Abilities, DamageDealtMessage, MELEE_RANGE},
+    core::{ClientIdComponent, Dead, GridCell, InterestedClients, NetworkIdCounter},
+    networking::{OutgoingMessage, OutgoingMessageData},
+    world::{SpatialGrid, spawn_monster},
+};
+use bevy::prelude::*;
+use game_core::{
+    components::{LevelComponent, Vitals},
+    networking::{NetworkId, NetworkIdMapping},
+    spells::{SpellEffect, SpellLibrary, SpellLibraryHandle, SpellTarget},
+};
+use protocol::models::{PetStance, Reaction};
+
+/// Aggro radius of aggressive pets whose monster has no AI configured
+const DEFAULT_PET_AGGRO_RADIUS: f32 = 10.0;
+/// Idle pets catch up once their owner is further away than this
+const PET_FOLLOW_DISTANCE: f32 = 3.0;
+/// Pets are summoned this far to the right of their owner
+const PET_SPAWN_OFFSET: f32 = 2.0;
+
+/// Spawns the monster of a summon spell as the owner's pet, replacing its current pet.
+#[allow(clippy::too_many_arguments)]
+pub fn summon_pets(
+    mut commands: Commands,
+    mut reader: MessageReader<SummonPetMessage>,
+    q_owners: Query<
+        (
+            &Transform,
+            &NetworkId,
+            &LevelComponent,
+            Option<&ClientIdComponent>,
+        ),
+        Without<Dead>,
+    >,
+    q_pets: Query<(Entity, &Pet, &NetworkId, &InterestedClients)>,
+    spell_library_handle: Res<SpellLibraryHandle>,
+    spell_assets: Res<Assets<SpellLibrary>>,
+    monster_library_handle: Res<MonsterLibraryHandle>,
+    monster_assets: Res<Assets<MonsterLibrary>>,
+    mut net_id_counter: ResMut<NetworkIdCounter>,
+    mut net_entity_map: ResMut<NetworkIdMapping>,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    let (Some(spell_library), Some(monster_library)) = (
+        spell_assets.get(&spell_library_handle.0),
+        monster_assets.get(&monster_library_handle.0),
+    ) else {
+        return;
+    };
+
+    for msg in reader.read() {
+        let Ok((owner_transform, owner_id, owner_level, owner_client_id)) =
+            q_owners.get(msg.owner_entity)
+        else {
+            continue;
+        };
+
+        let Some(monster_key) = spell_library.spells.get(&msg.spell_id).and_then(|spell| {
+            spell.effects.iter().find_map(|effect| match effect {
+                SpellEffect::SummonPet { monster } => Some(monster),
+                _ => None,
+            })
+        }) else {
+            tracing::warn!(spell_id = %msg.spell_id, "tried to summon a pet with a spell that summons none");
+            continue;
+        };
+        let monster_id = ContentId::from(monster_key);
+        let Some(blueprint) = monster_library.types.get(&monster_id) else {
+            tracing::warn!(%monster_key, "tried to summon a pet that is not in monsters.ron");
+            continue;
+        };
+
+        for (pet_entity, pet, network_id, interested) in q_pets.iter() {
+            if pet.owner == msg.owner_entity {
+                despawn_pet(
+                    &mut commands,
+                    &mut writer,
+                    pet_entity,
+                    network_id,
+                    interested,
+                );
+            }
+        }
+
+        let transform = owner_transform.with_translation(
+            owner_transform.translation + owner_transform.right() * PET_SPAWN_OFFSET,
+        );
+        let pet_entity = spawn_monster(
+            &mut commands,
+            &monster_id,
+            blueprint,
+            transform,
+            owner_level.0,
+            Some(spell_library),
+            &mut net_id_counter,
+            &mut net_entity_map,
+        );
+
+        let ai_def = blueprint.ai.as_ref();
+        commands.entity(pet_entity).insert((
+            Pet {
+                owner: msg.owner_entity,
+                owner_id: *owner_id,
+                owner_client_id: owner_client_id.map(|client_id| client_id.0),
+                spell_id: msg.spell_id,
+                stance: PetStance::default(),
+            },
+            AiBrain::default(),
+            AiMovement::default(),
+            AiPath::default(),
+            AiAbilityConfig {
+                priorities: ai_def
+                    .map(|ai| ai.ability_priorities.clone())
+                    .unwrap_or_default(),
+            },
+            AggroRadius(ai_def.map_or(DEFAULT_PET_AGGRO_RADIUS, |ai| ai.aggro_radius)),
+        ));
+        tracing::debug!(owner = ?msg.owner_entity, name = %blueprint.name, "summoned pet");
+    }
+}
+
+/// Passive pets also stop attacking.
+pub fn set_pet_stances(
+    mut reader: MessageReader<SetPetStanceMessage>,
+    mut q_pets: Query<(&mut Pet, &mut AiBrain)>,
+) {
+    for msg in reader.read() {
+        for (mut pet, mut brain) in q_pets.iter_mut() {
+            if pet.owner != msg.owner_entity {
+                continue;
+            }
+            pet.stance = msg.stance;
+            if msg.stance == PetStance::Passive {
+                brain.state = AiState::Idle;
+            }
+        }
+    }
+}
+
+pub fn dismiss_pets(
+    mut commands: Commands,
+    mut reader: MessageReader<DismissPetMessage>,
+    q_pets: Query<(Entity, &Pet, &NetworkId, &InterestedClients)>,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    for msg in reader.read() {
+        for (entity, pet, network_id, interested) in q_pets.iter() {
+            if pet.owner == msg.owner_entity {
+                despawn_pet(&mut commands, &mut writer, entity, network_id, interested);
+            }
+        }
+    }
+}
+
+/// Pets despawn once their owner dies, disconnects or despawns.
+pub fn despawn_orphaned_pets(
+    mut commands: Commands,
+    q_pets: Query<(Entity, &Pet, &NetworkId, &InterestedClients)>,
+    q_owners: Query<Has<Dead>>,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    for (entity, pet, network_id, interested) in q_pets.iter() {
+        if q_owners.get(pet.owner).unwrap_or(true) {
+            despawn_pet(&mut commands, &mut writer, entity, network_id, interested);
+        }
+    }
+}
+
+/// Ordered attacks switch targets in any stance.
+pub fn order_pet_attacks(
+    mut reader: MessageReader<PetAttackMessage>,
+    mut q_pets: Query<(Entity, &Pet, &mut AiBrain), Without<Dead>>,
+    q_alive: Query<(), With<Vitals>>,
+    reactions: Reactions,
+) {
+    for msg in reader.read() {
+        if !q_alive.contains(msg.target_entity) {
+            continue;
+        }
+        for (entity, pet, mut brain) in q_pets.iter_mut() {
+            if pet.owner == msg.owner_entity
+                && reactions.between(entity, msg.target_entity).can_attack()
+            {
+                brain.state = AiState::Chase {
+                    target: msg.target_entity,
+                };
+            }
+        }
+    }
+}
+
+/// Defensive and aggressive pets join fights their owner starts, and fight back when they or
+/// their owner are attacked.
+pub fn assist_owners(
+    mut reader: MessageReader<DamageDealtMessage>,
+    mut q_pets: Query<(Entity, &Pet, &mut AiBrain), Without<Dead>>,
+    reactions: Reactions,
+) {
+    for msg in reader.read() {
+        if msg.attacker_entity == msg.target_entity {
+            continue;
+        }
+        for (entity, pet, mut brain) in q_pets.iter_mut() {
+            if pet.stance == PetStance::Passive {
+                continue;
+            }
+            let target = if msg.attacker_entity == pet.owner {
+                msg.target_entity
+            } else if msg.target_entity == pet.owner || msg.target_entity == entity {
+                msg.attacker_entity
+            } else {
+                continue;
+            };
+            if target != entity && reactions.between(entity, target).can_attack() {
+                engage(&mut brain, target);
+            }
+        }
+    }
+}
+
+/// Idle aggressive pets attack the closest hostile actor within their aggro radius.
+#[allow(clippy::type_complexity)]
+pub fn detect_hostiles_for_pets(
+    mut q_pets: Query<
+        (
+            Entity,
+            &Transform,
+            &GridCell,
+            &AggroRadius,
+            &Pet,
+            &mut AiBrain,
+        ),
+        Without<Dead>,
+    >,
+    q_actors: Query<&Transform, With<Vitals>>,
+    reactions: Reactions,
+    grid: Res<SpatialGrid>,
+) {
+    for (pet_entity, pet_transform, pet_cell, aggro_radius, pet, mut brain) in q_pets.iter_mut() {
+        if pet.stance != PetStance::Aggressive || brain.state != AiState::Idle {
+            continue;
+        }
+
+        let pet_pos = pet_transform.translation;
+        let mut closest: Option<(Entity, f32)> = None;
+        for y in -1..=1 {
+            for x in -1..=1 {
+                let Some(cell_entities) = grid.cells.get(&(pet_cell.0 + IVec2::new(x, y))) else {
+                    continue;
+                };
+                for &entity in cell_entities {
+                    let Ok(transform) = q_actors.get(entity) else {
+                        continue;
+                    };
+                    let dist_sq = pet_pos.distance_squared(transform.translation);
+                    if dist_sq <= aggro_radius.0 * aggro_radius.0
+                        && closest.is_none_or(|(_, closest_sq)| dist_sq < closest_sq)
+                        && reactions.between(pet_entity, entity) == Reaction::Hostile
+                    {
+                        closest = Some((entity, dist_sq));
+                    }
+                }
+            }
+        }
+
+        if let Some((target, _)) = closest {
+            engage(&mut brain, target);
+        }
+    }
+}
+
+/// Moves pets between following their owner, chasing and fighting. Pets that end up too far
+/// from their owner drop their target and run back.
+#[allow(clippy::type_complexity)]
+pub fn pet_state_transitions(
+    mut q_pets: Query<
+        (
+            &Transform,
+            &Pet,
+            &mut AiBrain,
+            &mut AiMovement,
+            &Abilities,
+            &AiPath,
+        ),
+        Without<Dead>,
+    >,
+    q_actors: Query<&Transform>,
+    q_alive: Query<&Transform, With<Vitals>>,
+    library_handle: Res<SpellLibraryHandle>,
+    assets: Res<Assets<SpellLibrary>>,
+) {
+    let Some(library) = assets.get(&library_handle.0) else {
+        return;
+    };
+
+    for (pet_transform, pet, mut brain, mut movement, abilities, path) in q_pets.iter_mut() {
+        let Ok(owner_transform) = q_actors.get(pet.owner) else {
+            continue;
+        };
+        let pet_pos = pet_transform.translation;
+        let owner_pos = owner_transform.translation;
+        let owner_dist = pet_pos.distance(owner_pos);
+
+        if owner_dist > PET_LEASH_RANGE {
+            brain.state = AiState::Evading;
+        }
+
+        match brain.state {
+            AiState::Chase { target } | AiState::Combat { target } => {
+                // Unreachable targets are given up like dead ones
+                let Some(target_transform) = q_alive.get(target).ok().filter(|_| !path.unreachable)
+                else {
+                    brain.state = AiState::Idle;
+                    movement.target_position = Some(owner_pos);
+                    continue;
+                };
+                let target_pos = target_transform.translation;
+                let dist = pet_pos.distance(target_pos);
+
+                let in_range = dist <= MELEE_RANGE
+                    || abilities.known.iter().any(|ability| {
+                        library.spells.get(&ability.spell_id).is_some_and(|spell| {
+                            spell.target == SpellTarget::Hostile && dist <= spell.range
+                        })
+                    });
+                if in_range {
+                    brain.state = AiState::Combat { target };
+                    movement.target_position = None;
+                } else {
+                    brain.state = AiState::Chase { target };
+                    movement.target_position = Some(target_pos);
+                }
+            }
+            AiState::Evading if owner_dist > PET_FOLLOW_DISTANCE => {
+                movement.target_position = Some(owner_pos);
+            }
+            AiState::Idle | AiState::Patrol | AiState::Returning | AiState::Evading => {
+                brain.state = AiState::Idle;
+                movement.target_position = (owner_dist > PET_FOLLOW_DISTANCE).then_some(owner_pos);
+            }
+        }
+    }
+}
+
+/// Sends an idle pet after `target`, pets that are already fighting keep their target.
+fn engage(brain: &mut AiBrain, target: Entity) {
+    if brain.state == AiState::Idle {
+        brain.state = AiState::Chase { target };
+    }
+}
+
+fn despawn_pet(
+    commands: &mut Commands,
+    writer: &mut MessageWriter<OutgoingMessage>,
+    entity: Entity,
+    network_id: &NetworkId,
+    interested: &InterestedClients,
+) {
+    writer.write(OutgoingMessage::new(
+        interested.clients.iter().copied().collect(),
+        OutgoingMessageData::Despawn(*network_id),
+    ));
+    commands.entity(entity).despawn();
+}
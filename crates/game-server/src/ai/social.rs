@@ -0,0 +1,162 @@
+use super::components::{
+    AiBrain, AiMovement, AiState, CallForHelpRadius, LeashAnchor, PackMember, Pet, ThreatTable,
+};
+use crate::{
+    assets::Faction,
+    core::Dead,
+    telemetry::{AI_EVADES_TOTAL_METRIC, AI_STATE_TRANSITIONS_TOTAL_METRIC},
+    world::SpatialGrid,
+};
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Mob that is about to leave rest for a fight, along with everyone on its threat table.
+struct Pulled {
+    entity: Entity,
+    position: Vec3,
+    faction: Faction,
+    radius: Option<f32>,
+    pack: Option<Entity>,
+    targets: Vec<Entity>,
+}
+
+/// A mob that is being pulled alerts the rest of its pack and the resting mobs of its faction
+/// within its call-for-help radius. Alerted mobs put the puller's targets on their own threat
+/// table and engage along with it. Runs before the state transitions, so only mobs still at
+/// rest with threat count as pulled and alerted mobs don't alert others in turn.
+#[allow(clippy::type_complexity)]
+pub fn call_for_help(
+    mut q_mobs: Query<
+        (
+            Entity,
+            &Transform,
+            &Faction,
+            &AiBrain,
+            &mut ThreatTable,
+            Option<&CallForHelpRadius>,
+            Option<&PackMember>,
+        ),
+        (Without<Pet>, Without<Dead>),
+    >,
+    grid: Res<SpatialGrid>,
+) {
+    let pulled: Vec<Pulled> = q_mobs
+        .iter()
+        .filter(|(_, _, _, brain, threat_table, radius, member)| {
+            brain.state.is_at_rest()
+                && !threat_table.entries.is_empty()
+                && (radius.is_some() || member.is_some())
+        })
+        .map(
+            |(entity, transform, faction, _, threat_table, radius, member)| Pulled {
+                entity,
+                position: transform.translation,
+                faction: *faction,
+                radius: radius.map(|radius| radius.0),
+                pack: member.map(|member| member.pack),
+                targets: threat_table
+                    .entries
+                    .iter()
+                    .map(|entry| entry.entity)
+                    .collect(),
+            },
+        )
+        .collect();
+    if pulled.is_empty() {
+        return;
+    }
+
+    let mut packs: HashMap<Entity, Vec<Entity>> = pulled
+        .iter()
+        .filter_map(|pulled| pulled.pack)
+        .map(|pack| (pack, Vec::new()))
+        .collect();
+    if !packs.is_empty() {
+        for (entity, .., member) in q_mobs.iter() {
+            if let Some(member) = member
+                && let Some(members) = packs.get_mut(&member.pack)
+            {
+                members.push(entity);
+            }
+        }
+    }
+
+    for pulled in pulled {
+        let mut helpers = pulled
+            .pack
+            .and_then(|pack| packs.get(&pack))
+            .cloned()
+            .unwrap_or_default();
+        if let Some(radius) = pulled.radius {
+            helpers.extend(
+                grid.query_radius(pulled.position, radius)
+                    .into_iter()
+                    .filter(|&entity| {
+                        q_mobs.get(entity).is_ok_and(|(_, transform, faction, ..)| {
+                            *faction == pulled.faction
+                                && transform.translation.distance_squared(pulled.position)
+                                    <= radius * radius
+                        })
+                    }),
+            );
+        }
+
+        for helper in helpers {
+            if helper == pulled.entity {
+                continue;
+            }
+            let Ok((_, _, _, brain, mut threat_table, ..)) = q_mobs.get_mut(helper) else {
+                continue;
+            };
+            if !brain.state.is_at_rest() {
+                continue;
+            }
+            for &target in &pulled.targets {
+                threat_table.add_threat(target, 0.0);
+            }
+            tracing::debug!(caller = ?pulled.entity, ?helper, "mob joined the fight");
+        }
+    }
+}
+
+/// Once a member of a pack evades, the rest of the pack drops the fight and evades with it.
+pub fn evade_packs(
+    mut q_mobs: Query<
+        (
+            &PackMember,
+            &LeashAnchor,
+            &mut AiBrain,
+            &mut AiMovement,
+            &mut ThreatTable,
+        ),
+        Without<Dead>,
+    >,
+) {
+    let evading: HashSet<Entity> = q_mobs
+        .iter()
+        .filter(|(_, _, brain, ..)| brain.state == AiState::Evading)
+        .map(|(member, ..)| member.pack)
+        .collect();
+    if evading.is_empty() {
+        return;
+    }
+
+    for (member, leash, mut brain, mut movement, mut threat_table) in q_mobs.iter_mut() {
+        if !evading.contains(&member.pack) {
+            continue;
+        }
+        let from = match brain.state {
+            AiState::Chase { .. } => "chase",
+            AiState::Combat { .. } => "combat",
+            _ => continue,
+        };
+
+        brain.state = AiState::Evading;
+        movement.target_position = Some(leash.position);
+        threat_table.clear();
+        metrics::counter!(AI_STATE_TRANSITIONS_TOTAL_METRIC, "from" => from, "to" => "evading")
+            .increment(1);
+        metrics::counter!(AI_EVADES_TOTAL_METRIC).increment(1);
+        tracing::debug!("ai transition: {from} -> evading (pack evading)");
+    }
+}
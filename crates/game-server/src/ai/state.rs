@@ -1,12 +1,13 @@
-use super::components::{AiBrain, AiMovement, AiState, LeashAnchor, ThreatTable};
+use super::components::{AiBrain, AiMovement, AiPath, AiState, LeashAnchor, Patrol, ThreatTable};
 use crate::{
-    combat::Abilities,
+    combat::{Abilities, MELEE_RANGE},
     telemetry::{AI_EVADES_TOTAL_METRIC, AI_STATE_TRANSITIONS_TOTAL_METRIC},
 };
 use bevy::prelude::*;
 use game_core::spells::{SpellLibrary, SpellLibraryHandle};
 
 /// Evaluates and applies AI state transitions based on threat, distance, and leash.
+#[allow(clippy::type_complexity)]
 pub fn ai_state_transitions(
     mut q_mobs: Query<(
         &Transform,
@@ -15,8 +16,10 @@ pub fn ai_state_transitions(
         &ThreatTable,
         &LeashAnchor,
         &Abilities,
+        &AiPath,
+        Has<Patrol>,
     )>,
-    q_targets: Query<&Transform, Without<AiBrain>>,
+    q_targets: Query<&Transform>,
     library_handle: Res<SpellLibraryHandle>,
     assets: Res<Assets<SpellLibrary>>,
 ) {
@@ -24,23 +27,33 @@ pub fn ai_state_transitions(
         return;
     };
 
-    for (mob_transform, mut brain, mut movement, threat_table, leash, abilities) in
+    for (mob_transform, mut brain, mut movement, threat_table, leash, abilities, path, patrols) in
         q_mobs.iter_mut()
     {
+        // Patrollers go back to their route once they are home
+        let (rest_state, rest_label) = if patrols {
+            (AiState::Patrol, "patrol")
+        } else {
+            (AiState::Idle, "idle")
+        };
+
         let mob_pos = mob_transform.translation;
+        let is_in_melee_range = |entity: Entity| {
+            q_targets.get(entity).is_ok_and(|transform| {
+                mob_pos.distance_squared(transform.translation) <= MELEE_RANGE * MELEE_RANGE
+            })
+        };
 
         match &brain.state {
-            AiState::Idle => {
+            AiState::Idle | AiState::Patrol => {
                 // Transition to Chase if we have a threat target
-                if let Some(top_threat) = threat_table.highest_threat()
-                    && let Ok(target_transform) = q_targets.get(top_threat.entity)
+                if let Some(target) = threat_table.select_target(None, is_in_melee_range)
+                    && let Ok(target_transform) = q_targets.get(target)
                 {
-                    brain.state = AiState::Chase {
-                        target: top_threat.entity,
-                    };
+                    brain.state = AiState::Chase { target };
                     movement.target_position = Some(target_transform.translation);
-                    metrics::counter!(AI_STATE_TRANSITIONS_TOTAL_METRIC, "from" => "idle", "to" => "chase").increment(1);
-                    tracing::debug!(?top_threat.entity, "ai transition: idle -> chase");
+                    metrics::counter!(AI_STATE_TRANSITIONS_TOTAL_METRIC, "from" => rest_label, "to" => "chase").increment(1);
+                    tracing::debug!(?target, "ai transition: {rest_label} -> chase");
                 }
             }
             AiState::Chase { target } => {
@@ -64,6 +77,16 @@ pub fn ai_state_transitions(
                     continue;
                 }
 
+                // No path leads to the target, e.g. a player up on a cliff
+                if path.unreachable {
+                    brain.state = AiState::Evading;
+                    movement.target_position = Some(leash.position);
+                    metrics::counter!(AI_STATE_TRANSITIONS_TOTAL_METRIC, "from" => "chase", "to" => "evading").increment(1);
+                    metrics::counter!(AI_EVADES_TOTAL_METRIC).increment(1);
+                    tracing::debug!("ai transition: chase -> evading (target unreachable)");
+                    continue;
+                }
+
                 // Check if in range of any ability -> transition to Combat
                 let target_pos = q_targets.get(target).unwrap().translation;
                 let dist = mob_pos.distance(target_pos);
@@ -83,22 +106,14 @@ pub fn ai_state_transitions(
                     movement.target_position = Some(target_pos);
                 }
 
-                // Switch target if a higher threat exists (10% hysteresis)
-                if let Some(top_threat) = threat_table.highest_threat()
-                    && top_threat.entity != target
+                // Switch target on a taunt or once another entry pulls aggro (110% in melee
+                // range, 130% at range)
+                if let Some(new_target) =
+                    threat_table.select_target(Some(target), is_in_melee_range)
+                    && new_target != target
                 {
-                    let current_threat = threat_table
-                        .entries
-                        .iter()
-                        .find(|e| e.entity == target)
-                        .map(|e| e.threat)
-                        .unwrap_or(0.0);
-                    if top_threat.threat > current_threat * 1.1 {
-                        brain.state = AiState::Chase {
-                            target: top_threat.entity,
-                        };
-                        metrics::counter!(AI_STATE_TRANSITIONS_TOTAL_METRIC, "from" => "chase", "to" => "chase").increment(1);
-                    }
+                    brain.state = AiState::Chase { target: new_target };
+                    metrics::counter!(AI_STATE_TRANSITIONS_TOTAL_METRIC, "from" => "chase", "to" => "chase").increment(1);
                 }
             }
             AiState::Combat { target } => {
@@ -142,30 +157,22 @@ pub fn ai_state_transitions(
                     movement.target_position = None;
                 }
 
-                // Switch target if a higher threat exists (10% hysteresis)
-                if let Some(top_threat) = threat_table.highest_threat()
-                    && top_threat.entity != target
+                // Switch target on a taunt or once another entry pulls aggro (110% in melee
+                // range, 130% at range)
+                if let Some(new_target) =
+                    threat_table.select_target(Some(target), is_in_melee_range)
+                    && new_target != target
                 {
-                    let current_threat = threat_table
-                        .entries
-                        .iter()
-                        .find(|e| e.entity == target)
-                        .map(|e| e.threat)
-                        .unwrap_or(0.0);
-                    if top_threat.threat > current_threat * 1.1 {
-                        brain.state = AiState::Chase {
-                            target: top_threat.entity,
-                        };
-                        metrics::counter!(AI_STATE_TRANSITIONS_TOTAL_METRIC, "from" => "combat", "to" => "chase").increment(1);
-                    }
+                    brain.state = AiState::Chase { target: new_target };
+                    metrics::counter!(AI_STATE_TRANSITIONS_TOTAL_METRIC, "from" => "combat", "to" => "chase").increment(1);
                 }
             }
             AiState::Returning => {
                 let dist_sq = mob_pos.distance_squared(leash.position);
                 if dist_sq < 2.0 * 2.0 {
-                    brain.state = AiState::Idle;
+                    brain.state = rest_state;
                     movement.target_position = None;
-                    metrics::counter!(AI_STATE_TRANSITIONS_TOTAL_METRIC, "from" => "returning", "to" => "idle").increment(1);
+                    metrics::counter!(AI_STATE_TRANSITIONS_TOTAL_METRIC, "from" => "returning", "to" => rest_label).increment(1);
                 } else {
                     movement.target_position = Some(leash.position);
                 }
@@ -173,9 +180,9 @@ pub fn ai_state_transitions(
             AiState::Evading => {
                 let dist_sq = mob_pos.distance_squared(leash.position);
                 if dist_sq < 2.0 * 2.0 {
-                    brain.state = AiState::Idle;
+                    brain.state = rest_state;
                     movement.target_position = None;
-                    metrics::counter!(AI_STATE_TRANSITIONS_TOTAL_METRIC, "from" => "evading", "to" => "idle").increment(1);
+                    metrics::counter!(AI_STATE_TRANSITIONS_TOTAL_METRIC, "from" => "evading", "to" => rest_label).increment(1);
                 } else {
                     movement.target_position = Some(leash.position);
                 }
@@ -1,15 +1,26 @@
-use super::components::{AggroRadius, AiBehavior, AiBrain, AiState, ThreatTable};
+use super::components::{AggroRadius, AiBehavior, AiBrain, Pet, ThreatTable};
 use crate::{
-    combat::ApplySpellEffectMessage,
+    assets::Reactions,
+    combat::{ApplySpellEffectMessage, DamageDealtMessage, HealingDoneMessage},
     core::{ClientIdComponent, GridCell},
     world::SpatialGrid,
 };
 use bevy::prelude::*;
-use game_core::components::Vitals;
+use game_core::{
+    components::Vitals,
+    spells::{SpellEffect, SpellLibrary, SpellLibraryHandle},
+};
+use protocol::models::Reaction;
+use std::collections::HashMap;
+
+/// Share of effective healing turned into threat, split between the mobs fighting the target
+const HEALING_THREAT_FACTOR: f32 = 0.5;
 
-/// Detects players within aggro radius for idle aggressive mobs and adds initial threat.
+/// Detects hostile players within aggro radius for idle aggressive mobs and adds initial
+/// threat.
 pub fn detect_players(
     mut q_mobs: Query<(
+        Entity,
         &Transform,
         &GridCell,
         &AggroRadius,
@@ -17,10 +28,13 @@ pub fn detect_players(
         &AiBrain,
     )>,
     q_players: Query<(Entity, &Transform), With<ClientIdComponent>>,
+    reactions: Reactions,
     grid: Res<SpatialGrid>,
 ) {
-    for (mob_transform, mob_cell, aggro_radius, mut threat_table, brain) in q_mobs.iter_mut() {
-        if brain.state != AiState::Idle || brain.behavior != AiBehavior::Aggressive {
+    for (mob_entity, mob_transform, mob_cell, aggro_radius, mut threat_table, brain) in
+        q_mobs.iter_mut()
+    {
+        if !brain.state.is_at_rest() || brain.behavior != AiBehavior::Aggressive {
             continue;
         }
 
@@ -39,6 +53,9 @@ pub fn detect_players(
                     let Ok((player_entity, player_transform)) = q_players.get(entity) else {
                         continue;
                     };
+                    if reactions.between(mob_entity, player_entity) != Reaction::Hostile {
+                        continue;
+                    }
 
                     let dist_sq = mob_pos.distance_squared(player_transform.translation);
                     if dist_sq <= aggro_dist_sq {
@@ -50,13 +67,22 @@ pub fn detect_players(
     }
 }
 
-/// Adds threat when a mob takes damage from a player.
+/// Adds threat equal to the damage dealt, scaled by the spell's threat multiplier. Avoided
+/// attacks still put the attacker on the table, and pets put their owner on it as well.
 pub fn update_threat_on_damage(
-    mut reader: MessageReader<ApplySpellEffectMessage>,
+    mut reader: MessageReader<DamageDealtMessage>,
     mut q_mobs: Query<&mut ThreatTable>,
     q_vitals: Query<&Vitals>,
+    q_pets: Query<&Pet>,
+    library_handle: Res<SpellLibraryHandle>,
+    assets: Res<Assets<SpellLibrary>>,
 ) {
+    let library = assets.get(&library_handle.0);
+
     for msg in reader.read() {
+        if msg.attacker_entity == msg.target_entity {
+            continue;
+        }
         // Only track threat if the target is a mob with a threat table
         let Ok(mut threat_table) = q_mobs.get_mut(msg.target_entity) else {
             continue;
@@ -70,9 +96,113 @@ pub fn update_threat_on_damage(
             continue;
         }
 
-        // Add threat equal to damage dealt (spell_id lookup not needed here,
-        // since damage is already applied; we use a flat amount per hit for now)
-        threat_table.add_threat(msg.caster_entity, 10.0);
+        // Auto-attacks are not in the library and keep the default multiplier
+        let multiplier = library
+            .and_then(|library| library.spells.get(&msg.spell_id))
+            .map_or(1.0, |spell| spell.threat_multiplier);
+        threat_table.add_threat(msg.attacker_entity, msg.amount as f32 * multiplier);
+        if let Ok(pet) = q_pets.get(msg.attacker_entity) {
+            threat_table.add_threat(pet.owner, 0.0);
+        }
+    }
+}
+
+/// Healing a player generates threat on every mob that has the player on its threat table,
+/// split evenly between them.
+pub fn update_threat_on_healing(
+    mut reader: MessageReader<HealingDoneMessage>,
+    mut q_mobs: Query<&mut ThreatTable>,
+    library_handle: Res<SpellLibraryHandle>,
+    assets: Res<Assets<SpellLibrary>>,
+) {
+    if reader.is_empty() {
+        return;
+    }
+    let library = assets.get(&library_handle.0);
+
+    let heals: Vec<_> = reader
+        .read()
+        .filter(|msg| msg.amount > 0)
+        .map(|msg| {
+            let multiplier = library
+                .and_then(|library| library.spells.get(&msg.spell_id))
+                .map_or(1.0, |spell| spell.threat_multiplier);
+            let threat = msg.amount as f32 * HEALING_THREAT_FACTOR * multiplier;
+            (msg.healer_entity, msg.target_entity, threat)
+        })
+        .collect();
+    if heals.is_empty() {
+        return;
+    }
+
+    let mut engaged: HashMap<Entity, u32> = HashMap::new();
+    for threat_table in q_mobs.iter() {
+        for &(_, target, _) in &heals {
+            if threat_table.threat_of(target).is_some() {
+                *engaged.entry(target).or_default() += 1;
+            }
+        }
+    }
+
+    for mut threat_table in q_mobs.iter_mut() {
+        for &(healer, target, threat) in &heals {
+            if let Some(&count) = engaged.get(&target)
+                && threat_table.threat_of(target).is_some()
+            {
+                threat_table.add_threat(healer, threat / count as f32);
+            }
+        }
+    }
+}
+
+/// Applies the flat threat, taunts and threat drops of landed spells.
+pub fn apply_spell_threat(
+    mut reader: MessageReader<ApplySpellEffectMessage>,
+    mut q_mobs: Query<&mut ThreatTable>,
+    library_handle: Res<SpellLibraryHandle>,
+    assets: Res<Assets<SpellLibrary>>,
+) {
+    let Some(library) = assets.get(&library_handle.0) else {
+        return;
+    };
+
+    for msg in reader.read() {
+        let Some(spell) = library.spells.get(&msg.spell_id) else {
+            continue;
+        };
+
+        if msg.caster_entity != msg.target_entity
+            && let Ok(mut threat_table) = q_mobs.get_mut(msg.target_entity)
+        {
+            threat_table.add_threat(msg.caster_entity, spell.bonus_threat);
+            for effect in &spell.effects {
+                if let SpellEffect::Taunt { duration } = *effect {
+                    threat_table.taunt(msg.caster_entity, duration);
+                }
+            }
+        }
+
+        for effect in &spell.effects {
+            if let SpellEffect::DropThreat { fraction } = *effect {
+                for mut threat_table in q_mobs.iter_mut() {
+                    threat_table.scale_threat(msg.caster_entity, 1.0 - fraction);
+                }
+            }
+        }
+    }
+}
+
+/// Ends taunts once their duration runs out.
+pub fn tick_taunts(time: Res<Time>, mut q_mobs: Query<&mut ThreatTable>) {
+    for mut threat_table in q_mobs.iter_mut() {
+        if threat_table.taunt.is_none() {
+            continue;
+        }
+        if let Some(taunt) = threat_table.taunt.as_mut()
+            && taunt.timer.tick(time.delta()).is_finished()
+        {
+            threat_table.taunt = None;
+        }
     }
 }
 
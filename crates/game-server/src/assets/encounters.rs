@@ -0,0 +1,77 @@
+use super::ContentId;
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Ability the boss casts on a timer while its phase is active.
+#[derive(Deserialize, Debug, Clone)]
+pub struct RotationAbilityDef {
+    pub spell_id: u32,
+    /// Seconds between casts
+    pub interval: f32,
+    /// Seconds into the phase before the first cast, defaults to the interval
+    #[serde(default)]
+    pub delay: Option<f32>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EncounterAddDef {
+    pub monster_id: ContentId,
+    pub count: u32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EncounterPhaseDef {
+    /// The phase starts once the boss drops to this share of its maximum health
+    #[serde(default = "full_health")]
+    pub health_below: f32,
+    #[serde(default)]
+    pub rotation: Vec<RotationAbilityDef>,
+    /// Mobs spawned around the boss when the phase starts, they join the fight right away
+    #[serde(default)]
+    pub adds: Vec<EncounterAddDef>,
+}
+
+fn full_health() -> f32 {
+    1.0
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EnrageDef {
+    /// Seconds after the pull
+    pub after: f32,
+    /// Spell the boss applies to itself, it is not cast and can't be interrupted
+    pub spell_id: u32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EncounterDef {
+    pub name: String,
+    /// The encounter resets once the boss is pulled this far from its spawn position, players
+    /// further away drop off its threat table
+    pub boundary_radius: f32,
+    /// Phases in order of their health threshold
+    pub phases: Vec<EncounterPhaseDef>,
+    #[serde(default)]
+    pub enrage: Option<EnrageDef>,
+}
+
+impl EncounterDef {
+    /// Whether the spell is cast by a rotation, those spells are left out of the boss' ability
+    /// priorities.
+    pub fn is_rotation_spell(&self, spell_id: u32) -> bool {
+        self.phases
+            .iter()
+            .flat_map(|phase| &phase.rotation)
+            .any(|ability| ability.spell_id == spell_id)
+    }
+}
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct EncounterLibrary {
+    pub encounters: HashMap<ContentId, EncounterDef>,
+}
+
+#[derive(Resource)]
+pub struct EncounterLibraryHandle(pub Handle<EncounterLibrary>);
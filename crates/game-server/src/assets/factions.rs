@@ -0,0 +1,103 @@
+use super::ContentId;
+use crate::{
+    ai::Pet,
+    core::{Duel, PvpFlag},
+};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::reflect::TypePath;
+use protocol::models::Reaction;
+use serde::Deserialize;
+
+/// Faction all player characters belong to
+const PLAYER_FACTION: &str = "players";
+
+/// Faction of an actor. Players share one, mobs get theirs from their `MonsterDef`.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Faction(pub ContentId);
+
+impl Faction {
+    pub fn player() -> Self {
+        Self(ContentId::from(PLAYER_FACTION))
+    }
+}
+
+/// Reaction between two factions, it applies in both directions.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ReactionDef {
+    pub factions: (ContentId, ContentId),
+    pub reaction: Reaction,
+}
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct FactionLibrary {
+    pub reactions: Vec<ReactionDef>,
+}
+
+impl FactionLibrary {
+    /// Listed pairs use their reaction, otherwise factions are friendly to themselves and
+    /// neutral to each other.
+    pub fn reaction(&self, a: ContentId, b: ContentId) -> Reaction {
+        self.reactions
+            .iter()
+            .find(|def| def.factions == (a, b) || def.factions == (b, a))
+            .map(|def| def.reaction)
+            .unwrap_or(if a == b {
+                Reaction::Friendly
+            } else {
+                Reaction::Neutral
+            })
+    }
+}
+
+#[derive(Resource)]
+pub struct FactionLibraryHandle(pub Handle<FactionLibrary>);
+
+/// Reaction lookups between actors.
+#[derive(SystemParam)]
+pub struct Reactions<'w, 's> {
+    handle: Res<'w, FactionLibraryHandle>,
+    factions: Res<'w, Assets<FactionLibrary>>,
+    actors: Query<
+        'w,
+        's,
+        (
+            Option<&'static Faction>,
+            Option<&'static Duel>,
+            Has<PvpFlag>,
+        ),
+    >,
+    pets: Query<'w, 's, &'static Pet>,
+}
+
+impl Reactions<'_, '_> {
+    /// Reaction of actor `a` towards actor `b`. Active duelists and flagged players are
+    /// hostile to each other, everyone else follows their factions. Actors without a faction
+    /// are neutral to everyone, and until the library is loaded other factions are hostile.
+    /// Pets react and are reacted to like their owner.
+    pub fn between(&self, a: Entity, b: Entity) -> Reaction {
+        let owner = |entity| self.pets.get(entity).map_or(entity, |pet: &Pet| pet.owner);
+        let (a, b) = (owner(a), owner(b));
+
+        let (Ok((a_faction, a_duel, a_flagged)), Ok((b_faction, _, b_flagged))) =
+            (self.actors.get(a), self.actors.get(b))
+        else {
+            return Reaction::Neutral;
+        };
+
+        if a_duel.is_some_and(|duel| duel.opponent == b && duel.is_active())
+            || (a != b && a_flagged && b_flagged)
+        {
+            return Reaction::Hostile;
+        }
+
+        let (Some(a_faction), Some(b_faction)) = (a_faction, b_faction) else {
+            return Reaction::Neutral;
+        };
+        match self.factions.get(&self.handle.0) {
+            Some(library) => library.reaction(a_faction.0, b_faction.0),
+            None if a_faction == b_faction => Reaction::Friendly,
+            None => Reaction::Hostile,
+        }
+    }
+}
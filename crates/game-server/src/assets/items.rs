@@ -1,13 +1,40 @@
 use bevy::prelude::*;
 use bevy::reflect::TypePath;
+use game_core::stats::Stats;
+use protocol::models::DamageSchool;
 use serde::Deserialize;
 use std::collections::HashMap;
 
+/// Swing speed in seconds and damage range of a weapon, also used for mob attacks.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct WeaponDef {
+    pub speed: f32,
+    pub min_damage: i32,
+    pub max_damage: i32,
+    #[serde(default)]
+    pub school: DamageSchool,
+}
+
+impl WeaponDef {
+    /// Fists, used by characters without a weapon equipped
+    pub const UNARMED: Self = Self {
+        speed: 2.0,
+        min_damage: 4,
+        max_damage: 6,
+        school: DamageSchool::Physical,
+    };
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct ItemDef {
     pub name: String,
     pub stack_size: u16,
     pub asset_id: u32,
+    /// Bonus granted while the item is equipped
+    #[serde(default)]
+    pub stats: Stats,
+    #[serde(default)]
+    pub weapon: Option<WeaponDef>,
 }
 
 #[derive(Asset, TypePath, Deserialize, Debug)]
@@ -1,16 +1,22 @@
 mod content_id;
+mod encounters;
+mod factions;
 mod items;
 mod loot;
 mod monsters;
 mod zone;
 
 pub use content_id::ContentId;
+pub use encounters::*;
+pub use factions::*;
 pub use items::*;
 pub use loot::*;
 pub use monsters::*;
+pub use zone::{ZoneProp, ZoneTerrain};
 
 use bevy::prelude::*;
 use bevy_common_assets::ron::RonAssetPlugin;
+use game_core::auras::{AuraLibrary, AuraLibraryHandle};
 use game_core::props::{PropsConfig, PropsConfigHandle};
 use game_core::spells::{SpellLibrary, SpellLibraryHandle};
 use game_core::zone::ZoneDef;
@@ -20,6 +26,9 @@ pub struct ContentPlugin;
 impl Plugin for ContentPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
+            RonAssetPlugin::<AuraLibrary>::new(&["auras.ron"]),
+            RonAssetPlugin::<EncounterLibrary>::new(&["encounters.ron"]),
+            RonAssetPlugin::<FactionLibrary>::new(&["factions.ron"]),
             RonAssetPlugin::<ItemLibrary>::new(&["items.ron"]),
             RonAssetPlugin::<LootTableLibrary>::new(&["loot_tables.ron"]),
             RonAssetPlugin::<MonsterLibrary>::new(&["monsters.ron"]),
@@ -42,8 +51,14 @@ fn setup_assets(mut commands: Commands, assets: Res<AssetServer>) {
     commands.insert_resource(LootTableLibraryHandle(loot_tables_handle));
     let monsters_handle = assets.load::<MonsterLibrary>("monsters.ron");
     commands.insert_resource(MonsterLibraryHandle(monsters_handle));
+    let encounters_handle = assets.load::<EncounterLibrary>("encounters.ron");
+    commands.insert_resource(EncounterLibraryHandle(encounters_handle));
     let spells_handle = assets.load::<SpellLibrary>("spells.ron");
     commands.insert_resource(SpellLibraryHandle(spells_handle));
+    let auras_handle = assets.load::<AuraLibrary>("auras.ron");
+    commands.insert_resource(AuraLibraryHandle(auras_handle));
+    let factions_handle = assets.load::<FactionLibrary>("factions.ron");
+    commands.insert_resource(FactionLibraryHandle(factions_handle));
     let props_handle = assets.load::<PropsConfig>("world/props.ron");
     commands.insert_resource(PropsConfigHandle(props_handle));
 }
@@ -1,6 +1,8 @@
-use super::ContentId;
+use super::{ContentId, WeaponDef};
 use bevy::prelude::*;
 use bevy::reflect::TypePath;
+use game_core::stats::Stats;
+use protocol::models::DamageSchool;
 use serde::Deserialize;
 use std::collections::HashMap;
 
@@ -25,6 +27,9 @@ pub struct AiDef {
     #[serde(default)]
     pub behavior: AiBehaviorDef,
     pub aggro_radius: f32,
+    /// Idle mobs of the same faction within this radius join the fight when the mob is pulled
+    #[serde(default)]
+    pub call_for_help_radius: f32,
     pub leash_range: f32,
     #[serde(default)]
     pub ability_priorities: HashMap<u32, u8>,
@@ -37,11 +42,25 @@ pub struct MonsterDef {
     pub hp: i32,
     pub speed: f32,
     pub asset_id: u32,
+    /// Decides which actors the mob attacks and can be attacked by, see `factions.ron`
+    pub faction: ContentId,
     #[serde(default)]
     pub loot_tables: Vec<ContentId>,
     #[serde(default)]
     pub abilities: Vec<u32>,
+    /// Mobs with a weapon auto-attack their target between ability casts
+    #[serde(default)]
+    pub weapon: Option<WeaponDef>,
+    /// Armor, resistances and damage bonuses of the mob, health comes from `hp` instead
+    #[serde(default)]
+    pub stats: Option<Stats>,
+    /// Damage schools the mob takes no damage from
+    #[serde(default)]
+    pub immunities: Vec<DamageSchool>,
     pub ai: Option<AiDef>,
+    /// Boss encounter from `encounters.ron` the mob runs when pulled
+    #[serde(default)]
+    pub encounter: Option<ContentId>,
 }
 
 #[derive(Asset, TypePath, Deserialize, Debug)]
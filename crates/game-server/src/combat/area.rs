@@ -0,0 +1,183 @@
+use crate::{
+    assets::Reactions,
+    combat::{
+        line_of_sight::has_line_of_sight,
+        messages::{ApplyAreaSpellMessage, ApplySpellEffectMessage, CastTarget},
+    },
+    core::{ClientIdComponent, InterestedClients},
+    networking::{OutgoingMessage, OutgoingMessageData},
+    world::SpatialGrid,
+};
+use avian3d::prelude::SpatialQuery;
+use bevy::prelude::*;
+use game_core::{
+    components::Vitals,
+    constants::ACTOR_HALF_HEIGHT,
+    networking::NetworkId,
+    spells::{AreaShape, SpellLibrary, SpellLibraryHandle, SpellTarget},
+};
+use protocol::models::Reaction;
+
+/// Whether `point` lies within a cone of the given full opening angle (in radians) around
+/// `forward`. Only the horizontal direction is considered.
+pub fn is_in_cone(origin: Vec3, forward: Vec3, angle: f32, point: Vec3) -> bool {
+    let to_point = (point - origin).with_y(0.0);
+    let forward = forward.with_y(0.0);
+    if to_point.length_squared() < f32::EPSILON || forward.length_squared() < f32::EPSILON {
+        return true;
+    }
+    forward.angle_between(to_point) <= angle * 0.5
+}
+
+/// Resolves finished area spells into a spell effect per affected actor, closest first.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn resolve_area_spells(
+    mut reader: MessageReader<ApplyAreaSpellMessage>,
+    mut effect_writer: MessageWriter<ApplySpellEffectMessage>,
+    mut writer: MessageWriter<OutgoingMessage>,
+    grid: Res<SpatialGrid>,
+    library_handle: Res<SpellLibraryHandle>,
+    assets: Res<Assets<SpellLibrary>>,
+    q_caster: Query<(
+        &NetworkId,
+        &Transform,
+        &InterestedClients,
+        Option<&ClientIdComponent>,
+    )>,
+    q_actors: Query<&Transform, With<Vitals>>,
+    reactions: Reactions,
+    spatial_query: SpatialQuery,
+) {
+    let Some(library) = assets.get(&library_handle.0) else {
+        return;
+    };
+
+    for msg in reader.read() {
+        let Some(spell) = library.spells.get(&msg.spell_id) else {
+            tracing::warn!(spell_id = %msg.spell_id, "tried to apply invalid spell");
+            continue;
+        };
+        let Some(area) = spell.area.as_ref() else {
+            tracing::warn!(spell_id = %msg.spell_id, "tried to apply area of single target spell");
+            continue;
+        };
+
+        let Ok((caster_network_id, caster_transform, interested, caster_client_id)) =
+            q_caster.get(msg.caster_entity)
+        else {
+            tracing::debug!(caster = ?msg.caster_entity, "area spell caster no longer exists");
+            continue;
+        };
+
+        let center = match (area.shape, msg.target) {
+            (AreaShape::Circle { .. }, CastTarget::Entity(target_entity)) => {
+                let Ok(target_transform) = q_actors.get(target_entity) else {
+                    tracing::debug!(target = ?target_entity, "area spell target no longer exists");
+                    continue;
+                };
+                target_transform.translation
+            }
+            (AreaShape::Circle { .. }, CastTarget::Position(position)) => position,
+            (AreaShape::Cone { .. } | AreaShape::SelfRadius { .. }, _) => {
+                caster_transform.translation
+            }
+        };
+
+        // Actors behind obstacles are not hit, ground positions are raised to actor height
+        let sight_origin = match msg.target {
+            CastTarget::Position(_) if !area.shape.is_caster_centred() => {
+                center + Vec3::Y * ACTOR_HALF_HEIGHT
+            }
+            _ => center,
+        };
+
+        let radius = area.shape.radius();
+        let mut targets: Vec<(Entity, f32)> = grid
+            .query_radius(center, radius)
+            .into_iter()
+            .filter_map(|entity| {
+                let transform = q_actors.get(entity).ok()?;
+                let reaction = reactions.between(msg.caster_entity, entity);
+                let allowed = match spell.target {
+                    SpellTarget::Caster => entity == msg.caster_entity,
+                    SpellTarget::Friendly => reaction == Reaction::Friendly,
+                    SpellTarget::Hostile => entity != msg.caster_entity && reaction.can_attack(),
+                };
+                if !allowed {
+                    return None;
+                }
+
+                // Areas are flat, so height differences are ignored
+                let distance_sq = (transform.translation - center)
+                    .with_y(0.0)
+                    .length_squared();
+                if distance_sq > radius * radius {
+                    return None;
+                }
+                if let AreaShape::Cone { angle, .. } = area.shape
+                    && !is_in_cone(
+                        center,
+                        *caster_transform.forward(),
+                        angle.to_radians(),
+                        transform.translation,
+                    )
+                {
+                    return None;
+                }
+                if !has_line_of_sight(&spatial_query, sight_origin, transform.translation) {
+                    return None;
+                }
+                Some((entity, distance_sq))
+            })
+            .collect();
+
+        targets.sort_by(|a, b| a.1.total_cmp(&b.1));
+        targets.truncate(area.max_targets as usize);
+
+        tracing::debug!(
+            caster = ?msg.caster_entity,
+            spell_id = %msg.spell_id,
+            targets = targets.len(),
+            "area spell resolved"
+        );
+
+        for (target_entity, _) in targets {
+            effect_writer.write(ApplySpellEffectMessage {
+                caster_entity: msg.caster_entity,
+                caster_client_id: msg.caster_client_id,
+                target_entity,
+                spell_id: msg.spell_id,
+            });
+        }
+
+        let shape = match area.shape {
+            AreaShape::Circle { radius } | AreaShape::SelfRadius { radius } => {
+                protocol::models::AreaShape::Circle { radius }
+            }
+            AreaShape::Cone { radius, angle } => {
+                let (yaw, _, _) = caster_transform.rotation.to_euler(EulerRot::YXZ);
+                protocol::models::AreaShape::Cone {
+                    radius,
+                    angle: angle.to_radians(),
+                    yaw,
+                }
+            }
+        };
+
+        let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
+        recipients.extend(interested.clients.iter().copied());
+        if let Some(client_id) = caster_client_id {
+            recipients.push(client_id.0);
+        }
+
+        writer.write(OutgoingMessage::new(
+            recipients,
+            OutgoingMessageData::AreaEffect {
+                network_id: *caster_network_id,
+                spell_id: msg.spell_id,
+                center,
+                shape,
+            },
+        ));
+    }
+}
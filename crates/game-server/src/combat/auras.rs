@@ -0,0 +1,469 @@
+use crate::{
+    combat::{
+        combat_log::CombatLogMessage,
+        damage::{DamageImmunities, mitigate_damage},
+        messages::{DamageDealtMessage, HealingDoneMessage},
+    },
+    core::{BaseMovementSpeed, ClientIdComponent, InterestedClients, Tapped},
+    networking::{OutgoingMessage, OutgoingMessageData},
+};
+use bevy::prelude::*;
+use bevy_renet::renet::ClientId;
+use game_core::{
+    auras::{AuraDef, AuraKind, AuraLibrary, AuraLibraryHandle, AuraModifier, PeriodicEffect},
+    components::{LevelComponent, MovementSpeedComponent, Vitals},
+    crowd_control::{CrowdControl, CrowdControlState},
+    networking::NetworkId,
+    stats::Stats,
+};
+use protocol::models::{ActorAura, CombatLogEvent, DamageSchool, HitResult, Impact};
+
+#[derive(Debug, Clone)]
+pub struct ActiveAura {
+    pub aura_id: u32,
+    /// Spell that applied the aura, reported with periodic impacts
+    pub spell_id: u32,
+    pub source: Entity,
+    pub source_client_id: Option<ClientId>,
+    pub stacks: u32,
+    pub duration: Timer,
+    pub periodic: Option<Timer>,
+    /// Damage the aura's absorb shield can still soak
+    pub absorb_remaining: i32,
+}
+
+#[derive(Component, Debug, Clone, Default)]
+pub struct Auras {
+    pub active: Vec<ActiveAura>,
+}
+
+impl Auras {
+    /// Applies an aura for `duration` seconds, or refreshes its duration and adds a stack if
+    /// it is already active. Returns the resulting number of stacks.
+    pub fn apply(
+        &mut self,
+        aura_id: u32,
+        def: &AuraDef,
+        duration: f32,
+        spell_id: u32,
+        source: Entity,
+        source_client_id: Option<ClientId>,
+    ) -> u32 {
+        if let Some(existing) = self.active.iter_mut().find(|a| a.aura_id == aura_id) {
+            existing.stacks = (existing.stacks + 1).min(def.max_stacks.max(1));
+            existing.duration = Timer::from_seconds(duration, TimerMode::Once);
+            existing.spell_id = spell_id;
+            existing.source = source;
+            existing.source_client_id = source_client_id;
+            existing.absorb_remaining = def.absorb.map_or(0, |absorb| absorb.amount);
+            return existing.stacks;
+        }
+
+        self.active.push(ActiveAura {
+            aura_id,
+            spell_id,
+            source,
+            source_client_id,
+            stacks: 1,
+            duration: Timer::from_seconds(duration, TimerMode::Once),
+            periodic: def
+                .periodic
+                .as_ref()
+                .map(|p| Timer::from_seconds(p.interval, TimerMode::Repeating)),
+            absorb_remaining: def.absorb.map_or(0, |absorb| absorb.amount),
+        });
+        1
+    }
+
+    /// Removes up to `count` auras of the given kind, oldest first.
+    /// Returns the IDs of the removed auras.
+    pub fn dispel(&mut self, kind: AuraKind, count: u32, library: &AuraLibrary) -> Vec<u32> {
+        let mut removed = Vec::new();
+        self.active.retain(|aura| {
+            let matches = library
+                .auras
+                .get(&aura.aura_id)
+                .is_some_and(|def| def.kind == kind);
+            if matches && (removed.len() as u32) < count {
+                removed.push(aura.aura_id);
+                return false;
+            }
+            true
+        });
+        removed
+    }
+
+    /// Product of all matching modifiers, each applied once per stack.
+    fn modifier(
+        &self,
+        library: &AuraLibrary,
+        select: impl Fn(&AuraModifier) -> Option<f32>,
+    ) -> f32 {
+        self.active
+            .iter()
+            .filter_map(|aura| library.auras.get(&aura.aura_id).map(|def| (aura, def)))
+            .flat_map(|(aura, def)| {
+                def.modifiers
+                    .iter()
+                    .filter_map(&select)
+                    .map(move |value| value.powi(aura.stacks as i32))
+            })
+            .product()
+    }
+
+    pub fn movement_speed_multiplier(&self, library: &AuraLibrary) -> f32 {
+        self.modifier(library, |m| match m {
+            AuraModifier::MovementSpeed(v) => Some(*v),
+            _ => None,
+        })
+    }
+
+    pub fn damage_taken_multiplier(&self, library: &AuraLibrary) -> f32 {
+        self.modifier(library, |m| match m {
+            AuraModifier::DamageTaken(v) => Some(*v),
+            _ => None,
+        })
+    }
+
+    pub fn cast_speed_multiplier(&self, library: &AuraLibrary) -> f32 {
+        self.modifier(library, |m| match m {
+            AuraModifier::CastSpeed(v) => Some(*v),
+            _ => None,
+        })
+    }
+
+    /// Sum of all flat stat bonuses, each added once per stack.
+    pub fn stat_bonus(&self, library: &AuraLibrary) -> Stats {
+        self.active
+            .iter()
+            .filter_map(|aura| library.auras.get(&aura.aura_id).map(|def| (aura, def)))
+            .flat_map(|(aura, def)| {
+                def.modifiers.iter().filter_map(move |m| match m {
+                    AuraModifier::Stats(stats) => Some(*stats * aura.stacks as i32),
+                    _ => None,
+                })
+            })
+            .fold(Stats::default(), |total, bonus| total + bonus)
+    }
+
+    pub fn immune_to(&self, school: DamageSchool, library: &AuraLibrary) -> bool {
+        self.active.iter().any(|aura| {
+            library
+                .auras
+                .get(&aura.aura_id)
+                .is_some_and(|def| def.modifiers.contains(&AuraModifier::Immunity(school)))
+        })
+    }
+
+    /// Soaks up to `amount` damage of the given school with absorb shields, oldest first.
+    /// Used up shields expire on the next tick. Returns the absorbed amount.
+    pub fn absorb(&mut self, amount: i32, school: DamageSchool, library: &AuraLibrary) -> i32 {
+        let mut absorbed = 0;
+        for aura in self.active.iter_mut() {
+            if absorbed >= amount {
+                break;
+            }
+            let Some(absorb) = library.auras.get(&aura.aura_id).and_then(|def| def.absorb) else {
+                continue;
+            };
+            if aura.absorb_remaining <= 0 || absorb.school.is_some_and(|s| s != school) {
+                continue;
+            }
+
+            let soaked = aura.absorb_remaining.min(amount - absorbed);
+            aura.absorb_remaining -= soaked;
+            absorbed += soaked;
+            if aura.absorb_remaining == 0 {
+                let duration = aura.duration.duration();
+                aura.duration.set_elapsed(duration);
+            }
+        }
+        absorbed
+    }
+
+    /// Expires all auras applied by `source` without further periodic ticks, they are removed
+    /// on the next tick.
+    pub fn expire_from(&mut self, source: Entity) {
+        for aura in self.active.iter_mut().filter(|aura| aura.source == source) {
+            let duration = aura.duration.duration();
+            aura.duration.set_elapsed(duration);
+            aura.periodic = None;
+        }
+    }
+
+    /// Crowd-control effects of all active auras.
+    pub fn crowd_control(&self, library: &AuraLibrary) -> CrowdControlState {
+        let mut state = CrowdControlState::default();
+        for aura in &self.active {
+            if let Some(effect) = library
+                .auras
+                .get(&aura.aura_id)
+                .and_then(|def| def.crowd_control)
+            {
+                state.add(effect);
+            }
+        }
+        state
+    }
+
+    /// Source of the most recent aura with the given crowd-control effect.
+    pub fn source_of(&self, effect: CrowdControl, library: &AuraLibrary) -> Option<Entity> {
+        self.active
+            .iter()
+            .rev()
+            .find(|aura| {
+                library
+                    .auras
+                    .get(&aura.aura_id)
+                    .is_some_and(|def| def.crowd_control == Some(effect))
+            })
+            .map(|aura| aura.source)
+    }
+
+    pub fn to_net(&self) -> Vec<ActorAura> {
+        self.active
+            .iter()
+            .map(|aura| ActorAura {
+                aura_id: aura.aura_id,
+                stacks: aura.stacks.min(u8::MAX as u32) as u8,
+                remaining: aura.duration.remaining_secs(),
+            })
+            .collect()
+    }
+}
+
+/// Ticks aura durations and periodic effects, removing expired auras.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub fn tick_auras(
+    mut commands: Commands,
+    time: Res<Time>,
+    library_handle: Res<AuraLibraryHandle>,
+    assets: Res<Assets<AuraLibrary>>,
+    mut q_auras: Query<(
+        Entity,
+        &NetworkId,
+        &mut Auras,
+        &mut Vitals,
+        &InterestedClients,
+        Option<&ClientIdComponent>,
+        Option<&Tapped>,
+        Option<&Stats>,
+        Option<&DamageImmunities>,
+    )>,
+    q_levels: Query<&LevelComponent>,
+    mut writer: MessageWriter<OutgoingMessage>,
+    mut damage_writer: MessageWriter<DamageDealtMessage>,
+    mut healing_writer: MessageWriter<HealingDoneMessage>,
+    mut combat_log: MessageWriter<CombatLogMessage>,
+) {
+    let Some(library) = assets.get(&library_handle.0) else {
+        return;
+    };
+
+    for (
+        entity,
+        network_id,
+        mut auras,
+        mut vitals,
+        interested,
+        client_id,
+        tapped,
+        stats,
+        immunities,
+    ) in q_auras.iter_mut()
+    {
+        if auras.active.is_empty() {
+            continue;
+        }
+
+        let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
+        recipients.extend(interested.clients.iter().copied());
+        if let Some(client_id) = client_id {
+            recipients.push(client_id.0);
+        }
+
+        // Damage ticks are mitigated once all auras have ticked, absorb shields are auras too
+        let mut damage_ticks = Vec::new();
+        let mut expired = Vec::new();
+
+        // Advancing the timers alone doesn't mark the auras as changed, ticks and expiries do
+        for aura in auras.bypass_change_detection().active.iter_mut() {
+            aura.duration.tick(time.delta());
+
+            if let Some(periodic) = aura.periodic.as_mut()
+                && let Some(def) = library.auras.get(&aura.aura_id)
+                && let Some(periodic_def) = def.periodic.as_ref()
+            {
+                periodic.tick(time.delta());
+                for _ in 0..periodic.times_finished_this_tick() {
+                    match periodic_def.effect {
+                        PeriodicEffect::Damage { amount, school } => {
+                            damage_ticks.push((
+                                aura.spell_id,
+                                aura.source,
+                                aura.source_client_id,
+                                amount * aura.stacks as i32,
+                                school,
+                            ));
+                        }
+                        PeriodicEffect::Heal { amount } => {
+                            let amount = amount * aura.stacks as i32;
+                            let healed = amount.min(vitals.max_hp - vitals.hp).max(0);
+                            vitals.hp += healed;
+                            healing_writer.write(HealingDoneMessage {
+                                healer_entity: aura.source,
+                                target_entity: entity,
+                                spell_id: aura.spell_id,
+                                amount: healed,
+                            });
+                            combat_log.write(CombatLogMessage {
+                                source: Some(aura.source),
+                                target: entity,
+                                event: CombatLogEvent::Heal {
+                                    spell_id: aura.spell_id,
+                                    amount: healed,
+                                    periodic: true,
+                                },
+                            });
+                            writer.write(OutgoingMessage::new(
+                                recipients.clone(),
+                                OutgoingMessageData::SpellImpact {
+                                    target_network_id: *network_id,
+                                    spell_id: aura.spell_id,
+                                    impact: Impact::Heal { amount: healed },
+                                },
+                            ));
+                        }
+                    }
+                }
+            }
+
+            if aura.duration.is_finished() {
+                expired.push((aura.aura_id, aura.source));
+            }
+        }
+
+        if auras.active.iter().any(|aura| {
+            aura.periodic
+                .as_ref()
+                .is_some_and(|periodic| periodic.just_finished())
+        }) {
+            auras.set_changed();
+        }
+
+        for (spell_id, source, source_client_id, amount, school) in damage_ticks {
+            let source_level = q_levels.get(source).map_or(1, |level| level.0);
+            let damage = mitigate_damage(
+                amount,
+                school,
+                source_level,
+                stats,
+                immunities,
+                Some(&mut *auras),
+                Some(library),
+            );
+            let dealt = damage.map_or(0, |damage| damage.amount);
+            vitals.hp = vitals.hp.saturating_sub(dealt).max(0);
+            damage_writer.write(DamageDealtMessage {
+                attacker_entity: source,
+                target_entity: entity,
+                spell_id,
+                amount: dealt,
+            });
+            // Periodic ticks skip the hit table and always land
+            let impact = damage.map_or(Impact::Immune, |damage| {
+                damage.impact(school, HitResult::Hit)
+            });
+            if let Some(entry) =
+                CombatLogMessage::from_impact(source, entity, spell_id, &impact, true)
+            {
+                combat_log.write(entry);
+            }
+
+            if let Some(source_client_id) = source_client_id
+                && client_id.is_none()
+                && tapped.is_none()
+            {
+                commands.entity(entity).insert(Tapped {
+                    owner_id: source_client_id,
+                });
+            }
+
+            writer.write(OutgoingMessage::new(
+                recipients.clone(),
+                OutgoingMessageData::SpellImpact {
+                    target_network_id: *network_id,
+                    spell_id,
+                    impact,
+                },
+            ));
+        }
+
+        if expired.is_empty() {
+            continue;
+        }
+
+        auras.active.retain(|aura| !aura.duration.is_finished());
+        for (aura_id, source) in expired {
+            combat_log.write(CombatLogMessage {
+                source: Some(source),
+                target: entity,
+                event: CombatLogEvent::AuraRemoved { aura_id },
+            });
+            writer.write(OutgoingMessage::new(
+                recipients.clone(),
+                OutgoingMessageData::AuraRemoved {
+                    network_id: *network_id,
+                    aura_id,
+                },
+            ));
+        }
+    }
+}
+
+/// Recomputes movement speed when auras change and notifies clients of the new value.
+#[allow(clippy::type_complexity)]
+pub fn apply_movement_speed_modifiers(
+    library_handle: Res<AuraLibraryHandle>,
+    assets: Res<Assets<AuraLibrary>>,
+    mut q_actors: Query<
+        (
+            &NetworkId,
+            &Auras,
+            &BaseMovementSpeed,
+            &mut MovementSpeedComponent,
+            &InterestedClients,
+            Option<&ClientIdComponent>,
+        ),
+        Changed<Auras>,
+    >,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    let Some(library) = assets.get(&library_handle.0) else {
+        return;
+    };
+
+    for (network_id, auras, base_speed, mut movement_speed, interested, client_id) in
+        q_actors.iter_mut()
+    {
+        let speed = base_speed.0 * auras.movement_speed_multiplier(library);
+        if (movement_speed.0 - speed).abs() < f32::EPSILON {
+            continue;
+        }
+        movement_speed.0 = speed;
+
+        let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
+        recipients.extend(interested.clients.iter().copied());
+        if let Some(client_id) = client_id {
+            recipients.push(client_id.0);
+        }
+
+        writer.write(OutgoingMessage::new(
+            recipients,
+            OutgoingMessageData::MovementSpeedChanged {
+                network_id: *network_id,
+                movement_speed: speed,
+            },
+        ));
+    }
+}
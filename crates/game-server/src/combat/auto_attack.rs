@@ -1,17 +1,37 @@
 use crate::{
-    combat::messages::{StartAttackMessage, StopAttackMessage},
-    core::{ClientIdComponent, Dead, InterestedClients, Tapped},
+    ai::Pet,
+    assets::{ItemLibrary, ItemLibraryHandle, Reactions, WeaponDef},
+    combat::{
+        auras::Auras,
+        combat_log::CombatLogMessage,
+        damage::{DamageImmunities, mitigate_damage},
+        hit_table::{AttackKind, CombatRng, resolve_attack},
+        line_of_sight::has_line_of_sight,
+        messages::{
+            ApplySpellEffectMessage, DamageDealtMessage, StartAttackMessage, StopAttackMessage,
+        },
+    },
+    core::{ClientIdComponent, Dead, Equipment, InterestedClients, Tapped},
     networking::{OutgoingMessage, OutgoingMessageData},
     telemetry::AUTO_ATTACKS_TOTAL_METRIC,
 };
+use avian3d::prelude::SpatialQuery;
 use bevy::prelude::*;
-use game_core::components::Vitals;
+use game_core::auras::{AuraLibrary, AuraLibraryHandle};
+use game_core::components::{LevelComponent, Vitals};
+use game_core::crowd_control::CrowdControlState;
 use game_core::networking::NetworkId;
-use protocol::server::AUTO_ATTACK_VISUAL_ID;
+use game_core::stats::Stats;
+use protocol::{
+    models::{HitResult, Impact},
+    server::AUTO_ATTACK_VISUAL_ID,
+};
+use rand::Rng;
+use std::time::Duration;
 
-const MELEE_RANGE: f32 = 3.0;
-const AUTO_ATTACK_SPEED: f32 = 2.0;
-const AUTO_ATTACK_DAMAGE: i32 = 5;
+pub const MELEE_RANGE: f32 = 3.0;
+/// Lower bound for weapon speeds, so a misconfigured weapon can't swing every tick
+const MIN_SWING_SPEED: f32 = 0.5;
 
 #[derive(Component)]
 pub struct AutoAttack {
@@ -19,15 +39,54 @@ pub struct AutoAttack {
     pub swing_timer: Timer,
 }
 
+/// Weapon an actor auto-attacks with. Characters get it from their equipment, mobs from their
+/// `MonsterDef`. Characters without one fight unarmed, mobs without one don't auto-attack.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Weapon(pub WeaponDef);
+
+impl Weapon {
+    fn swing_speed(&self) -> f32 {
+        self.0.speed.max(MIN_SWING_SPEED)
+    }
+}
+
+/// Spell queued by an `on_next_swing` ability, it replaces the next auto-attack swing.
+#[derive(Component, Debug)]
+pub struct NextSwing {
+    pub spell_id: u32,
+}
+
+/// Equips the first weapon among a character's items whenever their equipment changes.
+pub fn equip_weapons(
+    mut commands: Commands,
+    item_library_handle: Res<ItemLibraryHandle>,
+    item_assets: Res<Assets<ItemLibrary>>,
+    q_characters: Query<(Entity, &Equipment), Changed<Equipment>>,
+) {
+    let Some(library) = item_assets.get(&item_library_handle.0) else {
+        return;
+    };
+
+    for (entity, equipment) in q_characters.iter() {
+        let weapon = equipment.weapon(library).unwrap_or(WeaponDef::UNARMED);
+        commands.entity(entity).insert(Weapon(weapon));
+    }
+}
+
 #[allow(clippy::type_complexity)]
 pub fn process_start_attack(
     mut commands: Commands,
     mut reader: MessageReader<StartAttackMessage>,
-    q_attacker: Query<(Entity, Option<&AutoAttack>), (With<Vitals>, Without<Dead>)>,
+    q_attacker: Query<
+        (Entity, Option<&AutoAttack>, Option<&Weapon>),
+        (With<Vitals>, Without<Dead>),
+    >,
     q_target: Query<Entity, (With<Vitals>, Without<Dead>)>,
+    reactions: Reactions,
 ) {
     for msg in reader.read() {
-        let Ok((attacker_entity, existing_attack)) = q_attacker.get(msg.attacker_entity) else {
+        let Ok((attacker_entity, existing_attack, weapon)) = q_attacker.get(msg.attacker_entity)
+        else {
             tracing::debug!(
                 attacker = ?msg.attacker_entity,
                 "start_attack: attacker is dead or invalid"
@@ -48,6 +107,18 @@ pub fn process_start_attack(
             continue;
         }
 
+        if !reactions
+            .between(attacker_entity, msg.target_entity)
+            .can_attack()
+        {
+            tracing::debug!(
+                attacker = ?attacker_entity,
+                target = ?msg.target_entity,
+                "start_attack: target is friendly"
+            );
+            continue;
+        }
+
         // If already attacking this target, ignore
         if let Some(existing) = existing_attack
             && existing.target == msg.target_entity
@@ -56,8 +127,12 @@ pub fn process_start_attack(
         }
 
         // Start with a finished timer so the first swing fires immediately when in range
-        let mut swing_timer = Timer::from_seconds(AUTO_ATTACK_SPEED, TimerMode::Repeating);
-        swing_timer.tick(std::time::Duration::from_secs_f32(AUTO_ATTACK_SPEED));
+        let swing_speed = weapon
+            .copied()
+            .unwrap_or(Weapon(WeaponDef::UNARMED))
+            .swing_speed();
+        let mut swing_timer = Timer::from_seconds(swing_speed, TimerMode::Repeating);
+        swing_timer.tick(Duration::from_secs_f32(swing_speed));
 
         commands.entity(attacker_entity).insert(AutoAttack {
             target: msg.target_entity,
@@ -78,14 +153,23 @@ pub fn process_stop_attack(
     }
 }
 
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn tick_auto_attack(
     mut commands: Commands,
     time: Res<Time>,
+    aura_library_handle: Res<AuraLibraryHandle>,
+    aura_assets: Res<Assets<AuraLibrary>>,
     mut q_attackers: Query<(
         Entity,
         &mut AutoAttack,
         &Transform,
         Option<&ClientIdComponent>,
+        Option<&LevelComponent>,
+        Option<&Stats>,
+        Option<&Weapon>,
+        Option<&NextSwing>,
+        Option<&CrowdControlState>,
+        Option<&Pet>,
     )>,
     mut q_targets: Query<
         (
@@ -94,16 +178,51 @@ pub fn tick_auto_attack(
             &mut Vitals,
             &InterestedClients,
             Option<&Tapped>,
+            Option<&mut Auras>,
+            Option<&LevelComponent>,
+            Option<&Stats>,
+            Option<&ClientIdComponent>,
+            Option<&DamageImmunities>,
         ),
         Without<Dead>,
     >,
+    spatial_query: SpatialQuery,
+    mut rng: ResMut<CombatRng>,
     mut writer: MessageWriter<OutgoingMessage>,
+    mut effect_writer: MessageWriter<ApplySpellEffectMessage>,
+    mut damage_writer: MessageWriter<DamageDealtMessage>,
+    mut combat_log: MessageWriter<CombatLogMessage>,
 ) {
-    for (attacker_entity, mut auto_attack, attacker_transform, attacker_client_id) in
-        q_attackers.iter_mut()
+    for (
+        attacker_entity,
+        mut auto_attack,
+        attacker_transform,
+        attacker_client_id,
+        attacker_level,
+        attacker_stats,
+        weapon,
+        next_swing,
+        crowd_control,
+        pet,
+    ) in q_attackers.iter_mut()
     {
-        let Ok((target_network_id, target_transform, mut target_vitals, interested, tapped)) =
-            q_targets.get_mut(auto_attack.target)
+        // Stuns and fears pause the swing timer like being out of range
+        if crowd_control.is_some_and(|state| !state.can_attack()) {
+            continue;
+        }
+
+        let Ok((
+            target_network_id,
+            target_transform,
+            mut target_vitals,
+            interested,
+            tapped,
+            target_auras,
+            target_level,
+            target_stats,
+            target_client_id,
+            immunities,
+        )) = q_targets.get_mut(auto_attack.target)
         else {
             // Target is dead or despawned, cancel auto-attack
             commands.entity(attacker_entity).remove::<AutoAttack>();
@@ -119,49 +238,140 @@ pub fn tick_auto_attack(
             continue;
         }
 
+        // Obstacles between attacker and target pause the swing timer as well
+        if !has_line_of_sight(
+            &spatial_query,
+            attacker_transform.translation,
+            target_transform.translation,
+        ) {
+            continue;
+        }
+
+        // Weapon swaps take effect on the running swing
+        let weapon = weapon.copied().unwrap_or(Weapon(WeaponDef::UNARMED));
+        let swing_speed = weapon.swing_speed();
+        if (auto_attack.swing_timer.duration().as_secs_f32() - swing_speed).abs() > f32::EPSILON {
+            auto_attack
+                .swing_timer
+                .set_duration(Duration::from_secs_f32(swing_speed));
+        }
+
         auto_attack.swing_timer.tick(time.delta());
 
         if !auto_attack.swing_timer.just_finished() {
             continue;
         }
-
-        // Apply damage
-        target_vitals.hp -= AUTO_ATTACK_DAMAGE;
         metrics::counter!(AUTO_ATTACKS_TOTAL_METRIC).increment(1);
 
-        // Tap the target if this is the first hit from a player
-        if let Some(client_id) = attacker_client_id
+        // Pets tap for their owner
+        let tapping_client_id = attacker_client_id
+            .map(|client_id| client_id.0)
+            .or(pet.and_then(|pet| pet.owner_client_id));
+
+        // A queued ability lands instead of the regular swing
+        if let Some(next_swing) = next_swing {
+            commands.entity(attacker_entity).remove::<NextSwing>();
+            effect_writer.write(ApplySpellEffectMessage {
+                caster_entity: attacker_entity,
+                caster_client_id: tapping_client_id,
+                target_entity: auto_attack.target,
+                spell_id: next_swing.spell_id,
+            });
+            continue;
+        }
+
+        // Roll weapon damage and the hit table, then apply damage mitigated by the target
+        let attacker_level = attacker_level.map_or(1, |level| level.0);
+        let weapon_damage = rng
+            .0
+            .gen_range(weapon.0.min_damage..=weapon.0.max_damage.max(weapon.0.min_damage));
+        let base_damage = attacker_stats.map_or(weapon_damage, |stats| {
+            stats.melee_damage(weapon_damage, swing_speed)
+        });
+        let (result, damage) = resolve_attack(
+            &mut rng.0,
+            AttackKind::Melee,
+            attacker_level,
+            attacker_stats,
+            target_level.map_or(1, |level| level.0),
+            target_stats,
+            base_damage,
+        );
+        let school = weapon.0.school;
+        let damage = mitigate_damage(
+            damage,
+            school,
+            attacker_level,
+            target_stats,
+            immunities,
+            target_auras.map(Mut::into_inner),
+            aura_assets.get(&aura_library_handle.0),
+        );
+        let dealt = damage.map_or(0, |damage| damage.amount);
+        target_vitals.hp = target_vitals.hp.saturating_sub(dealt).max(0);
+        damage_writer.write(DamageDealtMessage {
+            attacker_entity,
+            target_entity: auto_attack.target,
+            spell_id: AUTO_ATTACK_VISUAL_ID,
+            amount: dealt,
+        });
+
+        // Tap the target if this is the first hit from a player that wasn't avoided, players are
+        // never tapped
+        let avoided = matches!(
+            result,
+            HitResult::Miss | HitResult::Dodge | HitResult::Parry
+        );
+        if let Some(client_id) = tapping_client_id
+            && !avoided
+            && target_client_id.is_none()
             && tapped.is_none()
         {
             commands.entity(auto_attack.target).insert(Tapped {
-                owner_id: client_id.0,
+                owner_id: client_id,
             });
         }
 
         // Broadcast the hit to interested clients
-        let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
+        // NOTE: the target's own client is not within its interested set
+        let mut recipients = Vec::with_capacity(interested.clients.len() + 2);
         recipients.extend(interested.clients.iter().copied());
         if let Some(client_id) = attacker_client_id {
             recipients.push(client_id.0);
         }
+        if let Some(client_id) = target_client_id {
+            recipients.push(client_id.0);
+        }
+
+        let impact = damage.map_or(Impact::Immune, |damage| damage.impact(school, result));
+        if let Some(entry) = CombatLogMessage::from_impact(
+            attacker_entity,
+            auto_attack.target,
+            AUTO_ATTACK_VISUAL_ID,
+            &impact,
+            false,
+        ) {
+            combat_log.write(entry);
+        }
 
         writer.write(OutgoingMessage {
             recipients,
             data: OutgoingMessageData::SpellImpact {
                 target_network_id: *target_network_id,
                 spell_id: AUTO_ATTACK_VISUAL_ID,
-                impact_amount: AUTO_ATTACK_DAMAGE,
+                impact,
             },
         });
     }
 }
 
-/// Removes the AutoAttack component from entities that have died.
+/// Removes auto-attacks and queued swing abilities from entities that have died.
+#[allow(clippy::type_complexity)]
 pub fn cancel_auto_attack_on_death(
     mut commands: Commands,
-    q_dead_attackers: Query<Entity, (With<AutoAttack>, With<Dead>)>,
+    q_dead_attackers: Query<Entity, (Or<(With<AutoAttack>, With<NextSwing>)>, With<Dead>)>,
 ) {
     for entity in q_dead_attackers.iter() {
-        commands.entity(entity).remove::<AutoAttack>();
+        commands.entity(entity).remove::<(AutoAttack, NextSwing)>();
     }
 }
@@ -0,0 +1,194 @@
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+};
+
+use bevy::prelude::*;
+use bevy_renet::renet::ClientId;
+use game_core::networking::NetworkId;
+use protocol::models::{CombatLogEntry, CombatLogEvent, HitResult, Impact};
+use serde::Serialize;
+
+use crate::{
+    configuration::Settings,
+    core::{ClientIdComponent, InterestedClients, NameComponent, ServerTick},
+    networking::{OutgoingMessage, OutgoingMessageData},
+};
+
+/// Entries sent to a single client per tick, the client's own entries are sent first
+const MAX_COMBAT_LOG_ENTRIES_PER_TICK: usize = 32;
+
+/// Written by combat systems for every event that belongs in the combat log.
+#[derive(Message, Debug)]
+pub struct CombatLogMessage {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub event: CombatLogEvent,
+}
+
+impl CombatLogMessage {
+    /// Builds the log entry for a spell or swing impact. Displacements and dispels are not
+    /// logged, dispelled auras are logged as removals.
+    pub fn from_impact(
+        source: Entity,
+        target: Entity,
+        spell_id: u32,
+        impact: &Impact,
+        periodic: bool,
+    ) -> Option<Self> {
+        let event = match *impact {
+            Impact::Damage {
+                result: result @ (HitResult::Miss | HitResult::Dodge | HitResult::Parry),
+                ..
+            } => CombatLogEvent::Miss { spell_id, result },
+            Impact::Damage {
+                amount,
+                result,
+                school,
+                absorbed,
+                resisted,
+            } => CombatLogEvent::Damage {
+                spell_id,
+                amount,
+                result,
+                school,
+                absorbed,
+                resisted,
+                periodic,
+            },
+            Impact::Heal { amount } => CombatLogEvent::Heal {
+                spell_id,
+                amount,
+                periodic,
+            },
+            Impact::AuraApplied { aura_id } => CombatLogEvent::AuraApplied { aura_id },
+            Impact::Immune => CombatLogEvent::Immune { spell_id },
+            Impact::Dispel { .. } | Impact::Teleport | Impact::Knockback => return None,
+        };
+        Some(Self {
+            source: Some(source),
+            target,
+            event,
+        })
+    }
+}
+
+/// JSONL sink for the combat log, only present if `combat_log_path` is configured.
+#[derive(Resource)]
+pub struct CombatLogFile(BufWriter<File>);
+
+/// One line of the combat log file.
+#[derive(Serialize)]
+struct CombatLogRecord<'a> {
+    tick: u32,
+    source_name: Option<&'a str>,
+    target_name: &'a str,
+    #[serde(flatten)]
+    entry: &'a CombatLogEntry,
+}
+
+pub fn open_combat_log_file(mut commands: Commands, settings: Res<Settings>) {
+    let Some(path) = &settings.combat_log_path else {
+        return;
+    };
+
+    match OpenOptions::new().create(true).append(true).open(path) {
+        Ok(file) => {
+            tracing::info!(%path, "writing combat log");
+            commands.insert_resource(CombatLogFile(BufWriter::new(file)));
+        }
+        Err(err) => {
+            tracing::error!(?err, %path, "failed to open combat log file");
+        }
+    }
+}
+
+/// Batches the tick's combat log per client. Clients get the entries of every actor they can
+/// see, entries involving their own character are sent first and the rest is capped.
+pub fn flush_combat_log(
+    mut reader: MessageReader<CombatLogMessage>,
+    q_actors: Query<(
+        &NetworkId,
+        &NameComponent,
+        Option<&InterestedClients>,
+        Option<&ClientIdComponent>,
+    )>,
+    tick: Res<ServerTick>,
+    mut file: Option<ResMut<CombatLogFile>>,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    if reader.is_empty() {
+        return;
+    }
+
+    let mut batches: HashMap<ClientId, (Vec<CombatLogEntry>, Vec<CombatLogEntry>)> = HashMap::new();
+
+    for msg in reader.read() {
+        let Ok((target_id, target_name, interested, target_client_id)) = q_actors.get(msg.target)
+        else {
+            continue;
+        };
+        // The source may have despawned since, e.g. the caster of a damage over time aura
+        let source = msg.source.and_then(|source| q_actors.get(source).ok());
+
+        let entry = CombatLogEntry {
+            source_id: source.map(|(network_id, ..)| network_id.0),
+            target_id: target_id.0,
+            event: msg.event.clone(),
+        };
+
+        if let Some(CombatLogFile(file)) = file.as_deref_mut() {
+            let record = CombatLogRecord {
+                tick: tick.0,
+                source_name: source.map(|(_, name, ..)| &*name.0),
+                target_name: &target_name.0,
+                entry: &entry,
+            };
+            let result = serde_json::to_writer(&mut *file, &record)
+                .map_err(std::io::Error::from)
+                .and_then(|_| file.write_all(b"\n"));
+            if let Err(err) = result {
+                tracing::warn!(?err, "failed to write combat log entry");
+            }
+        }
+
+        let mut involved = [
+            target_client_id.map(|client_id| client_id.0),
+            source.and_then(|(.., client_id)| client_id.map(|client_id| client_id.0)),
+        ];
+        if involved[0] == involved[1] {
+            involved[1] = None;
+        }
+        for client_id in involved.iter().flatten() {
+            batches.entry(*client_id).or_default().0.push(entry.clone());
+        }
+        for client_id in interested.into_iter().flat_map(|i| i.clients.iter()) {
+            if !involved.contains(&Some(*client_id)) {
+                batches.entry(*client_id).or_default().1.push(entry.clone());
+            }
+        }
+    }
+
+    if let Some(CombatLogFile(file)) = file.as_deref_mut()
+        && let Err(err) = file.flush()
+    {
+        tracing::warn!(?err, "failed to flush combat log");
+    }
+
+    for (client_id, (mut entries, others)) in batches {
+        entries.extend(others);
+        if entries.len() > MAX_COMBAT_LOG_ENTRIES_PER_TICK {
+            tracing::debug!(
+                %client_id,
+                dropped = entries.len() - MAX_COMBAT_LOG_ENTRIES_PER_TICK,
+                "combat log entries over the per-tick cap"
+            );
+            entries.truncate(MAX_COMBAT_LOG_ENTRIES_PER_TICK);
+        }
+        writer.write(OutgoingMessage::new(
+            vec![client_id],
+            OutgoingMessageData::CombatLog(entries),
+        ));
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{
+    combat::messages::DamageDealtMessage,
+    core::{ClientIdComponent, Dead, InterestedClients},
+    networking::{OutgoingMessage, OutgoingMessageData},
+};
+use bevy::prelude::*;
+use game_core::networking::NetworkId;
+
+/// Seconds without hostile activity before an actor leaves combat
+const COMBAT_TIMEOUT_SECS: f32 = 6.0;
+
+/// Present while an actor deals or takes damage. Blocks out-of-combat health regeneration
+/// and rage decay.
+#[derive(Component, Debug)]
+pub struct InCombat {
+    timeout: Timer,
+}
+
+impl Default for InCombat {
+    fn default() -> Self {
+        Self {
+            timeout: Timer::from_seconds(COMBAT_TIMEOUT_SECS, TimerMode::Once),
+        }
+    }
+}
+
+/// Puts both sides of every attack in combat, or extends their combat timeout. Misses count
+/// as hostile activity too.
+pub fn enter_combat(
+    mut commands: Commands,
+    mut reader: MessageReader<DamageDealtMessage>,
+    mut q_actors: Query<Option<&mut InCombat>, Without<Dead>>,
+) {
+    for msg in reader.read() {
+        for entity in [msg.attacker_entity, msg.target_entity] {
+            match q_actors.get_mut(entity) {
+                Ok(Some(mut in_combat)) => in_combat.timeout.reset(),
+                Ok(None) => {
+                    commands.entity(entity).try_insert(InCombat::default());
+                }
+                Err(_) => {}
+            }
+        }
+    }
+}
+
+/// Drops actors out of combat once their timeout runs out.
+pub fn tick_combat_timeouts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_in_combat: Query<(Entity, &mut InCombat)>,
+) {
+    for (entity, mut in_combat) in q_in_combat.iter_mut() {
+        in_combat.timeout.tick(time.delta());
+        if in_combat.timeout.is_finished() {
+            commands.entity(entity).remove::<InCombat>();
+        }
+    }
+}
+
+/// Tells interested clients and the actor itself when it enters or leaves combat.
+pub fn replicate_combat_state(
+    q_entered: Query<Entity, Added<InCombat>>,
+    mut left: RemovedComponents<InCombat>,
+    q_actors: Query<(&NetworkId, &InterestedClients, Option<&ClientIdComponent>)>,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    let entered = q_entered.iter().map(|entity| (entity, true));
+    let left = left.read().map(|entity| (entity, false));
+
+    for (entity, in_combat) in entered.chain(left) {
+        // Despawned actors drop out of combat without a message
+        let Ok((network_id, interested, client_id)) = q_actors.get(entity) else {
+            continue;
+        };
+
+        let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
+        recipients.extend(interested.clients.iter().copied());
+        if let Some(client_id) = client_id {
+            recipients.push(client_id.0);
+        }
+
+        writer.write(OutgoingMessage::new(
+            recipients,
+            OutgoingMessageData::CombatStateChanged {
+                network_id: *network_id,
+                in_combat,
+            },
+        ));
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{
+    combat::auras::Auras,
+    core::{ClientIdComponent, InterestedClients},
+    networking::{OutgoingMessage, OutgoingMessageData},
+};
+use avian3d::prelude::{Collider, SpatialQuery};
+use bevy::prelude::*;
+use game_core::{
+    auras::{AuraLibrary, AuraLibraryHandle},
+    character_controller::{self, CharacterVelocityY},
+    components::{GroundedComponent, MovementSpeedComponent},
+    crowd_control::{CrowdControl, CrowdControlState},
+    movement::MoveInput,
+    networking::NetworkId,
+};
+use std::time::Duration;
+
+/// Seconds after the last application before a diminishing returns category resets
+const DIMINISHING_RETURNS_RESET_SECS: f32 = 18.0;
+/// Duration multipliers of successive applications in the same category, immune after that
+const DIMINISHING_RETURNS: [f32; 3] = [1.0, 0.5, 0.25];
+/// Share of their movement speed feared actors flee at
+const FEAR_SPEED_FRACTION: f32 = 0.7;
+
+#[derive(Debug)]
+struct DiminishingEntry {
+    category: CrowdControl,
+    applications: usize,
+    reset: Timer,
+}
+
+/// Recent crowd-control applications per category, shortening repeated ones.
+#[derive(Component, Debug, Default)]
+pub struct DiminishingReturns {
+    entries: Vec<DiminishingEntry>,
+}
+
+impl DiminishingReturns {
+    /// Registers an application of `category` and returns its duration multiplier,
+    /// or `None` if the actor is immune to it for now.
+    pub fn apply(&mut self, category: CrowdControl) -> Option<f32> {
+        let index = match self.entries.iter().position(|e| e.category == category) {
+            Some(index) => index,
+            None => {
+                self.entries.push(DiminishingEntry {
+                    category,
+                    applications: 0,
+                    reset: Timer::from_seconds(DIMINISHING_RETURNS_RESET_SECS, TimerMode::Once),
+                });
+                self.entries.len() - 1
+            }
+        };
+
+        // Immune applications don't extend the window
+        let entry = &mut self.entries[index];
+        let multiplier = DIMINISHING_RETURNS.get(entry.applications).copied()?;
+        entry.applications += 1;
+        entry.reset.reset();
+        Some(multiplier)
+    }
+
+    fn tick(&mut self, delta: Duration) {
+        for entry in self.entries.iter_mut() {
+            entry.reset.tick(delta);
+        }
+        self.entries.retain(|entry| !entry.reset.is_finished());
+    }
+}
+
+pub fn tick_diminishing_returns(time: Res<Time>, mut q_returns: Query<&mut DiminishingReturns>) {
+    for mut returns in q_returns.iter_mut() {
+        if !returns.entries.is_empty() {
+            returns.tick(time.delta());
+        }
+    }
+}
+
+/// Derives the crowd-control state of actors from their auras.
+pub fn update_crowd_control(
+    library_handle: Res<AuraLibraryHandle>,
+    assets: Res<Assets<AuraLibrary>>,
+    mut q_actors: Query<(&Auras, &mut CrowdControlState), Changed<Auras>>,
+) {
+    let Some(library) = assets.get(&library_handle.0) else {
+        return;
+    };
+
+    for (auras, mut state) in q_actors.iter_mut() {
+        state.set_if_neq(auras.crowd_control(library));
+    }
+}
+
+/// Makes feared actors flee from the source of the fear. Their own movement input is
+/// ignored meanwhile, see `process_client_movements` and `apply_ai_movement`.
+#[allow(clippy::type_complexity)]
+pub fn move_feared_actors(
+    mut commands: Commands,
+    library_handle: Res<AuraLibraryHandle>,
+    assets: Res<Assets<AuraLibrary>>,
+    spatial_query: SpatialQuery,
+    mut q_actors: Query<(
+        Entity,
+        &CrowdControlState,
+        &Auras,
+        &mut Transform,
+        &Collider,
+        &MovementSpeedComponent,
+        &mut CharacterVelocityY,
+    )>,
+) {
+    let Some(library) = assets.get(&library_handle.0) else {
+        return;
+    };
+
+    // Sources are read before any feared actor is moved, stuns and roots still hold them
+    let feared: Vec<(Entity, Option<Vec3>)> = q_actors
+        .iter()
+        .filter(|(_, state, ..)| state.feared && !state.stunned && !state.rooted)
+        .map(|(entity, _, auras, ..)| {
+            let source_position = auras
+                .source_of(CrowdControl::Fear, library)
+                .and_then(|source| q_actors.get(source).ok())
+                .map(|(.., transform, _, _, _)| transform.translation);
+            (entity, source_position)
+        })
+        .collect();
+
+    for (entity, source_position) in feared {
+        let Ok((_, _, _, mut transform, collider, speed, mut vel_y)) = q_actors.get_mut(entity)
+        else {
+            continue;
+        };
+
+        // Without a source to run from, keep running straight ahead
+        let direction = source_position
+            .map(|source| {
+                (transform.translation - source)
+                    .with_y(0.0)
+                    .normalize_or_zero()
+            })
+            .filter(|direction| *direction != Vec3::ZERO)
+            .unwrap_or(transform.forward().with_y(0.0));
+
+        let input = MoveInput {
+            yaw: (-direction.x).atan2(-direction.z),
+            forward: 1.0,
+            sideways: 0.0,
+        };
+        let result = character_controller::character_move_step(
+            transform.translation,
+            vel_y.0,
+            &input,
+            speed.0 * FEAR_SPEED_FRACTION,
+            collider,
+            entity,
+            &spatial_query,
+        );
+
+        transform.translation = result.position;
+        transform.rotation = Quat::from_rotation_y(result.yaw);
+        vel_y.0 = result.velocity_y;
+
+        if result.grounded {
+            commands.entity(entity).insert(GroundedComponent);
+        } else {
+            commands.entity(entity).remove::<GroundedComponent>();
+        }
+    }
+}
+
+/// Sends crowd-control changes to interested clients and the affected actor. Newly spawned
+/// and revived actors are skipped, spawns carry the state already.
+#[allow(clippy::type_complexity)]
+pub fn replicate_crowd_control(
+    q_changed: Query<
+        (
+            &NetworkId,
+            Ref<CrowdControlState>,
+            &InterestedClients,
+            Option<&ClientIdComponent>,
+        ),
+        Changed<CrowdControlState>,
+    >,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    for (network_id, state, interested, client_id) in q_changed.iter() {
+        if state.is_added() {
+            continue;
+        }
+
+        let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
+        recipients.extend(interested.clients.iter().copied());
+        if let Some(client_id) = client_id {
+            recipients.push(client_id.0);
+        }
+
+        writer.write(OutgoingMessage::new(
+            recipients,
+            OutgoingMessageData::CrowdControlChanged {
+                network_id: *network_id,
+                state: *state,
+            },
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_applications_diminish_until_immune() {
+        let mut returns = DiminishingReturns::default();
+        assert_eq!(returns.apply(CrowdControl::Stun), Some(1.0));
+        assert_eq!(returns.apply(CrowdControl::Stun), Some(0.5));
+        assert_eq!(returns.apply(CrowdControl::Stun), Some(0.25));
+        assert_eq!(returns.apply(CrowdControl::Stun), None);
+    }
+
+    #[test]
+    fn categories_diminish_separately() {
+        let mut returns = DiminishingReturns::default();
+        returns.apply(CrowdControl::Stun);
+        returns.apply(CrowdControl::Stun);
+        assert_eq!(returns.apply(CrowdControl::Root), Some(1.0));
+        assert_eq!(returns.apply(CrowdControl::Fear), Some(1.0));
+    }
+
+    #[test]
+    fn categories_reset_after_window() {
+        let mut returns = DiminishingReturns::default();
+        returns.apply(CrowdControl::Silence);
+        returns.apply(CrowdControl::Silence);
+
+        returns.tick(Duration::from_secs_f32(
+            DIMINISHING_RETURNS_RESET_SECS - 1.0,
+        ));
+        assert_eq!(returns.apply(CrowdControl::Silence), Some(0.25));
+
+        returns.tick(Duration::from_secs_f32(DIMINISHING_RETURNS_RESET_SECS));
+        assert_eq!(returns.apply(CrowdControl::Silence), Some(1.0));
+    }
+}
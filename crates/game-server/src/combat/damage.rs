@@ -0,0 +1,190 @@
+use crate::combat::auras::Auras;
+use bevy::prelude::*;
+use game_core::{auras::AuraLibrary, stats::Stats};
+use protocol::models::{DamageSchool, HitResult, Impact};
+
+/// Schools an actor takes no damage from regardless of its auras, e.g. fire for a fire
+/// elemental. Mobs get it from their `MonsterDef`.
+#[derive(Component, Debug, Clone, Default)]
+pub struct DamageImmunities(pub Vec<DamageSchool>);
+
+/// Damage left of a hit after the target's mitigation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MitigatedDamage {
+    /// Damage taken by the target's health
+    pub amount: i32,
+    /// Soaked by absorb shields
+    pub absorbed: i32,
+    /// Negated by magic resistances, armor reduction is not reported
+    pub resisted: i32,
+}
+
+impl MitigatedDamage {
+    pub fn impact(&self, school: DamageSchool, result: HitResult) -> Impact {
+        Impact::Damage {
+            amount: self.amount,
+            result,
+            school,
+            absorbed: self.absorbed,
+            resisted: self.resisted,
+        }
+    }
+}
+
+/// Applies the target's armor or resistances, damage taken modifiers and absorb shields to
+/// a hit, in that order. Returns `None` if the target is immune to the school.
+pub fn mitigate_damage(
+    amount: i32,
+    school: DamageSchool,
+    attacker_level: i32,
+    stats: Option<&Stats>,
+    immunities: Option<&DamageImmunities>,
+    auras: Option<&mut Auras>,
+    library: Option<&AuraLibrary>,
+) -> Option<MitigatedDamage> {
+    if immunities.is_some_and(|immunities| immunities.0.contains(&school)) {
+        return None;
+    }
+    if let (Some(auras), Some(library)) = (auras.as_deref(), library)
+        && auras.immune_to(school, library)
+    {
+        return None;
+    }
+
+    let (amount, resisted) = match stats {
+        Some(stats) if school == DamageSchool::Physical => {
+            let reduction = stats.armor_reduction(attacker_level);
+            ((amount as f32 * (1.0 - reduction)).round() as i32, 0)
+        }
+        Some(stats) => {
+            let reduction = stats.resistance_reduction(school, attacker_level);
+            let resisted = (amount as f32 * reduction).round() as i32;
+            (amount - resisted, resisted)
+        }
+        None => (amount, 0),
+    };
+
+    let (Some(auras), Some(library)) = (auras, library) else {
+        return Some(MitigatedDamage {
+            amount,
+            absorbed: 0,
+            resisted,
+        });
+    };
+
+    let amount = (amount as f32 * auras.damage_taken_multiplier(library)).round() as i32;
+    let absorbed = if amount > 0 {
+        auras.absorb(amount, school, library)
+    } else {
+        0
+    };
+    Some(MitigatedDamage {
+        amount: amount - absorbed,
+        absorbed,
+        resisted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game_core::auras::{AbsorbDef, AuraDef, AuraKind};
+    use std::collections::HashMap;
+
+    const SHIELD_ID: u32 = 1;
+
+    fn shield_library(school: Option<DamageSchool>) -> AuraLibrary {
+        AuraLibrary {
+            auras: HashMap::from([(
+                SHIELD_ID,
+                AuraDef {
+                    name: "Shield".to_string(),
+                    kind: AuraKind::Buff,
+                    duration: 30.0,
+                    max_stacks: 1,
+                    periodic: None,
+                    modifiers: Vec::new(),
+                    crowd_control: None,
+                    absorb: Some(AbsorbDef { amount: 10, school }),
+                    visual_id: 0,
+                },
+            )]),
+        }
+    }
+
+    fn shielded(library: &AuraLibrary) -> Auras {
+        let mut auras = Auras::default();
+        let def = &library.auras[&SHIELD_ID];
+        auras.apply(SHIELD_ID, def, def.duration, 0, Entity::PLACEHOLDER, None);
+        auras
+    }
+
+    #[test]
+    fn immune_schools_take_no_damage() {
+        let immunities = DamageImmunities(vec![DamageSchool::Fire]);
+        let hit = |school| mitigate_damage(20, school, 1, None, Some(&immunities), None, None);
+        assert_eq!(hit(DamageSchool::Fire), None);
+        assert_eq!(
+            hit(DamageSchool::Frost).map(|damage| damage.amount),
+            Some(20)
+        );
+    }
+
+    #[test]
+    fn resistances_report_resisted_damage() {
+        let stats = Stats {
+            frost_resistance: 10,
+            ..default()
+        };
+        let damage =
+            mitigate_damage(20, DamageSchool::Frost, 1, Some(&stats), None, None, None).unwrap();
+        assert_eq!(
+            damage,
+            MitigatedDamage {
+                amount: 10,
+                absorbed: 0,
+                resisted: 10,
+            }
+        );
+    }
+
+    #[test]
+    fn shields_soak_damage_until_used_up() {
+        let library = shield_library(None);
+        let mut auras = shielded(&library);
+        let mut hit = |amount| {
+            mitigate_damage(
+                amount,
+                DamageSchool::Shadow,
+                1,
+                None,
+                None,
+                Some(&mut auras),
+                Some(&library),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(hit(6).amount, 0);
+        assert_eq!(hit(6).amount, 2);
+        let damage = hit(6);
+        assert_eq!((damage.amount, damage.absorbed), (6, 0));
+    }
+
+    #[test]
+    fn school_shields_ignore_other_schools() {
+        let library = shield_library(Some(DamageSchool::Fire));
+        let mut auras = shielded(&library);
+        let damage = mitigate_damage(
+            8,
+            DamageSchool::Physical,
+            1,
+            None,
+            None,
+            Some(&mut auras),
+            Some(&library),
+        )
+        .unwrap();
+        assert_eq!((damage.amount, damage.absorbed), (8, 0));
+    }
+}
@@ -0,0 +1,280 @@
+use bevy::prelude::*;
+use game_core::stats::Stats;
+use protocol::models::{DamageSchool, HitResult};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+const BASE_MISS_CHANCE: f32 = 5.0;
+const BASE_SPELL_MISS_CHANCE: f32 = 4.0;
+const BASE_DODGE_CHANCE: f32 = 5.0;
+const BASE_PARRY_CHANCE: f32 = 5.0;
+const BASE_BLOCK_CHANCE: f32 = 5.0;
+const BASE_CRIT_CHANCE: f32 = 5.0;
+/// Change in percentage points per level the defender is above the attacker
+const LEVEL_DIFFERENCE_CHANCE: f32 = 1.0;
+
+const CRIT_MULTIPLIER: f32 = 2.0;
+/// Share of the damage a successful block absorbs
+const BLOCK_REDUCTION: f32 = 0.3;
+
+/// Random source for combat rolls, seeded in tests so resolution is deterministic.
+#[derive(Resource)]
+pub struct CombatRng(pub StdRng);
+
+impl Default for CombatRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
+impl CombatRng {
+    pub fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackKind {
+    Melee,
+    /// Spells can only miss or crit, they can't be dodged, parried or blocked
+    Spell,
+}
+
+impl AttackKind {
+    /// Physical abilities roll the melee table, magic schools the spell table.
+    pub fn for_school(school: DamageSchool) -> Self {
+        match school {
+            DamageSchool::Physical => AttackKind::Melee,
+            _ => AttackKind::Spell,
+        }
+    }
+}
+
+/// Chance of each outcome in percent, rolled on a single table in this order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HitChances {
+    pub miss: f32,
+    pub dodge: f32,
+    pub parry: f32,
+    pub block: f32,
+    pub crit: f32,
+}
+
+impl HitChances {
+    /// Chances from the level difference, adjusted by the attacker's hit and crit and the
+    /// defender's dodge. Actors without stats roll on the base chances.
+    pub fn new(
+        kind: AttackKind,
+        attacker_level: i32,
+        attacker_stats: Option<&Stats>,
+        defender_level: i32,
+        defender_stats: Option<&Stats>,
+    ) -> Self {
+        let level_bonus = (defender_level - attacker_level) as f32 * LEVEL_DIFFERENCE_CHANCE;
+        let adjusted = |base: f32| (base + level_bonus).max(0.0);
+
+        let hit = attacker_stats.map_or(0.0, Stats::hit_chance);
+        let crit = attacker_stats.map_or(0.0, |stats| match kind {
+            AttackKind::Melee => stats.melee_crit_chance(),
+            AttackKind::Spell => stats.spell_crit_chance(),
+        });
+        let crit = (BASE_CRIT_CHANCE - level_bonus + crit).max(0.0);
+
+        match kind {
+            AttackKind::Melee => Self {
+                miss: adjusted(BASE_MISS_CHANCE - hit),
+                dodge: adjusted(
+                    BASE_DODGE_CHANCE + defender_stats.map_or(0.0, Stats::dodge_chance),
+                ),
+                parry: adjusted(BASE_PARRY_CHANCE),
+                block: adjusted(BASE_BLOCK_CHANCE),
+                crit,
+            },
+            AttackKind::Spell => Self {
+                miss: adjusted(BASE_SPELL_MISS_CHANCE - hit),
+                dodge: 0.0,
+                parry: 0.0,
+                block: 0.0,
+                crit,
+            },
+        }
+    }
+
+    /// Rolls once on the table, outcomes pushed past 100% can't happen.
+    pub fn roll(&self, rng: &mut impl Rng) -> HitResult {
+        let roll = rng.gen_range(0.0..100.0);
+        let table = [
+            (self.miss, HitResult::Miss),
+            (self.dodge, HitResult::Dodge),
+            (self.parry, HitResult::Parry),
+            (self.block, HitResult::Block),
+            (self.crit, HitResult::Critical),
+        ];
+
+        let mut threshold = 0.0;
+        for (chance, result) in table {
+            threshold += chance;
+            if roll < threshold {
+                return result;
+            }
+        }
+        HitResult::Hit
+    }
+}
+
+/// Damage dealt after applying the hit result to the rolled amount.
+pub fn apply_hit_result(amount: i32, result: HitResult) -> i32 {
+    match result {
+        HitResult::Hit => amount,
+        HitResult::Critical => (amount as f32 * CRIT_MULTIPLIER).round() as i32,
+        HitResult::Block => (amount as f32 * (1.0 - BLOCK_REDUCTION)).round() as i32,
+        HitResult::Miss | HitResult::Dodge | HitResult::Parry => 0,
+    }
+}
+
+/// Rolls an attack and returns the outcome with the resulting damage.
+pub fn resolve_attack(
+    rng: &mut impl Rng,
+    kind: AttackKind,
+    attacker_level: i32,
+    attacker_stats: Option<&Stats>,
+    defender_level: i32,
+    defender_stats: Option<&Stats>,
+    amount: i32,
+) -> (HitResult, i32) {
+    let result = HitChances::new(
+        kind,
+        attacker_level,
+        attacker_stats,
+        defender_level,
+        defender_stats,
+    )
+    .roll(rng);
+    (result, apply_hit_result(amount, result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcomes(
+        kind: AttackKind,
+        attacker: Option<&Stats>,
+        defender: Option<&Stats>,
+    ) -> Vec<HitResult> {
+        let mut rng = CombatRng::seeded(42);
+        (0..10_000)
+            .map(|_| resolve_attack(&mut rng.0, kind, 10, attacker, 10, defender, 10).0)
+            .collect()
+    }
+
+    fn share(results: &[HitResult], result: HitResult) -> f32 {
+        results.iter().filter(|r| **r == result).count() as f32 / results.len() as f32 * 100.0
+    }
+
+    #[test]
+    fn same_seed_gives_same_results() {
+        let mut a = CombatRng::seeded(7);
+        let mut b = CombatRng::seeded(7);
+        for _ in 0..100 {
+            assert_eq!(
+                resolve_attack(&mut a.0, AttackKind::Melee, 10, None, 12, None, 20),
+                resolve_attack(&mut b.0, AttackKind::Melee, 10, None, 12, None, 20),
+            );
+        }
+    }
+
+    #[test]
+    fn spells_are_never_dodged_parried_or_blocked() {
+        let results = outcomes(AttackKind::Spell, None, None);
+        assert!(
+            !results
+                .iter()
+                .any(|r| matches!(r, HitResult::Dodge | HitResult::Parry | HitResult::Block))
+        );
+        assert!(results.contains(&HitResult::Miss));
+        assert!(results.contains(&HitResult::Critical));
+    }
+
+    #[test]
+    fn melee_rolls_match_chances() {
+        let results = outcomes(AttackKind::Melee, None, None);
+        for result in [
+            HitResult::Miss,
+            HitResult::Dodge,
+            HitResult::Parry,
+            HitResult::Block,
+            HitResult::Critical,
+        ] {
+            let share = share(&results, result);
+            assert!((share - 5.0).abs() < 1.0, "{result:?} at {share}%");
+        }
+    }
+
+    #[test]
+    fn higher_level_defenders_avoid_more() {
+        let even = HitChances::new(AttackKind::Melee, 10, None, 10, None);
+        let higher = HitChances::new(AttackKind::Melee, 10, None, 13, None);
+        assert!(higher.miss > even.miss);
+        assert!(higher.dodge > even.dodge);
+        assert!(higher.crit < even.crit);
+
+        let lower = HitChances::new(AttackKind::Melee, 20, None, 10, None);
+        assert_eq!(lower.miss, 0.0);
+        assert_eq!(lower.crit, 15.0);
+    }
+
+    #[test]
+    fn stats_shift_the_rolls() {
+        let attacker = Stats {
+            hit: 5,
+            crit: 10,
+            ..default()
+        };
+        let defender = Stats {
+            dodge: 10,
+            ..default()
+        };
+        let results = outcomes(AttackKind::Melee, Some(&attacker), Some(&defender));
+        assert!(!results.contains(&HitResult::Miss));
+        for (result, chance) in [
+            (HitResult::Dodge, 15.0),
+            (HitResult::Parry, 5.0),
+            (HitResult::Critical, 15.0),
+        ] {
+            let share = share(&results, result);
+            assert!((share - chance).abs() < 1.0, "{result:?} at {share}%");
+        }
+
+        // Intellect only raises the crit chance of spells
+        let caster = Stats {
+            intellect: 200,
+            ..default()
+        };
+        let melee = outcomes(AttackKind::Melee, Some(&caster), None);
+        let spells = outcomes(AttackKind::Spell, Some(&caster), None);
+        assert!((share(&melee, HitResult::Critical) - 5.0).abs() < 1.0);
+        assert!((share(&spells, HitResult::Critical) - 15.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn hit_results_scale_damage() {
+        assert_eq!(apply_hit_result(10, HitResult::Hit), 10);
+        assert_eq!(apply_hit_result(10, HitResult::Critical), 20);
+        assert_eq!(apply_hit_result(10, HitResult::Block), 7);
+        assert_eq!(apply_hit_result(10, HitResult::Miss), 0);
+        assert_eq!(apply_hit_result(10, HitResult::Dodge), 0);
+        assert_eq!(apply_hit_result(10, HitResult::Parry), 0);
+    }
+
+    #[test]
+    fn physical_damage_rolls_the_melee_table() {
+        assert_eq!(
+            AttackKind::for_school(DamageSchool::Physical),
+            AttackKind::Melee
+        );
+        assert_eq!(
+            AttackKind::for_school(DamageSchool::Fire),
+            AttackKind::Spell
+        );
+    }
+}
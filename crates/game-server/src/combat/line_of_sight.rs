@@ -0,0 +1,18 @@
+use avian3d::prelude::{SpatialQuery, SpatialQueryFilter};
+use bevy::prelude::*;
+use game_core::{collision::GameLayer, constants::ACTOR_EYE_OFFSET};
+
+/// Whether terrain or props block the view from an actor at `from` to the point `to`.
+/// `from` is the viewer's capsule center, the ray starts at its eye height.
+/// Actors live on their own layer, so they never block line of sight.
+pub fn has_line_of_sight(spatial_query: &SpatialQuery, from: Vec3, to: Vec3) -> bool {
+    let origin = from + Vec3::Y * ACTOR_EYE_OFFSET;
+    let Ok((direction, distance)) = Dir3::new_and_length(to - origin) else {
+        return true;
+    };
+
+    let filter = SpatialQueryFilter::from_mask([GameLayer::Default, GameLayer::Ground]);
+    spatial_query
+        .cast_ray(origin, direction, distance, true, &filter)
+        .is_none()
+}
@@ -1,9 +1,37 @@
 use bevy::prelude::*;
 use bevy_renet::renet::ClientId;
 
+/// What a spell is being cast at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CastTarget {
+    Entity(Entity),
+    /// Ground position, only valid for area spells
+    Position(Vec3),
+}
+
 #[derive(Message, Debug)]
 pub struct CastSpellActionMessage {
     pub caster_entity: Entity,
+    pub target: CastTarget,
+    pub spell_id: u32,
+}
+
+/// Written when an area spell finishes casting, resolved into one `ApplySpellEffectMessage`
+/// per actor in the area.
+#[derive(Message, Debug)]
+pub struct ApplyAreaSpellMessage {
+    pub caster_entity: Entity,
+    pub caster_client_id: Option<ClientId>,
+    pub target: CastTarget,
+    pub spell_id: u32,
+}
+
+/// Written when a spell with a projectile finishes casting, its effects are applied once the
+/// projectile reaches the target.
+#[derive(Message, Debug)]
+pub struct LaunchProjectileMessage {
+    pub caster_entity: Entity,
+    pub caster_client_id: Option<ClientId>,
     pub target_entity: Entity,
     pub spell_id: u32,
 }
@@ -26,3 +54,52 @@ pub struct StartAttackMessage {
 pub struct StopAttackMessage {
     pub attacker_entity: Entity,
 }
+
+/// Written when a dead player releases their spirit to respawn at a graveyard.
+#[derive(Message, Debug)]
+pub struct ReleaseSpiritMessage {
+    pub player_entity: Entity,
+}
+
+/// Written whenever damage lands, after all reductions. Used for rage generation and threat.
+/// `spell_id` is `AUTO_ATTACK_VISUAL_ID` for swings and the applying spell for periodic damage.
+#[derive(Message, Debug)]
+pub struct DamageDealtMessage {
+    pub attacker_entity: Entity,
+    pub target_entity: Entity,
+    pub spell_id: u32,
+    pub amount: i32,
+}
+
+/// Written whenever a heal lands, `amount` excludes overhealing. Used for healing threat.
+#[derive(Message, Debug)]
+pub struct HealingDoneMessage {
+    pub healer_entity: Entity,
+    pub target_entity: Entity,
+    pub spell_id: u32,
+    pub amount: i32,
+}
+
+#[derive(Message, Debug)]
+pub struct RequestDuelMessage {
+    pub challenger_entity: Entity,
+    pub target_entity: Entity,
+}
+
+#[derive(Message, Debug)]
+pub struct AcceptDuelMessage {
+    pub player_entity: Entity,
+}
+
+#[derive(Message, Debug)]
+pub struct SetPvpFlagMessage {
+    pub player_entity: Entity,
+    pub enabled: bool,
+}
+
+/// Written when a player's duel or PvP flag changes how others regard them, once the change
+/// is applied.
+#[derive(Message, Debug)]
+pub struct ReactionsChangedMessage {
+    pub entity: Entity,
+}
@@ -1,22 +1,45 @@
+mod area;
+mod auras;
 mod auto_attack;
+mod combat_log;
+mod combat_state;
+mod crowd_control;
+mod damage;
+mod hit_table;
+mod line_of_sight;
 pub mod messages;
+mod power;
+mod projectiles;
+mod pvp;
+mod resurrection;
 mod spells;
+mod stats;
 mod vitals;
 
 use avian3d::prelude::*;
 use bevy::prelude::*;
 
+pub use area::is_in_cone;
+pub use auras::Auras;
+pub use auto_attack::{AutoAttack, MELEE_RANGE, Weapon};
+pub use combat_log::CombatLogMessage;
+pub use combat_state::InCombat;
+pub use crowd_control::DiminishingReturns;
+pub use damage::DamageImmunities;
+pub use hit_table::CombatRng;
+pub use line_of_sight::has_line_of_sight;
 pub use messages::*;
-pub use spells::{Abilities, Casting};
-pub use vitals::EntityDeath;
+pub use power::PowerRegen;
+pub use spells::{Abilities, Ability, Casting, SavedCooldowns};
+pub use vitals::{EntityDeath, LivingBundle, on_entity_death, on_vitals_changed};
 
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CombatSet {
     /// Validate and begin combat actions (spell casts, start/stop attack).
     ProcessActions,
-    /// Tick ongoing combat state (swing timers, cast bars, cooldowns).
+    /// Tick ongoing combat state (swing timers, cast bars, projectiles, cooldowns, auras).
     Tick,
-    /// Apply resolved effects (spell damage).
+    /// Apply resolved effects (spell damage, area spells).
     ApplyEffects,
 }
 
@@ -25,16 +48,37 @@ pub struct CombatPlugin;
 impl Plugin for CombatPlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<CastSpellActionMessage>();
+        app.add_message::<ApplyAreaSpellMessage>();
+        app.add_message::<LaunchProjectileMessage>();
         app.add_message::<ApplySpellEffectMessage>();
         app.add_message::<StartAttackMessage>();
         app.add_message::<StopAttackMessage>();
+        app.add_message::<ReleaseSpiritMessage>();
+        app.add_message::<DamageDealtMessage>();
+        app.add_message::<HealingDoneMessage>();
+        app.add_message::<RequestDuelMessage>();
+        app.add_message::<AcceptDuelMessage>();
+        app.add_message::<SetPvpFlagMessage>();
+        app.add_message::<ReactionsChangedMessage>();
+        app.add_message::<CombatLogMessage>();
+
+        app.init_resource::<SavedCooldowns>();
+        app.init_resource::<CombatRng>();
+        app.init_resource::<vitals::HealthRegenTimer>();
+
+        app.add_systems(Startup, combat_log::open_combat_log_file);
 
         app.add_systems(
             FixedPreUpdate,
             (
+                spells::release_queued_casts.before(spells::process_spell_casts),
                 spells::process_spell_casts,
                 auto_attack::process_start_attack,
                 auto_attack::process_stop_attack,
+                resurrection::process_release_spirit,
+                pvp::process_duel_requests,
+                pvp::process_duel_accepts,
+                pvp::process_pvp_flags,
             )
                 .in_set(CombatSet::ProcessActions),
         );
@@ -43,18 +87,59 @@ impl Plugin for CombatPlugin {
             FixedUpdate,
             (
                 vitals::on_vitals_changed,
-                spells::tick_casting,
+                auras::tick_auras,
+                auras::apply_movement_speed_modifiers.after(auras::tick_auras),
+                crowd_control::update_crowd_control.after(auras::tick_auras),
+                crowd_control::move_feared_actors.after(crowd_control::update_crowd_control),
+                crowd_control::tick_diminishing_returns,
+                stats::recompute_stats.after(auras::tick_auras),
+                (
+                    spells::apply_cast_pushback
+                        .after(auto_attack::tick_auto_attack)
+                        .after(auras::tick_auras),
+                    spells::tick_casting,
+                )
+                    .chain()
+                    .after(crowd_control::update_crowd_control),
+                (
+                    projectiles::launch_projectiles,
+                    projectiles::tick_projectiles,
+                )
+                    .chain()
+                    .after(spells::tick_casting),
                 spells::tick_ability_cooldowns,
-                auto_attack::tick_auto_attack,
+                power::regenerate_power,
+                vitals::regenerate_health,
+                combat_state::tick_combat_timeouts,
+                auto_attack::equip_weapons,
+                auto_attack::tick_auto_attack.after(auto_attack::equip_weapons),
                 auto_attack::cancel_auto_attack_on_death,
                 vitals::tick_corpse_despawn_timers,
+                pvp::tick_duel_requests,
+                pvp::tick_duels
+                    .after(auto_attack::tick_auto_attack)
+                    .after(auras::tick_auras)
+                    .before(vitals::on_vitals_changed),
+                pvp::tick_pvp_flags,
             )
                 .in_set(CombatSet::Tick),
         );
 
         app.add_systems(
             FixedPostUpdate,
-            spells::apply_spell_effect
+            (
+                area::resolve_area_spells,
+                spells::apply_spell_effect,
+                resurrection::apply_resurrections,
+                combat_state::enter_combat,
+                power::generate_rage,
+                power::replicate_power,
+                combat_state::replicate_combat_state,
+                crowd_control::replicate_crowd_control,
+                pvp::replicate_reactions,
+                combat_log::flush_combat_log,
+            )
+                .chain()
                 .in_set(CombatSet::ApplyEffects)
                 .after(PhysicsSystems::Last),
         );
@@ -0,0 +1,141 @@
+use crate::{
+    combat::{InCombat, messages::DamageDealtMessage},
+    core::{ClientIdComponent, Dead},
+    networking::{OutgoingMessage, OutgoingMessageData},
+    social::PartyId,
+};
+use bevy::prelude::*;
+use game_core::{networking::NetworkId, power::Power};
+use protocol::models::PowerKind;
+
+const POWER_TICK_SECS: f32 = 2.0;
+/// Seconds after spending mana before it regenerates again
+const MANA_REGEN_DELAY_SECS: f32 = 5.0;
+/// Share of maximum mana regenerated per tick
+const MANA_REGEN_FRACTION: f32 = 0.04;
+const ENERGY_PER_TICK: i32 = 20;
+const RAGE_DECAY_PER_TICK: i32 = 3;
+const RAGE_PER_DAMAGE_DEALT: f32 = 0.5;
+const RAGE_PER_DAMAGE_TAKEN: f32 = 0.25;
+
+/// Server-side regeneration state of a power pool.
+#[derive(Component, Debug)]
+pub struct PowerRegen {
+    tick: Timer,
+    /// Pauses mana regeneration after spending
+    delay: Timer,
+}
+
+impl Default for PowerRegen {
+    fn default() -> Self {
+        let mut delay = Timer::from_seconds(0.0, TimerMode::Once);
+        delay.tick(std::time::Duration::ZERO);
+        Self {
+            tick: Timer::from_seconds(POWER_TICK_SECS, TimerMode::Repeating),
+            delay,
+        }
+    }
+}
+
+impl PowerRegen {
+    /// Restarts the delay before mana regenerates again.
+    pub fn interrupt(&mut self, kind: PowerKind) {
+        if kind == PowerKind::Mana {
+            self.delay = Timer::from_seconds(MANA_REGEN_DELAY_SECS, TimerMode::Once);
+        }
+    }
+}
+
+/// Regenerates mana and energy on every power tick, rage decays once out of combat.
+pub fn regenerate_power(
+    time: Res<Time>,
+    mut q_power: Query<(&mut Power, &mut PowerRegen, Has<InCombat>), Without<Dead>>,
+) {
+    for (mut power, mut regen, in_combat) in q_power.iter_mut() {
+        regen.delay.tick(time.delta());
+        regen.tick.tick(time.delta());
+        let ticks = regen.tick.times_finished_this_tick() as i32;
+        if ticks == 0 || !regen.delay.is_finished() {
+            continue;
+        }
+
+        let change = match power.kind {
+            PowerKind::Mana => {
+                ((power.max as f32 * MANA_REGEN_FRACTION).round() as i32).max(1) * ticks
+            }
+            PowerKind::Energy => ENERGY_PER_TICK * ticks,
+            PowerKind::Rage if in_combat => continue,
+            PowerKind::Rage => -RAGE_DECAY_PER_TICK * ticks,
+        };
+
+        // Only touch the component when the value moves, so full pools aren't replicated
+        let current = (power.current + change).clamp(0, power.max);
+        if current != power.current {
+            power.current = current;
+        }
+    }
+}
+
+/// Builds rage for both sides of every hit.
+pub fn generate_rage(
+    mut reader: MessageReader<DamageDealtMessage>,
+    mut q_power: Query<&mut Power, Without<Dead>>,
+) {
+    for msg in reader.read() {
+        if msg.amount <= 0 {
+            continue;
+        }
+
+        let sides = [
+            (msg.attacker_entity, RAGE_PER_DAMAGE_DEALT),
+            (msg.target_entity, RAGE_PER_DAMAGE_TAKEN),
+        ];
+        for (entity, rate) in sides {
+            let Ok(mut power) = q_power.get_mut(entity) else {
+                continue;
+            };
+            if power.kind != PowerKind::Rage {
+                continue;
+            }
+
+            let amount = ((msg.amount as f32 * rate).round() as i32).max(1);
+            if power.current < power.max {
+                power.gain(amount);
+            }
+        }
+    }
+}
+
+/// Sends power changes to the owning client and their party members. Joining a party
+/// replicates the pool to the new party as well.
+#[allow(clippy::type_complexity)]
+pub fn replicate_power(
+    q_changed: Query<
+        (&NetworkId, &Power, &ClientIdComponent, Option<&PartyId>),
+        Or<(Changed<Power>, Changed<PartyId>)>,
+    >,
+    q_party_members: Query<(&ClientIdComponent, &PartyId)>,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    for (network_id, power, client_id, party_id) in q_changed.iter() {
+        let mut recipients = vec![client_id.0];
+        if let Some(party_id) = party_id {
+            recipients.extend(
+                q_party_members
+                    .iter()
+                    .filter(|(member, member_party)| {
+                        member_party.0 == party_id.0 && member.0 != client_id.0
+                    })
+                    .map(|(member, _)| member.0),
+            );
+        }
+
+        writer.write(OutgoingMessage::new(
+            recipients,
+            OutgoingMessageData::PowerChanged {
+                network_id: *network_id,
+                power: *power,
+            },
+        ));
+    }
+}
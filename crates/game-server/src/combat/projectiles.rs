@@ -0,0 +1,127 @@
+use crate::{
+    combat::messages::{ApplySpellEffectMessage, LaunchProjectileMessage},
+    core::{ClientIdComponent, InterestedClients},
+    networking::{OutgoingMessage, OutgoingMessageData},
+};
+use bevy::prelude::*;
+use bevy_renet::renet::ClientId;
+use game_core::{
+    components::Vitals,
+    networking::NetworkId,
+    spells::{SpellLibrary, SpellLibraryHandle},
+};
+
+/// Server-side homing projectile. It is not an actor, so the position is kept here instead of
+/// in a `Transform` to keep it out of the spatial grid and visibility.
+#[derive(Component, Debug)]
+pub struct Projectile {
+    pub caster_entity: Entity,
+    pub caster_client_id: Option<ClientId>,
+    pub target_entity: Entity,
+    pub spell_id: u32,
+    pub position: Vec3,
+    pub speed: f32,
+}
+
+#[allow(clippy::type_complexity)]
+pub fn launch_projectiles(
+    mut commands: Commands,
+    mut reader: MessageReader<LaunchProjectileMessage>,
+    library_handle: Res<SpellLibraryHandle>,
+    assets: Res<Assets<SpellLibrary>>,
+    q_caster: Query<(
+        &NetworkId,
+        &Transform,
+        &InterestedClients,
+        Option<&ClientIdComponent>,
+    )>,
+    q_target: Query<(&NetworkId, &Transform), With<Vitals>>,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    let Some(library) = assets.get(&library_handle.0) else {
+        return;
+    };
+
+    for msg in reader.read() {
+        let Some(speed) = library
+            .spells
+            .get(&msg.spell_id)
+            .and_then(|spell| spell.projectile_speed)
+        else {
+            tracing::warn!(spell_id = %msg.spell_id, "tried to launch projectile for spell without one");
+            continue;
+        };
+
+        let Ok((caster_network_id, caster_transform, interested, caster_client_id)) =
+            q_caster.get(msg.caster_entity)
+        else {
+            continue;
+        };
+        let Ok((target_network_id, target_transform)) = q_target.get(msg.target_entity) else {
+            tracing::debug!(target = ?msg.target_entity, "projectile target is gone before launch");
+            continue;
+        };
+
+        let speed = speed.max(f32::EPSILON);
+        let position = caster_transform.translation;
+        let travel_time = position.distance(target_transform.translation) / speed;
+
+        commands.spawn(Projectile {
+            caster_entity: msg.caster_entity,
+            caster_client_id: msg.caster_client_id,
+            target_entity: msg.target_entity,
+            spell_id: msg.spell_id,
+            position,
+            speed,
+        });
+
+        let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
+        recipients.extend(interested.clients.iter().copied());
+        if let Some(client_id) = caster_client_id {
+            recipients.push(client_id.0);
+        }
+
+        writer.write(OutgoingMessage::new(
+            recipients,
+            OutgoingMessageData::ProjectileLaunched {
+                caster_network_id: *caster_network_id,
+                target_network_id: *target_network_id,
+                spell_id: msg.spell_id,
+                travel_time,
+            },
+        ));
+    }
+}
+
+/// Moves projectiles towards their target and applies the spell on arrival.
+/// Projectiles whose target died or despawned fizzle out.
+pub fn tick_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_projectiles: Query<(Entity, &mut Projectile)>,
+    q_targets: Query<&Transform, With<Vitals>>,
+    mut writer: MessageWriter<ApplySpellEffectMessage>,
+) {
+    for (entity, mut projectile) in q_projectiles.iter_mut() {
+        let Ok(target_transform) = q_targets.get(projectile.target_entity) else {
+            tracing::debug!(target = ?projectile.target_entity, "projectile target is gone");
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        let step = projectile.speed * time.delta_secs();
+        let to_target = target_transform.translation - projectile.position;
+        if to_target.length() > step {
+            projectile.position += to_target.normalize() * step;
+            continue;
+        }
+
+        writer.write(ApplySpellEffectMessage {
+            caster_entity: projectile.caster_entity,
+            caster_client_id: projectile.caster_client_id,
+            target_entity: projectile.target_entity,
+            spell_id: projectile.spell_id,
+        });
+        commands.entity(entity).despawn();
+    }
+}
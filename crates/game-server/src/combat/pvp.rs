@@ -0,0 +1,491 @@
+use crate::{
+    assets::Reactions,
+    combat::{
+        Auras,
+        auto_attack::{AutoAttack, NextSwing},
+        messages::{
+            AcceptDuelMessage, DamageDealtMessage, ReactionsChangedMessage, RequestDuelMessage,
+            SetPvpFlagMessage,
+        },
+    },
+    core::{ClientIdComponent, Dead, Duel, DuelRequest, InterestedClients, PvpFlag},
+    networking::{OutgoingMessage, OutgoingMessageData},
+};
+use bevy::prelude::*;
+use game_core::{components::Vitals, networking::NetworkId};
+use protocol::models::DuelEndReason;
+
+/// Maximum distance between the challenger and the challenged player
+const DUEL_REQUEST_RANGE: f32 = 20.0;
+/// Seconds a duel request waits to be accepted
+const DUEL_REQUEST_TIMEOUT_SECS: f32 = 30.0;
+/// Seconds between accepting a duel and the duelists becoming hostile
+const DUEL_COUNTDOWN_SECS: f32 = 3.0;
+/// Duelists further than this from the centre of the duel lose
+const DUEL_BOUNDARY_RADIUS: f32 = 40.0;
+/// Seconds a PvP flag stays up after the player turns it off
+const PVP_FLAG_TIMEOUT_SECS: f32 = 300.0;
+
+/// Sends duel requests to the challenged player, if both are free to duel and close enough.
+#[allow(clippy::type_complexity)]
+pub fn process_duel_requests(
+    mut commands: Commands,
+    mut reader: MessageReader<RequestDuelMessage>,
+    q_players: Query<
+        (
+            &NetworkId,
+            &Transform,
+            &ClientIdComponent,
+            Has<Duel>,
+            Has<DuelRequest>,
+        ),
+        Without<Dead>,
+    >,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    for msg in reader.read() {
+        if msg.challenger_entity == msg.target_entity {
+            tracing::debug!(challenger = ?msg.challenger_entity, "cannot duel self");
+            continue;
+        }
+
+        let (
+            Ok((challenger_network_id, challenger_transform, _, challenger_in_duel, _)),
+            Ok((_, target_transform, target_client_id, target_in_duel, target_requested)),
+        ) = (
+            q_players.get(msg.challenger_entity),
+            q_players.get(msg.target_entity),
+        )
+        else {
+            tracing::debug!(
+                challenger = ?msg.challenger_entity,
+                target = ?msg.target_entity,
+                "duel request between invalid or dead players"
+            );
+            continue;
+        };
+
+        if challenger_in_duel || target_in_duel || target_requested {
+            tracing::debug!(challenger = ?msg.challenger_entity, target = ?msg.target_entity, "duel participant is busy");
+            continue;
+        }
+        if challenger_transform
+            .translation
+            .distance(target_transform.translation)
+            > DUEL_REQUEST_RANGE
+        {
+            tracing::debug!(challenger = ?msg.challenger_entity, target = ?msg.target_entity, "duel target out of range");
+            continue;
+        }
+
+        commands.entity(msg.target_entity).insert(DuelRequest {
+            challenger: msg.challenger_entity,
+            expiry: Timer::from_seconds(DUEL_REQUEST_TIMEOUT_SECS, TimerMode::Once),
+        });
+        writer.write(OutgoingMessage::new(
+            vec![target_client_id.0],
+            OutgoingMessageData::DuelRequested {
+                challenger_network_id: *challenger_network_id,
+            },
+        ));
+    }
+}
+
+/// Starts the countdown of an accepted duel, centring its boundary between the duelists.
+pub fn process_duel_accepts(
+    mut commands: Commands,
+    mut reader: MessageReader<AcceptDuelMessage>,
+    q_requests: Query<&DuelRequest>,
+    q_players: Query<(&NetworkId, &Transform, &ClientIdComponent, Has<Duel>), Without<Dead>>,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    for msg in reader.read() {
+        let Ok(request) = q_requests.get(msg.player_entity) else {
+            tracing::debug!(player = ?msg.player_entity, "no duel request to accept");
+            continue;
+        };
+        commands.entity(msg.player_entity).remove::<DuelRequest>();
+
+        let (
+            Ok((player_network_id, player_transform, player_client_id, player_in_duel)),
+            Ok((
+                challenger_network_id,
+                challenger_transform,
+                challenger_client_id,
+                challenger_in_duel,
+            )),
+        ) = (
+            q_players.get(msg.player_entity),
+            q_players.get(request.challenger),
+        )
+        else {
+            tracing::debug!(player = ?msg.player_entity, "duel challenger is gone or dead");
+            continue;
+        };
+        if player_in_duel || challenger_in_duel {
+            tracing::debug!(player = ?msg.player_entity, "duel participant is busy");
+            continue;
+        }
+
+        let center = (player_transform.translation + challenger_transform.translation) / 2.0;
+        let duelists = [
+            (
+                msg.player_entity,
+                player_client_id,
+                request.challenger,
+                challenger_network_id,
+            ),
+            (
+                request.challenger,
+                challenger_client_id,
+                msg.player_entity,
+                player_network_id,
+            ),
+        ];
+        for (entity, client_id, opponent, opponent_network_id) in duelists {
+            commands.entity(entity).insert(Duel {
+                opponent,
+                opponent_id: *opponent_network_id,
+                center,
+                countdown: Timer::from_seconds(DUEL_COUNTDOWN_SECS, TimerMode::Once),
+            });
+            writer.write(OutgoingMessage::new(
+                vec![client_id.0],
+                OutgoingMessageData::DuelStarting {
+                    opponent_network_id: *opponent_network_id,
+                    center,
+                    radius: DUEL_BOUNDARY_RADIUS,
+                    countdown: DUEL_COUNTDOWN_SECS,
+                },
+            ));
+        }
+    }
+}
+
+/// Drops duel requests that were not accepted in time.
+pub fn tick_duel_requests(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_requests: Query<(Entity, &mut DuelRequest)>,
+) {
+    for (entity, mut request) in q_requests.iter_mut() {
+        if request.expiry.tick(time.delta()).is_finished() {
+            commands.entity(entity).remove::<DuelRequest>();
+        }
+    }
+}
+
+/// Runs duel countdowns and decides duels. A lethal hit from the opponent leaves the loser at
+/// 1 hp instead of killing them, so this runs before deaths are resolved. Leaving the boundary,
+/// dying to something else or disconnecting forfeits the duel.
+#[allow(clippy::type_complexity)]
+pub fn tick_duels(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut damage_reader: MessageReader<DamageDealtMessage>,
+    mut q_duelists: Query<(
+        Entity,
+        &NetworkId,
+        &mut Duel,
+        &Transform,
+        Option<&mut Vitals>,
+        Option<&mut Auras>,
+        &InterestedClients,
+        &ClientIdComponent,
+    )>,
+    mut writer: MessageWriter<OutgoingMessage>,
+    mut reactions_writer: MessageWriter<ReactionsChangedMessage>,
+) {
+    let mut losers: Vec<(Entity, DuelEndReason)> = Vec::new();
+
+    for msg in damage_reader.read() {
+        let Ok((_, network_id, duel, _, Some(mut vitals), _, interested, client_id)) =
+            q_duelists.get_mut(msg.target_entity)
+        else {
+            continue;
+        };
+        if duel.opponent != msg.attacker_entity || !duel.is_active() || vitals.hp > 0 {
+            continue;
+        }
+
+        vitals.hp = 1;
+        let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
+        recipients.extend(interested.clients.iter().copied());
+        recipients.push(client_id.0);
+        writer.write(OutgoingMessage::new(
+            recipients,
+            OutgoingMessageData::VitalsChanged {
+                network_id: *network_id,
+                vitals: vitals.clone(),
+            },
+        ));
+        losers.push((msg.target_entity, DuelEndReason::Defeated));
+    }
+
+    for (entity, _, mut duel, transform, vitals, ..) in q_duelists.iter_mut() {
+        if !duel.is_active() && duel.countdown.tick(time.delta()).is_finished() {
+            reactions_writer.write(ReactionsChangedMessage { entity });
+        }
+
+        // Dead actors have no vitals
+        if vitals.is_none() {
+            losers.push((entity, DuelEndReason::Forfeited));
+        } else if transform.translation.distance(duel.center) > DUEL_BOUNDARY_RADIUS {
+            losers.push((entity, DuelEndReason::LeftBoundary));
+        }
+    }
+
+    let mut ended: Vec<Entity> = Vec::new();
+    for (loser, reason) in losers {
+        if ended.contains(&loser) {
+            continue;
+        }
+        let Ok((_, loser_network_id, duel, _, _, _, loser_interested, loser_client_id)) =
+            q_duelists.get(loser)
+        else {
+            continue;
+        };
+        let winner = duel.opponent;
+
+        let mut recipients: Vec<_> = loser_interested.clients.iter().copied().collect();
+        recipients.push(loser_client_id.0);
+        let winner_network_id = match q_duelists.get(winner) {
+            Ok((_, winner_network_id, _, _, _, _, winner_interested, winner_client_id)) => {
+                recipients.extend(winner_interested.clients.iter().copied());
+                recipients.push(winner_client_id.0);
+                *winner_network_id
+            }
+            Err(_) => duel.opponent_id,
+        };
+        recipients.sort_unstable();
+        recipients.dedup();
+
+        tracing::debug!(?winner, ?loser, ?reason, "duel ended");
+        writer.write(OutgoingMessage::new(
+            recipients,
+            OutgoingMessageData::DuelEnded {
+                winner_network_id,
+                loser_network_id: *loser_network_id,
+                reason,
+            },
+        ));
+        end_duel(
+            &mut commands,
+            &mut reactions_writer,
+            &mut q_duelists,
+            loser,
+            winner,
+        );
+        ended.extend([loser, winner]);
+    }
+
+    // Duelists whose opponent disconnected win by forfeit
+    let abandoned: Vec<Entity> = q_duelists
+        .iter()
+        .filter(|(entity, _, duel, ..)| {
+            !ended.contains(entity) && !q_duelists.contains(duel.opponent)
+        })
+        .map(|(entity, ..)| entity)
+        .collect();
+    for winner in abandoned {
+        let Ok((_, winner_network_id, duel, _, _, _, interested, client_id)) =
+            q_duelists.get(winner)
+        else {
+            continue;
+        };
+        let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
+        recipients.extend(interested.clients.iter().copied());
+        recipients.push(client_id.0);
+        writer.write(OutgoingMessage::new(
+            recipients,
+            OutgoingMessageData::DuelEnded {
+                winner_network_id: *winner_network_id,
+                loser_network_id: duel.opponent_id,
+                reason: DuelEndReason::Forfeited,
+            },
+        ));
+        let opponent = duel.opponent;
+        end_duel(
+            &mut commands,
+            &mut reactions_writer,
+            &mut q_duelists,
+            winner,
+            opponent,
+        );
+    }
+}
+
+/// Removes the duel from both duelists, along with the auras they applied to each other.
+#[allow(clippy::type_complexity)]
+fn end_duel(
+    commands: &mut Commands,
+    reactions_writer: &mut MessageWriter<ReactionsChangedMessage>,
+    q_duelists: &mut Query<(
+        Entity,
+        &NetworkId,
+        &mut Duel,
+        &Transform,
+        Option<&mut Vitals>,
+        Option<&mut Auras>,
+        &InterestedClients,
+        &ClientIdComponent,
+    )>,
+    a: Entity,
+    b: Entity,
+) {
+    for (entity, opponent) in [(a, b), (b, a)] {
+        let Ok((.., auras, _, _)) = q_duelists.get_mut(entity) else {
+            continue;
+        };
+        if let Some(mut auras) = auras {
+            auras.expire_from(opponent);
+        }
+        commands.entity(entity).remove::<Duel>();
+        reactions_writer.write(ReactionsChangedMessage { entity });
+    }
+}
+
+/// Flags players for world PvP. Turning the flag off starts its timeout instead of dropping it.
+pub fn process_pvp_flags(
+    mut commands: Commands,
+    mut reader: MessageReader<SetPvpFlagMessage>,
+    mut q_players: Query<(&ClientIdComponent, Option<&mut PvpFlag>)>,
+    mut writer: MessageWriter<OutgoingMessage>,
+    mut reactions_writer: MessageWriter<ReactionsChangedMessage>,
+) {
+    for msg in reader.read() {
+        let Ok((client_id, flag)) = q_players.get_mut(msg.player_entity) else {
+            continue;
+        };
+
+        let expires_in = match (flag, msg.enabled) {
+            (None, true) => {
+                commands
+                    .entity(msg.player_entity)
+                    .insert(PvpFlag::default());
+                reactions_writer.write(ReactionsChangedMessage {
+                    entity: msg.player_entity,
+                });
+                None
+            }
+            (Some(mut flag), true) => {
+                flag.timeout = None;
+                None
+            }
+            (Some(mut flag), false) => {
+                let timeout = flag.timeout.get_or_insert_with(|| {
+                    Timer::from_seconds(PVP_FLAG_TIMEOUT_SECS, TimerMode::Once)
+                });
+                Some(timeout.remaining_secs())
+            }
+            (None, false) => continue,
+        };
+
+        writer.write(OutgoingMessage::new(
+            vec![client_id.0],
+            OutgoingMessageData::PvpFlagChanged {
+                flagged: true,
+                expires_in,
+            },
+        ));
+    }
+}
+
+/// Drops PvP flags whose timeout ran out. Fighting another flagged player restarts it.
+pub fn tick_pvp_flags(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut damage_reader: MessageReader<DamageDealtMessage>,
+    mut q_flagged: Query<(Entity, &ClientIdComponent, &mut PvpFlag)>,
+    mut writer: MessageWriter<OutgoingMessage>,
+    mut reactions_writer: MessageWriter<ReactionsChangedMessage>,
+) {
+    for msg in damage_reader.read() {
+        let Ok(flags) = q_flagged.get_many_mut([msg.attacker_entity, msg.target_entity]) else {
+            continue;
+        };
+        for (_, _, mut flag) in flags {
+            if let Some(timeout) = flag.timeout.as_mut() {
+                timeout.reset();
+            }
+        }
+    }
+
+    for (entity, client_id, mut flag) in q_flagged.iter_mut() {
+        let Some(timeout) = flag.timeout.as_mut() else {
+            continue;
+        };
+        if timeout.tick(time.delta()).is_finished() {
+            commands.entity(entity).remove::<PvpFlag>();
+            reactions_writer.write(ReactionsChangedMessage { entity });
+            writer.write(OutgoingMessage::new(
+                vec![client_id.0],
+                OutgoingMessageData::PvpFlagChanged {
+                    flagged: false,
+                    expires_in: None,
+                },
+            ));
+        }
+    }
+}
+
+/// Sends players around a changed player their new reactions towards each other, and stops
+/// auto-attacks between players that can no longer attack each other.
+pub fn replicate_reactions(
+    mut commands: Commands,
+    mut reader: MessageReader<ReactionsChangedMessage>,
+    q_players: Query<(
+        Entity,
+        &NetworkId,
+        &ClientIdComponent,
+        &InterestedClients,
+        Option<&AutoAttack>,
+    )>,
+    reactions: Reactions,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    for msg in reader.read() {
+        let Ok((entity, network_id, client_id, interested, auto_attack)) =
+            q_players.get(msg.entity)
+        else {
+            continue;
+        };
+
+        for (other, other_network_id, other_client_id, other_interested, other_auto_attack) in
+            q_players.iter()
+        {
+            if other == entity {
+                continue;
+            }
+
+            if interested.clients.contains(&other_client_id.0) {
+                writer.write(OutgoingMessage::new(
+                    vec![other_client_id.0],
+                    OutgoingMessageData::ReactionChanged {
+                        network_id: *network_id,
+                        reaction: reactions.between(other, entity),
+                    },
+                ));
+            }
+            if other_interested.clients.contains(&client_id.0) {
+                writer.write(OutgoingMessage::new(
+                    vec![client_id.0],
+                    OutgoingMessageData::ReactionChanged {
+                        network_id: *other_network_id,
+                        reaction: reactions.between(entity, other),
+                    },
+                ));
+            }
+
+            if reactions.between(entity, other).can_attack() {
+                continue;
+            }
+            if auto_attack.is_some_and(|attack| attack.target == other) {
+                commands.entity(entity).remove::<(AutoAttack, NextSwing)>();
+            }
+            if other_auto_attack.is_some_and(|attack| attack.target == entity) {
+                commands.entity(other).remove::<(AutoAttack, NextSwing)>();
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{
+    combat::{
+        messages::{ApplySpellEffectMessage, ReleaseSpiritMessage},
+        vitals::revive,
+    },
+    core::{ClientIdComponent, Dead, InterestedClients},
+    networking::{OutgoingMessage, OutgoingMessageData},
+};
+use bevy::prelude::*;
+use game_core::{
+    components::Vitals,
+    constants::ACTOR_HALF_HEIGHT,
+    networking::NetworkId,
+    spells::{SpellEffect, SpellLibrary, SpellLibraryHandle},
+    stats::Stats,
+    zone::{ZoneDef, ZoneDefHandle},
+};
+
+/// Share of maximum health a player respawns with after releasing their spirit
+const RELEASE_HEALTH_FRACTION: f32 = 0.5;
+
+/// Respawns dead players that released their spirit at the nearest graveyard.
+#[allow(clippy::type_complexity)]
+pub fn process_release_spirit(
+    mut commands: Commands,
+    mut reader: MessageReader<ReleaseSpiritMessage>,
+    zone_handle: Option<Res<ZoneDefHandle>>,
+    zone_assets: Res<Assets<ZoneDef>>,
+    mut q_dead: Query<
+        (
+            &NetworkId,
+            &mut Transform,
+            &Stats,
+            &InterestedClients,
+            &ClientIdComponent,
+        ),
+        With<Dead>,
+    >,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    let zone = zone_handle.and_then(|handle| zone_assets.get(&handle.0));
+
+    for msg in reader.read() {
+        let Ok((network_id, mut transform, stats, interested, client_id)) =
+            q_dead.get_mut(msg.player_entity)
+        else {
+            tracing::debug!(entity = ?msg.player_entity, "player tried to release spirit while alive");
+            continue;
+        };
+
+        let Some(zone) = zone else {
+            tracing::warn!("zone is not loaded, unable to find a graveyard");
+            continue;
+        };
+
+        let graveyard = zone.nearest_graveyard(transform.translation);
+        transform.translation = graveyard + Vec3::Y * ACTOR_HALF_HEIGHT;
+
+        let vitals = revived_vitals(stats, RELEASE_HEALTH_FRACTION);
+        revive(&mut commands.entity(msg.player_entity), vitals.clone());
+        tracing::debug!(entity = ?msg.player_entity, ?graveyard, "player released spirit");
+
+        write_revived(&mut writer, *network_id, vitals, interested, client_id);
+    }
+}
+
+/// Brings dead players back to life in place when a resurrection spell lands on them.
+pub fn apply_resurrections(
+    mut commands: Commands,
+    library_handle: Res<SpellLibraryHandle>,
+    assets: Res<Assets<SpellLibrary>>,
+    mut reader: MessageReader<ApplySpellEffectMessage>,
+    q_dead: Query<(&NetworkId, &Stats, &InterestedClients, &ClientIdComponent), With<Dead>>,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    let Some(library) = assets.get(&library_handle.0) else {
+        return;
+    };
+
+    for msg in reader.read() {
+        let Some(health_fraction) = library.spells.get(&msg.spell_id).and_then(|spell| {
+            spell.effects.iter().find_map(|effect| match *effect {
+                SpellEffect::Resurrect { health_fraction } => Some(health_fraction),
+                _ => None,
+            })
+        }) else {
+            continue;
+        };
+
+        // The target may have released or been resurrected by someone else during the cast
+        let Ok((network_id, stats, interested, client_id)) = q_dead.get(msg.target_entity) else {
+            tracing::debug!(entity = ?msg.target_entity, "resurrection target is no longer dead");
+            continue;
+        };
+
+        let vitals = revived_vitals(stats, health_fraction);
+        revive(&mut commands.entity(msg.target_entity), vitals.clone());
+        tracing::debug!(
+            target = ?msg.target_entity,
+            caster = ?msg.caster_entity,
+            spell_id = %msg.spell_id,
+            "player resurrected"
+        );
+
+        write_revived(&mut writer, *network_id, vitals, interested, client_id);
+    }
+}
+
+fn revived_vitals(stats: &Stats, health_fraction: f32) -> Vitals {
+    let max_hp = stats.max_hp();
+    Vitals {
+        hp: ((max_hp as f32 * health_fraction).round() as i32).clamp(1, max_hp),
+        max_hp,
+    }
+}
+
+fn write_revived(
+    writer: &mut MessageWriter<OutgoingMessage>,
+    network_id: NetworkId,
+    vitals: Vitals,
+    interested: &InterestedClients,
+    client_id: &ClientIdComponent,
+) {
+    let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
+    recipients.extend(interested.clients.iter().copied());
+    recipients.push(client_id.0);
+
+    writer.write(OutgoingMessage::new(
+        recipients,
+        OutgoingMessageData::Revived { network_id, vitals },
+    ));
+}
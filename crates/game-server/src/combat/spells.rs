@@ -1,22 +1,83 @@
 use crate::{
-    combat::messages::{ApplySpellEffectMessage, CastSpellActionMessage},
-    core::{ClientIdComponent, InterestedClients, Tapped},
+    ai::{Pet, SummonPetMessage},
+    assets::Reactions,
+    combat::{
+        auras::Auras,
+        auto_attack::NextSwing,
+        combat_log::CombatLogMessage,
+        crowd_control::DiminishingReturns,
+        damage::{DamageImmunities, mitigate_damage},
+        hit_table::{AttackKind, CombatRng, resolve_attack},
+        line_of_sight::has_line_of_sight,
+        messages::{
+            ApplyAreaSpellMessage, ApplySpellEffectMessage, CastSpellActionMessage, CastTarget,
+            DamageDealtMessage, HealingDoneMessage, LaunchProjectileMessage,
+        },
+        power::PowerRegen,
+    },
+    core::{ClientIdComponent, Dead, InterestedClients, Tapped},
     networking::{OutgoingMessage, OutgoingMessageData},
     telemetry::SPELL_CASTS_TOTAL_METRIC,
 };
+use avian3d::prelude::{SpatialQuery, SpatialQueryFilter};
 use bevy::prelude::*;
 use game_core::{
-    components::Vitals,
+    auras::{AuraKind, AuraLibrary, AuraLibraryHandle},
+    collision::GameLayer,
+    components::{LevelComponent, Vitals},
+    constants::ACTOR_HALF_HEIGHT,
+    crowd_control::CrowdControlState,
     networking::NetworkId,
-    spells::{SpellLibrary, SpellLibraryHandle},
+    power::Power,
+    spells::{GLOBAL_COOLDOWN_SECS, SpellEffect, SpellLibrary, SpellLibraryHandle, SpellTarget},
+    stats::Stats,
 };
+use protocol::{
+    models::{CastFailedReason, CombatLogEvent, HitResult, Impact, Reaction},
+    server::{AbilityCooldown, GLOBAL_COOLDOWN_SPELL_ID},
+};
+use std::{collections::HashMap, time::Instant};
+
+/// Distance kept between a displaced actor and the obstacle that stopped it.
+const DISPLACEMENT_WALL_MARGIN: f32 = 0.5;
+/// Casts requested this close to the end of the current cast are queued instead of rejected
+const SPELL_QUEUE_WINDOW_SECS: f32 = 0.4;
+/// Delay added to a cast by each damaging hit
+const CAST_PUSHBACK_SECS: f32 = 0.5;
+/// Share of a channel's full duration lost to each damaging hit
+const CHANNEL_PUSHBACK_FRACTION: f32 = 0.25;
+/// Hits beyond this many no longer push a cast or channel back
+const MAX_PUSHBACKS: u32 = 2;
 
 #[derive(Component)]
 pub struct Casting {
     pub spell_id: u32,
-    pub target: Entity,
+    pub target: CastTarget,
+    /// Cast time, or the whole channel for channelled spells
     pub timer: Timer,
     pub castable_while_moving: bool,
+    /// Interval between the effects of a channelled spell
+    pub channel: Option<Timer>,
+    pub pushbacks: u32,
+    /// Power spent when the cast started, given back if it is interrupted
+    pub cost: i32,
+}
+
+impl Casting {
+    /// Power to give back when the cast is interrupted. Channels keep it once they ticked.
+    pub fn refund(&self) -> i32 {
+        match &self.channel {
+            Some(channel) if self.timer.elapsed() >= channel.duration() => 0,
+            _ => self.cost,
+        }
+    }
+}
+
+/// Cast requested within the spell queue window, started once the current cast ends.
+#[derive(Component, Debug)]
+pub struct QueuedCast {
+    pub spell_id: u32,
+    pub target: CastTarget,
 }
 
 #[derive(Debug, Clone)]
@@ -25,9 +86,10 @@ pub struct Ability {
     pub cooldown: Timer,
 }
 
-#[derive(Component, Debug, Clone, Default)]
+#[derive(Component, Debug, Clone)]
 pub struct Abilities {
     pub known: Vec<Ability>,
+    pub global_cooldown: Timer,
 }
 
 impl Abilities {
@@ -36,23 +98,89 @@ impl Abilities {
             .iter()
             .map(|&spell_id| {
                 let cooldown_secs = spell_cooldowns.get(&spell_id).copied().unwrap_or(1.0);
-                let mut timer = Timer::from_seconds(cooldown_secs, TimerMode::Once);
-                timer.tick(std::time::Duration::from_secs_f32(cooldown_secs));
                 Ability {
                     spell_id,
-                    cooldown: timer,
+                    cooldown: finished_timer(cooldown_secs),
                 }
             })
             .collect();
-        Self { known }
+        Self {
+            known,
+            global_cooldown: finished_timer(GLOBAL_COOLDOWN_SECS),
+        }
+    }
+
+    /// Resumes cooldowns saved from a previous session.
+    pub fn restore_cooldowns(&mut self, cooldowns: &[AbilityCooldown]) {
+        for saved in cooldowns {
+            if let Some(ability) = self.known.iter_mut().find(|a| a.spell_id == saved.spell_id) {
+                let duration = ability.cooldown.duration().as_secs_f32();
+                let elapsed = (duration - saved.remaining).clamp(0.0, duration);
+                ability
+                    .cooldown
+                    .set_elapsed(std::time::Duration::from_secs_f32(elapsed));
+            }
+        }
+    }
+
+    /// Cooldowns that are still running, used to persist them across reconnects.
+    pub fn running_cooldowns(&self) -> Vec<AbilityCooldown> {
+        self.known
+            .iter()
+            .filter(|a| !a.cooldown.is_finished())
+            .map(|a| AbilityCooldown {
+                spell_id: a.spell_id,
+                remaining: a.cooldown.remaining_secs(),
+                duration: a.cooldown.duration().as_secs_f32(),
+            })
+            .collect()
+    }
+}
+
+fn finished_timer(secs: f32) -> Timer {
+    let mut timer = Timer::from_seconds(secs, TimerMode::Once);
+    timer.tick(std::time::Duration::from_secs_f32(secs));
+    timer
+}
+
+/// Ability cooldowns of characters that logged out, keyed by character ID.
+/// Kept in memory only, so they reset when the server restarts.
+#[derive(Resource, Default)]
+pub struct SavedCooldowns(pub HashMap<i32, (Instant, Vec<AbilityCooldown>)>);
+
+impl SavedCooldowns {
+    pub fn save(&mut self, character_id: i32, cooldowns: Vec<AbilityCooldown>) {
+        if cooldowns.is_empty() {
+            self.0.remove(&character_id);
+        } else {
+            self.0.insert(character_id, (Instant::now(), cooldowns));
+        }
+    }
+
+    /// Takes the saved cooldowns of a character, minus the time spent offline.
+    pub fn take(&mut self, character_id: i32) -> Vec<AbilityCooldown> {
+        let Some((saved_at, cooldowns)) = self.0.remove(&character_id) else {
+            return Vec::new();
+        };
+
+        let offline = saved_at.elapsed().as_secs_f32();
+        cooldowns
+            .into_iter()
+            .filter(|c| c.remaining > offline)
+            .map(|c| AbilityCooldown {
+                remaining: c.remaining - offline,
+                ..c
+            })
+            .collect()
     }
 }
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn process_spell_casts(
     mut commands: Commands,
     mut reader: MessageReader<CastSpellActionMessage>,
     mut writer: MessageWriter<OutgoingMessage>,
+    mut combat_log: MessageWriter<CombatLogMessage>,
     mut q_caster: Query<(
         &NetworkId,
         Option<&ClientIdComponent>,
@@ -60,10 +188,20 @@ pub fn process_spell_casts(
         &InterestedClients,
         Option<&Casting>,
         &mut Abilities,
+        Option<&Auras>,
+        Option<&Stats>,
+        Has<Dead>,
+        Option<&mut Power>,
+        Option<&mut PowerRegen>,
+        Option<&CrowdControlState>,
     )>,
-    q_target: Query<&Transform>,
+    q_target: Query<(&Transform, Has<ClientIdComponent>, Has<Dead>)>,
+    reactions: Reactions,
     library_handle: Res<SpellLibraryHandle>,
     assets: Res<Assets<SpellLibrary>>,
+    aura_library_handle: Res<AuraLibraryHandle>,
+    aura_assets: Res<Assets<AuraLibrary>>,
+    spatial_query: SpatialQuery,
 ) {
     let Some(library) = assets.get(&library_handle.0) else {
         tracing::info!("still waiting for spells library to load");
@@ -78,6 +216,12 @@ pub fn process_spell_casts(
             interested,
             casting,
             mut abilities,
+            caster_auras,
+            caster_stats,
+            caster_is_dead,
+            mut caster_power,
+            caster_power_regen,
+            caster_crowd_control,
         )) = q_caster.get_mut(msg.caster_entity)
         else {
             tracing::warn!(
@@ -87,73 +231,320 @@ pub fn process_spell_casts(
             continue;
         };
 
+        if caster_is_dead {
+            tracing::debug!(caster = ?msg.caster_entity, "dead caster tried to cast");
+            reject_cast(
+                &mut writer,
+                caster_client_id,
+                msg.spell_id,
+                CastFailedReason::Dead,
+            );
+            continue;
+        }
+
+        if let Some(state) = caster_crowd_control
+            && !state.can_cast()
+        {
+            tracing::debug!(caster = ?msg.caster_entity, ?state, "crowd controlled caster tried to cast");
+            let reason = if state.stunned {
+                CastFailedReason::Stunned
+            } else if state.feared {
+                CastFailedReason::Feared
+            } else {
+                CastFailedReason::Silenced
+            };
+            reject_cast(&mut writer, caster_client_id, msg.spell_id, reason);
+            continue;
+        }
+
+        // Casts sent just before the current one ends start on the next tick instead
+        if let Some(casting) = casting
+            && casting.timer.remaining_secs() <= SPELL_QUEUE_WINDOW_SECS
+        {
+            tracing::debug!(caster = ?msg.caster_entity, spell_id = %msg.spell_id, "queued cast");
+            commands.entity(msg.caster_entity).insert(QueuedCast {
+                spell_id: msg.spell_id,
+                target: msg.target,
+            });
+            continue;
+        }
+
         if casting.is_some() {
             tracing::debug!(caster = ?msg.caster_entity, "caster tried to cast while already casting");
-            metrics::counter!(SPELL_CASTS_TOTAL_METRIC, "result" => "rejected").increment(1);
+            reject_cast(
+                &mut writer,
+                caster_client_id,
+                msg.spell_id,
+                CastFailedReason::AlreadyCasting,
+            );
             continue;
         }
 
         let Some(ability) = abilities.known.iter().find(|a| a.spell_id == msg.spell_id) else {
             tracing::debug!(caster = ?msg.caster_entity, spell_id = %msg.spell_id, "caster does not know this spell");
-            metrics::counter!(SPELL_CASTS_TOTAL_METRIC, "result" => "rejected").increment(1);
+            reject_cast(
+                &mut writer,
+                caster_client_id,
+                msg.spell_id,
+                CastFailedReason::UnknownSpell,
+            );
             continue;
         };
 
         if !ability.cooldown.is_finished() {
             tracing::debug!(caster = ?msg.caster_entity, spell_id = %msg.spell_id, "spell is on cooldown");
-            metrics::counter!(SPELL_CASTS_TOTAL_METRIC, "result" => "rejected").increment(1);
+            reject_cast(
+                &mut writer,
+                caster_client_id,
+                msg.spell_id,
+                CastFailedReason::OnCooldown,
+            );
             continue;
         }
 
-        let Ok(target_transform) = q_target.get(msg.target_entity) else {
-            tracing::debug!(caster = ?msg.caster_entity, target = ?msg.target_entity, "caster selected invalid target");
-            metrics::counter!(SPELL_CASTS_TOTAL_METRIC, "result" => "rejected").increment(1);
+        let Some(spell) = library.spells.get(&msg.spell_id) else {
+            tracing::debug!(caster = ?msg.caster_entity, spell_id = %msg.spell_id, "caster used invalid spell ID");
+            reject_cast(
+                &mut writer,
+                caster_client_id,
+                msg.spell_id,
+                CastFailedReason::UnknownSpell,
+            );
             continue;
         };
 
-        let Some(spell) = library.spells.get(&msg.spell_id) else {
-            tracing::debug!(caster = ?msg.caster_entity, spell_id = %msg.spell_id, "caster used invalid spell ID");
-            metrics::counter!(SPELL_CASTS_TOTAL_METRIC, "result" => "rejected").increment(1);
+        if spell.triggers_gcd && !abilities.global_cooldown.is_finished() {
+            tracing::debug!(caster = ?msg.caster_entity, spell_id = %msg.spell_id, "global cooldown is active");
+            reject_cast(
+                &mut writer,
+                caster_client_id,
+                msg.spell_id,
+                CastFailedReason::OnCooldown,
+            );
             continue;
+        }
+
+        if let Some(power) = caster_power.as_ref()
+            && power.current < spell.cost
+        {
+            tracing::debug!(caster = ?msg.caster_entity, spell_id = %msg.spell_id, "not enough power");
+            reject_cast(
+                &mut writer,
+                caster_client_id,
+                msg.spell_id,
+                CastFailedReason::NotEnoughPower,
+            );
+            continue;
+        }
+
+        // Caster-centred areas ignore the selected target
+        let caster_centred = spell
+            .area
+            .as_ref()
+            .is_some_and(|area| area.shape.is_caster_centred());
+        let target = if spell.target == SpellTarget::Caster || caster_centred {
+            CastTarget::Entity(msg.caster_entity)
+        } else {
+            msg.target
+        };
+
+        let target_position = match target {
+            CastTarget::Entity(target_entity) => {
+                let Ok((target_transform, target_is_player, target_is_dead)) =
+                    q_target.get(target_entity)
+                else {
+                    tracing::debug!(caster = ?msg.caster_entity, target = ?target_entity, "caster selected invalid target");
+                    reject_cast(
+                        &mut writer,
+                        caster_client_id,
+                        msg.spell_id,
+                        CastFailedReason::InvalidTarget,
+                    );
+                    continue;
+                };
+
+                // Resurrections need a dead friendly player, everything else a living target
+                let reaction = reactions.between(msg.caster_entity, target_entity);
+                let target_allowed = match spell.target {
+                    _ if spell.resurrects() => {
+                        target_is_dead && target_is_player && reaction == Reaction::Friendly
+                    }
+                    _ if target_is_dead => false,
+                    SpellTarget::Caster => true,
+                    _ if caster_centred => true,
+                    SpellTarget::Friendly => reaction == Reaction::Friendly,
+                    SpellTarget::Hostile => {
+                        target_entity != msg.caster_entity && reaction.can_attack()
+                    }
+                };
+                if !target_allowed {
+                    tracing::debug!(caster = ?msg.caster_entity, target = ?target_entity, spell_id = %msg.spell_id, "target not allowed for spell");
+                    reject_cast(
+                        &mut writer,
+                        caster_client_id,
+                        msg.spell_id,
+                        CastFailedReason::InvalidTarget,
+                    );
+                    continue;
+                }
+
+                target_transform.translation
+            }
+            CastTarget::Position(position) => {
+                if spell.area.is_none() {
+                    tracing::debug!(caster = ?msg.caster_entity, spell_id = %msg.spell_id, "spell cannot be cast at a position");
+                    reject_cast(
+                        &mut writer,
+                        caster_client_id,
+                        msg.spell_id,
+                        CastFailedReason::InvalidTarget,
+                    );
+                    continue;
+                }
+                position
+            }
         };
 
         if caster_transform
             .translation
-            .distance_squared(target_transform.translation)
+            .distance_squared(target_position)
             > spell.range * spell.range
         {
             tracing::debug!(caster = ?msg.caster_entity, ?spell, "target is out of range");
-            metrics::counter!(SPELL_CASTS_TOTAL_METRIC, "result" => "rejected").increment(1);
+            reject_cast(
+                &mut writer,
+                caster_client_id,
+                msg.spell_id,
+                CastFailedReason::OutOfRange,
+            );
             continue;
         }
 
-        commands.entity(msg.caster_entity).insert(Casting {
-            spell_id: msg.spell_id,
-            target: msg.target_entity,
-            timer: Timer::from_seconds(spell.casting_duration, TimerMode::Once),
-            castable_while_moving: spell.castable_while_moving,
-        });
+        // Ground positions are raised so the ray doesn't end inside the terrain
+        let sight_target = match target {
+            CastTarget::Entity(target_entity) if target_entity == msg.caster_entity => None,
+            CastTarget::Entity(_) => Some(target_position),
+            CastTarget::Position(position) => Some(position + Vec3::Y * ACTOR_HALF_HEIGHT),
+        };
+        if let Some(sight_target) = sight_target
+            && !has_line_of_sight(&spatial_query, caster_transform.translation, sight_target)
+        {
+            tracing::debug!(caster = ?msg.caster_entity, spell_id = %msg.spell_id, "target is not in line of sight");
+            reject_cast(
+                &mut writer,
+                caster_client_id,
+                msg.spell_id,
+                CastFailedReason::LineOfSight,
+            );
+            continue;
+        }
+
+        let aura_cast_speed = match (caster_auras, aura_assets.get(&aura_library_handle.0)) {
+            (Some(auras), Some(aura_library)) => auras.cast_speed_multiplier(aura_library),
+            _ => 1.0,
+        };
+        let cast_speed = aura_cast_speed * caster_stats.map_or(1.0, Stats::cast_speed_multiplier);
+        let casting_duration = match spell.channel {
+            Some(channel) => channel.duration,
+            None => spell.casting_duration / cast_speed.max(f32::EPSILON),
+        };
+
+        // Power is spent up front like cooldowns and refunded on interrupts, spending mana
+        // pauses its regeneration
+        let mut cost = 0;
+        if let Some(power) = caster_power.as_mut()
+            && spell.cost > 0
+        {
+            power.spend(spell.cost);
+            cost = spell.cost;
+            if let Some(mut regen) = caster_power_regen {
+                regen.interrupt(power.kind);
+            }
+        }
+
+        // Swing abilities wait for the next auto-attack instead of being cast
+        if spell.on_next_swing {
+            commands.entity(msg.caster_entity).insert(NextSwing {
+                spell_id: msg.spell_id,
+            });
+        } else {
+            commands.entity(msg.caster_entity).insert(Casting {
+                spell_id: msg.spell_id,
+                target,
+                timer: Timer::from_seconds(casting_duration, TimerMode::Once),
+                castable_while_moving: spell.castable_while_moving && spell.channel.is_none(),
+                channel: spell
+                    .channel
+                    .map(|channel| Timer::from_seconds(channel.interval, TimerMode::Repeating)),
+                pushbacks: 0,
+                cost,
+            });
+        }
 
         metrics::counter!(SPELL_CASTS_TOTAL_METRIC, "result" => "success").increment(1);
         tracing::debug!(
             caster = ?msg.caster_entity,
             spell_id = %msg.spell_id,
-            target = ?msg.target_entity,
+            ?target,
             "spell cast started"
         );
 
-        // Reset the ability cooldown
+        // Reset the ability cooldown and the GCD, the caster's client mirrors both
         if let Some(ability) = abilities
             .known
             .iter_mut()
             .find(|a| a.spell_id == msg.spell_id)
         {
             ability.cooldown.reset();
+
+            let duration = ability.cooldown.duration().as_secs_f32();
+            if let Some(client_id) = caster_client_id
+                && duration > 0.0
+            {
+                writer.write(OutgoingMessage::new(
+                    vec![client_id.0],
+                    OutgoingMessageData::CooldownStarted {
+                        spell_id: msg.spell_id,
+                        duration,
+                    },
+                ));
+            }
+        }
+
+        if spell.triggers_gcd {
+            abilities.global_cooldown.reset();
+
+            if let Some(client_id) = caster_client_id {
+                writer.write(OutgoingMessage::new(
+                    vec![client_id.0],
+                    OutgoingMessageData::CooldownStarted {
+                        spell_id: GLOBAL_COOLDOWN_SPELL_ID,
+                        duration: GLOBAL_COOLDOWN_SECS,
+                    },
+                ));
+            }
+        }
+
+        if spell.on_next_swing {
+            continue;
         }
 
+        // Ground-targeted casts are logged against the caster
+        combat_log.write(CombatLogMessage {
+            source: Some(msg.caster_entity),
+            target: match target {
+                CastTarget::Entity(target_entity) => target_entity,
+                CastTarget::Position(_) => msg.caster_entity,
+            },
+            event: CombatLogEvent::CastStarted {
+                spell_id: msg.spell_id,
+            },
+        });
+
         let outgoing_msg = OutgoingMessageData::StartCasting {
             network_id: *caster_network_id,
             spell_id: msg.spell_id,
+            duration: casting_duration,
         };
 
         let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
@@ -169,6 +560,23 @@ pub fn process_spell_casts(
     }
 }
 
+/// Counts a rejected cast and tells the caster's client why it failed.
+fn reject_cast(
+    writer: &mut MessageWriter<OutgoingMessage>,
+    caster_client_id: Option<&ClientIdComponent>,
+    spell_id: u32,
+    reason: CastFailedReason,
+) {
+    metrics::counter!(SPELL_CASTS_TOTAL_METRIC, "result" => "rejected").increment(1);
+    if let Some(client_id) = caster_client_id {
+        writer.write(OutgoingMessage::new(
+            vec![client_id.0],
+            OutgoingMessageData::CastFailed { spell_id, reason },
+        ));
+    }
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn tick_casting(
     mut commands: Commands,
     time: Res<Time>,
@@ -177,98 +585,599 @@ pub fn tick_casting(
         &mut Casting,
         Ref<Transform>,
         Option<&ClientIdComponent>,
+        Option<&CrowdControlState>,
+        Option<&Pet>,
+        Option<&mut Power>,
     )>,
+    library_handle: Res<SpellLibraryHandle>,
+    assets: Res<Assets<SpellLibrary>>,
     mut writer: MessageWriter<ApplySpellEffectMessage>,
+    mut area_writer: MessageWriter<ApplyAreaSpellMessage>,
+    mut projectile_writer: MessageWriter<LaunchProjectileMessage>,
 ) {
-    for (entity, mut cast, transform, client_id) in q_casting.iter_mut() {
+    let Some(library) = assets.get(&library_handle.0) else {
+        return;
+    };
+
+    for (entity, mut cast, transform, client_id, crowd_control, pet, power) in q_casting.iter_mut()
+    {
+        // Stuns, silences and fears interrupt the cast, clients drop the cast bar on their own
+        if crowd_control.is_some_and(|state| !state.can_cast()) {
+            refund_cast(&cast, power);
+            commands.entity(entity).remove::<(Casting, QueuedCast)>();
+            tracing::debug!(?entity, "cast interrupted by crowd control");
+            continue;
+        }
+
         // Cancel non-movable casts if the caster's Transform changed this tick
         if transform.is_changed() && !cast.castable_while_moving {
-            commands.entity(entity).remove::<Casting>();
+            refund_cast(&cast, power);
+            commands.entity(entity).remove::<(Casting, QueuedCast)>();
             tracing::debug!(?entity, "caster moved while casting stationary spell");
             continue;
         }
 
         cast.timer.tick(time.delta());
+
+        // Channels apply their effects on every interval, casts once they complete
+        let releases = match cast.channel.as_mut() {
+            Some(channel) => channel.tick(time.delta()).times_finished_this_tick(),
+            None => u32::from(cast.timer.is_finished()),
+        };
+
+        // Pets tap for their owner
+        let caster_client_id = client_id
+            .map(|c| c.0)
+            .or(pet.and_then(|pet| pet.owner_client_id));
+        let spell = library.spells.get(&cast.spell_id);
+        let is_area = spell.is_some_and(|spell| spell.area.is_some());
+        let has_projectile = spell.is_some_and(|spell| spell.projectile_speed.is_some());
+
+        for _ in 0..releases {
+            match cast.target {
+                _ if is_area => {
+                    area_writer.write(ApplyAreaSpellMessage {
+                        caster_entity: entity,
+                        caster_client_id,
+                        target: cast.target,
+                        spell_id: cast.spell_id,
+                    });
+                }
+                CastTarget::Entity(target_entity) if has_projectile => {
+                    projectile_writer.write(LaunchProjectileMessage {
+                        caster_entity: entity,
+                        caster_client_id,
+                        target_entity,
+                        spell_id: cast.spell_id,
+                    });
+                }
+                CastTarget::Entity(target_entity) => {
+                    writer.write(ApplySpellEffectMessage {
+                        caster_entity: entity,
+                        caster_client_id,
+                        target_entity,
+                        spell_id: cast.spell_id,
+                    });
+                }
+                CastTarget::Position(_) => {
+                    tracing::warn!(?entity, spell_id = %cast.spell_id, "single target spell was cast at a position");
+                }
+            }
+        }
+
         if cast.timer.is_finished() {
-            writer.write(ApplySpellEffectMessage {
-                caster_entity: entity,
-                caster_client_id: client_id.map(|c| c.0),
-                target_entity: cast.target,
-                spell_id: cast.spell_id,
-            });
             commands.entity(entity).remove::<Casting>();
         }
     }
 }
 
+/// Gives the power spent on an interrupted cast back to the caster.
+fn refund_cast(cast: &Casting, power: Option<Mut<Power>>) {
+    let refund = cast.refund();
+    if let Some(mut power) = power
+        && refund > 0
+    {
+        power.gain(refund);
+    }
+}
+
+/// Damaging hits delay casts and shorten channels, up to `MAX_PUSHBACKS` times per cast.
+pub fn apply_cast_pushback(
+    mut reader: MessageReader<DamageDealtMessage>,
+    mut q_casting: Query<(
+        &mut Casting,
+        &NetworkId,
+        &InterestedClients,
+        Option<&ClientIdComponent>,
+    )>,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    for msg in reader.read() {
+        if msg.amount <= 0 || msg.attacker_entity == msg.target_entity {
+            continue;
+        }
+        let Ok((mut cast, network_id, interested, client_id)) =
+            q_casting.get_mut(msg.target_entity)
+        else {
+            continue;
+        };
+        if cast.pushbacks >= MAX_PUSHBACKS || cast.timer.is_finished() {
+            continue;
+        }
+        cast.pushbacks += 1;
+
+        let elapsed = cast.timer.elapsed_secs();
+        let pushed_back = if cast.channel.is_some() {
+            let duration = cast.timer.duration().as_secs_f32();
+            (elapsed + duration * CHANNEL_PUSHBACK_FRACTION).min(duration)
+        } else {
+            (elapsed - CAST_PUSHBACK_SECS).max(0.0)
+        };
+        cast.timer
+            .set_elapsed(std::time::Duration::from_secs_f32(pushed_back));
+
+        let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
+        recipients.extend(interested.clients.iter().copied());
+        if let Some(client_id) = client_id {
+            recipients.push(client_id.0);
+        }
+        writer.write(OutgoingMessage::new(
+            recipients,
+            OutgoingMessageData::CastPushback {
+                network_id: *network_id,
+                remaining: cast.timer.remaining_secs(),
+            },
+        ));
+    }
+}
+
+/// Starts casts queued during the previous cast, once it has finished.
+pub fn release_queued_casts(
+    mut commands: Commands,
+    q_queued: Query<(Entity, &QueuedCast), Without<Casting>>,
+    mut writer: MessageWriter<CastSpellActionMessage>,
+) {
+    for (entity, queued) in q_queued.iter() {
+        writer.write(CastSpellActionMessage {
+            caster_entity: entity,
+            target: queued.target,
+            spell_id: queued.spell_id,
+        });
+        commands.entity(entity).remove::<QueuedCast>();
+    }
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn apply_spell_effect(
     mut commands: Commands,
     library_handle: Res<SpellLibraryHandle>,
     assets: Res<Assets<SpellLibrary>>,
+    aura_library_handle: Res<AuraLibraryHandle>,
+    aura_assets: Res<Assets<AuraLibrary>>,
+    spatial_query: SpatialQuery,
     mut reader: MessageReader<ApplySpellEffectMessage>,
     mut q_target: Query<(
         &NetworkId,
         &mut Vitals,
+        &mut Transform,
+        &mut Auras,
         &InterestedClients,
         Option<&ClientIdComponent>,
         Option<&Tapped>,
+        Option<&mut DiminishingReturns>,
+        Option<&DamageImmunities>,
     )>,
+    q_attributes: Query<(&LevelComponent, Option<&Stats>)>,
+    reactions: Reactions,
+    mut rng: ResMut<CombatRng>,
     mut writer: MessageWriter<OutgoingMessage>,
+    mut damage_writer: MessageWriter<DamageDealtMessage>,
+    mut healing_writer: MessageWriter<HealingDoneMessage>,
+    mut pet_writer: MessageWriter<SummonPetMessage>,
+    mut combat_log: MessageWriter<CombatLogMessage>,
 ) {
     let Some(library) = assets.get(&library_handle.0) else {
         return;
     };
+    let aura_library = aura_assets.get(&aura_library_handle.0);
 
     for msg in reader.read() {
         let Some(spell) = library.spells.get(&msg.spell_id) else {
             tracing::warn!(spell_id = %msg.spell_id, "tried to apply invalid spell");
             continue;
         };
+        // Dead targets have no vitals, resurrections are applied separately
+        if spell.resurrects() {
+            continue;
+        }
+
+        // Knockback pushes away from the caster, so read its position before borrowing the target
+        let caster_position = q_target
+            .get(msg.caster_entity)
+            .map(|(_, _, transform, ..)| transform.translation)
+            .ok();
 
-        let Ok((target_network_id, mut target_vitals, interested, target_client_id, tapped)) =
-            q_target.get_mut(msg.target_entity)
+        let Ok((
+            target_network_id,
+            mut target_vitals,
+            mut target_transform,
+            mut target_auras,
+            interested,
+            target_client_id,
+            tapped,
+            mut diminishing_returns,
+            immunities,
+        )) = q_target.get_mut(msg.target_entity)
         else {
             tracing::debug!(entity_id = ?msg.target_entity, "tried to apply spell to invalid entity");
             continue;
         };
 
-        let outgoing_msg = OutgoingMessageData::SpellImpact {
-            target_network_id: *target_network_id,
-            spell_id: msg.spell_id,
-            impact_amount: spell.damage,
-        };
-
+        // NOTE: the target's own client is not within its interested set
         let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
         recipients.extend(interested.clients.iter().copied());
+        if let Some(client_id) = target_client_id {
+            recipients.push(client_id.0);
+        }
 
-        if let Some(caster_client_id) = msg.caster_client_id {
-            // NOTE: caster's own ID is not within the interested set
-            if msg.caster_entity == msg.target_entity {
-                recipients.push(caster_client_id);
-            }
+        let (caster_level, caster_stats) = q_attributes
+            .get(msg.caster_entity)
+            .map(|(level, stats)| (level.0, stats.copied()))
+            .unwrap_or((1, None));
+        let (target_level, target_stats) = q_attributes
+            .get(msg.target_entity)
+            .map(|(level, stats)| (level.0, stats.copied()))
+            .unwrap_or((1, None));
+        let reaction = reactions.between(msg.caster_entity, msg.target_entity);
+        let with_spell_power =
+            |amount: i32| caster_stats.map_or(amount, |stats| stats.spell_amount(amount));
+
+        let filter = SpatialQueryFilter::from_excluded_entities([msg.target_entity])
+            .with_mask([GameLayer::Default, GameLayer::Ground]);
+
+        for effect in &spell.effects {
+            // An avoided hit stops the rest of the spell from landing
+            let mut avoided = false;
+            let impact = match *effect {
+                SpellEffect::Damage { amount, school } => {
+                    let (result, amount) = resolve_attack(
+                        &mut rng.0,
+                        AttackKind::for_school(school),
+                        caster_level,
+                        caster_stats.as_ref(),
+                        target_level,
+                        target_stats.as_ref(),
+                        with_spell_power(amount),
+                    );
+                    avoided = matches!(
+                        result,
+                        HitResult::Miss | HitResult::Dodge | HitResult::Parry
+                    );
+
+                    // TODO: Notify interested clients of tap
+                    if let Some(caster_client_id) = msg.caster_client_id
+                        && !avoided
+                        && target_client_id.is_none()
+                        && tapped.is_none()
+                    {
+                        commands.entity(msg.target_entity).insert(Tapped {
+                            owner_id: caster_client_id,
+                        });
+                    }
+
+                    let damage = mitigate_damage(
+                        amount,
+                        school,
+                        caster_level,
+                        target_stats.as_ref(),
+                        immunities,
+                        Some(&mut *target_auras),
+                        aura_library,
+                    );
+                    let dealt = damage.map_or(0, |damage| damage.amount);
+                    target_vitals.hp = target_vitals.hp.saturating_sub(dealt).max(0);
+                    damage_writer.write(DamageDealtMessage {
+                        attacker_entity: msg.caster_entity,
+                        target_entity: msg.target_entity,
+                        spell_id: msg.spell_id,
+                        amount: dealt,
+                    });
+                    damage.map_or(Impact::Immune, |damage| damage.impact(school, result))
+                }
+                SpellEffect::Heal { amount } => {
+                    let amount = with_spell_power(amount);
+                    let healed = amount.min(target_vitals.max_hp - target_vitals.hp).max(0);
+                    target_vitals.hp += healed;
+                    healing_writer.write(HealingDoneMessage {
+                        healer_entity: msg.caster_entity,
+                        target_entity: msg.target_entity,
+                        spell_id: msg.spell_id,
+                        amount: healed,
+                    });
+                    Impact::Heal { amount: healed }
+                }
+                SpellEffect::Teleport { distance } => {
+                    let Ok(direction) = Dir3::new(target_transform.forward().with_y(0.0)) else {
+                        continue;
+                    };
+                    let origin = target_transform.translation;
+                    target_transform.translation += *direction
+                        * clamp_displacement(&spatial_query, &filter, origin, direction, distance);
+                    Impact::Teleport
+                }
+                SpellEffect::Knockback { distance } => {
+                    let away = caster_position
+                        .map(|pos| (target_transform.translation - pos).with_y(0.0))
+                        .unwrap_or(Vec3::ZERO);
+                    let direction =
+                        Dir3::new(away).or_else(|_| Dir3::new(target_transform.back().with_y(0.0)));
+                    let Ok(direction) = direction else {
+                        continue;
+                    };
+                    let origin = target_transform.translation;
+                    target_transform.translation += *direction
+                        * clamp_displacement(&spatial_query, &filter, origin, direction, distance);
+                    Impact::Knockback
+                }
+                SpellEffect::ApplyAura { aura_id } => {
+                    let Some(aura_def) = aura_library.and_then(|lib| lib.auras.get(&aura_id))
+                    else {
+                        tracing::warn!(%aura_id, spell_id = %msg.spell_id, "tried to apply invalid aura");
+                        continue;
+                    };
+
+                    // Repeated crowd control is shortened until the target becomes immune
+                    let multiplier =
+                        match (aura_def.crowd_control, diminishing_returns.as_deref_mut()) {
+                            (Some(category), Some(returns)) => returns.apply(category),
+                            _ => Some(1.0),
+                        };
+                    let Some(multiplier) = multiplier else {
+                        tracing::debug!(target = ?msg.target_entity, %aura_id, "target is immune to crowd control");
+                        combat_log.write(CombatLogMessage {
+                            source: Some(msg.caster_entity),
+                            target: msg.target_entity,
+                            event: CombatLogEvent::Immune {
+                                spell_id: msg.spell_id,
+                            },
+                        });
+                        writer.write(OutgoingMessage::new(
+                            recipients.clone(),
+                            OutgoingMessageData::SpellImpact {
+                                target_network_id: *target_network_id,
+                                spell_id: msg.spell_id,
+                                impact: Impact::Immune,
+                            },
+                        ));
+                        continue;
+                    };
+
+                    let duration = aura_def.duration * multiplier;
+                    let stacks = target_auras.apply(
+                        aura_id,
+                        aura_def,
+                        duration,
+                        msg.spell_id,
+                        msg.caster_entity,
+                        msg.caster_client_id,
+                    );
+                    writer.write(OutgoingMessage::new(
+                        recipients.clone(),
+                        OutgoingMessageData::AuraApplied {
+                            network_id: *target_network_id,
+                            aura_id,
+                            stacks,
+                            duration,
+                        },
+                    ));
+                    Impact::AuraApplied { aura_id }
+                }
+                SpellEffect::Dispel { count } => {
+                    let Some(aura_library) = aura_library else {
+                        continue;
+                    };
 
-            // TODO: Healing something should not tap it
-            // TODO: Notify interested clients of tap
-            if target_client_id.is_none() && tapped.is_none() {
-                commands.entity(msg.target_entity).insert(Tapped {
-                    owner_id: caster_client_id,
-                });
+                    // Hostile casters strip buffs, friendly casters cleanse debuffs
+                    let kind = if reaction.can_attack() {
+                        AuraKind::Buff
+                    } else {
+                        AuraKind::Debuff
+                    };
+                    let removed = target_auras.dispel(kind, count, aura_library);
+                    if removed.is_empty() {
+                        continue;
+                    }
+
+                    for &aura_id in &removed {
+                        combat_log.write(CombatLogMessage {
+                            source: Some(msg.caster_entity),
+                            target: msg.target_entity,
+                            event: CombatLogEvent::AuraRemoved { aura_id },
+                        });
+                        writer.write(OutgoingMessage::new(
+                            recipients.clone(),
+                            OutgoingMessageData::AuraRemoved {
+                                network_id: *target_network_id,
+                                aura_id,
+                            },
+                        ));
+                    }
+                    Impact::Dispel {
+                        count: removed.len() as u32,
+                    }
+                }
+                SpellEffect::SummonPet { .. } => {
+                    pet_writer.write(SummonPetMessage {
+                        owner_entity: msg.caster_entity,
+                        spell_id: msg.spell_id,
+                    });
+                    continue;
+                }
+                // Resurrections are applied separately, threat effects by the AI
+                SpellEffect::Resurrect { .. }
+                | SpellEffect::Taunt { .. }
+                | SpellEffect::DropThreat { .. } => continue,
+            };
+
+            if let Some(entry) = CombatLogMessage::from_impact(
+                msg.caster_entity,
+                msg.target_entity,
+                msg.spell_id,
+                &impact,
+                false,
+            ) {
+                combat_log.write(entry);
+            }
+            writer.write(OutgoingMessage {
+                recipients: recipients.clone(),
+                data: OutgoingMessageData::SpellImpact {
+                    target_network_id: *target_network_id,
+                    spell_id: msg.spell_id,
+                    impact,
+                },
+            });
+            if avoided {
+                break;
             }
         }
+    }
+}
 
-        writer.write(OutgoingMessage {
-            recipients,
-            data: outgoing_msg,
-        });
-
-        target_vitals.hp -= spell.damage;
+/// Shortens a displacement so the actor stops in front of the first obstacle along its path.
+fn clamp_displacement(
+    spatial_query: &SpatialQuery,
+    filter: &SpatialQueryFilter,
+    origin: Vec3,
+    direction: Dir3,
+    distance: f32,
+) -> f32 {
+    match spatial_query.cast_ray(origin, direction, distance, true, filter) {
+        Some(hit) => (hit.distance - DISPLACEMENT_WALL_MARGIN).max(0.0),
+        None => distance,
     }
 }
 
 pub fn tick_ability_cooldowns(time: Res<Time>, mut q_abilities: Query<&mut Abilities>) {
     for mut abilities in q_abilities.iter_mut() {
+        abilities.global_cooldown.tick(time.delta());
         for ability in abilities.known.iter_mut() {
             ability.cooldown.tick(time.delta());
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use protocol::models::PowerKind;
+    use std::time::Duration;
+
+    const STEP: Duration = Duration::from_millis(100);
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_message::<ApplySpellEffectMessage>()
+            .add_message::<ApplyAreaSpellMessage>()
+            .add_message::<LaunchProjectileMessage>()
+            .init_resource::<Time>()
+            .init_resource::<Assets<SpellLibrary>>()
+            .add_systems(Update, tick_casting);
+
+        let library = app
+            .world_mut()
+            .resource_mut::<Assets<SpellLibrary>>()
+            .add(SpellLibrary {
+                spells: HashMap::new(),
+            });
+        app.insert_resource(SpellLibraryHandle(library));
+        app
+    }
+
+    /// Spawns a caster that spent 30 of its 100 mana on a stationary cast.
+    fn start_cast(app: &mut App, channel: Option<Timer>) -> Entity {
+        let caster = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                Power {
+                    kind: PowerKind::Mana,
+                    current: 70,
+                    max: 100,
+                },
+            ))
+            .id();
+        // Let the spawn settle so it doesn't count as movement
+        app.update();
+        app.world_mut().entity_mut(caster).insert(Casting {
+            spell_id: 1,
+            target: CastTarget::Position(Vec3::ZERO),
+            timer: Timer::from_seconds(2.0, TimerMode::Once),
+            castable_while_moving: false,
+            channel,
+            pushbacks: 0,
+            cost: 30,
+        });
+        caster
+    }
+
+    fn run(app: &mut App, duration: Duration) {
+        for _ in 0..duration.as_millis() / STEP.as_millis() {
+            app.world_mut().resource_mut::<Time>().advance_by(STEP);
+            app.update();
+        }
+    }
+
+    fn step_aside(app: &mut App, caster: Entity) {
+        app.world_mut()
+            .get_mut::<Transform>(caster)
+            .unwrap()
+            .translation
+            .x += 1.0;
+    }
+
+    fn mana(app: &App, caster: Entity) -> i32 {
+        app.world().get::<Power>(caster).unwrap().current
+    }
+
+    #[test]
+    fn interrupted_casts_refund_their_cost() {
+        let mut app = app();
+        let caster = start_cast(&mut app, None);
+        run(&mut app, Duration::from_secs(1));
+        assert_eq!(mana(&app, caster), 70);
+
+        step_aside(&mut app, caster);
+        run(&mut app, STEP);
+        assert!(app.world().get::<Casting>(caster).is_none());
+        assert_eq!(mana(&app, caster), 100);
+    }
+
+    #[test]
+    fn completed_casts_keep_their_cost() {
+        let mut app = app();
+        let caster = start_cast(&mut app, None);
+        run(&mut app, Duration::from_secs(3));
+        assert!(app.world().get::<Casting>(caster).is_none());
+
+        step_aside(&mut app, caster);
+        run(&mut app, STEP);
+        assert_eq!(mana(&app, caster), 70);
+    }
+
+    #[test]
+    fn channels_are_only_refunded_before_their_first_tick() {
+        let channel = || Some(Timer::from_seconds(0.5, TimerMode::Repeating));
+
+        let mut app = app();
+        let early = start_cast(&mut app, channel());
+        let late = start_cast(&mut app, channel());
+        run(&mut app, Duration::from_millis(200));
+        step_aside(&mut app, early);
+        run(&mut app, Duration::from_millis(500));
+        step_aside(&mut app, late);
+        run(&mut app, STEP);
+
+        assert_eq!(mana(&app, early), 100);
+        assert_eq!(mana(&app, late), 70);
+    }
+}
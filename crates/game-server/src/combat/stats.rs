@@ -0,0 +1,90 @@
+use crate::{
+    assets::{ItemLibrary, ItemLibraryHandle},
+    combat::auras::Auras,
+    core::{ClientIdComponent, Equipment, InterestedClients},
+    networking::{OutgoingMessage, OutgoingMessageData},
+};
+use bevy::prelude::*;
+use game_core::{
+    auras::{AuraLibrary, AuraLibraryHandle},
+    components::{LevelComponent, Vitals},
+    networking::NetworkId,
+    power::Power,
+    stats::Stats,
+};
+
+/// Recomputes character stats when their level, equipment or auras change.
+/// Maximum health follows stamina, gained health is added to the current health as well.
+/// Maximum mana follows intellect.
+#[allow(clippy::type_complexity)]
+pub fn recompute_stats(
+    item_library_handle: Res<ItemLibraryHandle>,
+    item_assets: Res<Assets<ItemLibrary>>,
+    aura_library_handle: Res<AuraLibraryHandle>,
+    aura_assets: Res<Assets<AuraLibrary>>,
+    mut q_characters: Query<
+        (
+            &NetworkId,
+            &LevelComponent,
+            &Equipment,
+            Option<&Auras>,
+            &mut Stats,
+            Option<&mut Vitals>,
+            Option<&mut Power>,
+            &InterestedClients,
+            Option<&ClientIdComponent>,
+        ),
+        Or<(Changed<LevelComponent>, Changed<Equipment>, Changed<Auras>)>,
+    >,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    let Some(item_library) = item_assets.get(&item_library_handle.0) else {
+        return;
+    };
+    let aura_library = aura_assets.get(&aura_library_handle.0);
+
+    for (network_id, level, equipment, auras, mut stats, vitals, power, interested, client_id) in
+        q_characters.iter_mut()
+    {
+        let mut total = Stats::base(level.0) + equipment.stat_bonus(item_library);
+        if let (Some(auras), Some(aura_library)) = (auras, aura_library) {
+            total += auras.stat_bonus(aura_library);
+        }
+        if !stats.set_if_neq(total) {
+            continue;
+        }
+
+        if let Some(mut power) = power {
+            let max_power = total.max_power(power.kind);
+            if power.max != max_power {
+                power.set_max(max_power);
+            }
+        }
+
+        // Dead characters have no vitals, they are derived again on resurrection
+        let Some(mut vitals) = vitals else {
+            continue;
+        };
+        let max_hp = total.max_hp();
+        if vitals.max_hp == max_hp {
+            continue;
+        }
+        let gained = (max_hp - vitals.max_hp).max(0);
+        vitals.max_hp = max_hp;
+        vitals.hp = (vitals.hp + gained).min(max_hp);
+
+        let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
+        recipients.extend(interested.clients.iter().copied());
+        if let Some(client_id) = client_id {
+            recipients.push(client_id.0);
+        }
+
+        writer.write(OutgoingMessage::new(
+            recipients,
+            OutgoingMessageData::VitalsChanged {
+                network_id: *network_id,
+                vitals: vitals.clone(),
+            },
+        ));
+    }
+}
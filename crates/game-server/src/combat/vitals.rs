@@ -1,19 +1,56 @@
 use crate::{
+    ai::AiBrain,
+    combat::{Auras, CombatLogMessage, DiminishingReturns, InCombat},
     core::{ClientIdComponent, Dead, InterestedClients},
     networking::{OutgoingMessage, OutgoingMessageData},
     telemetry::PLAYER_DEATHS_TOTAL_METRIC,
 };
 use bevy::prelude::*;
 use game_core::components::{MovementSpeedComponent, Vitals};
+use game_core::constants::BASE_MOVEMENT_SPEED;
+use game_core::crowd_control::CrowdControlState;
 use game_core::networking::NetworkId;
+use protocol::models::CombatLogEvent;
+
+const HEALTH_REGEN_TICK_SECS: f32 = 2.0;
+/// Share of maximum health regenerated per tick out of combat
+const HEALTH_REGEN_FRACTION: f32 = 0.05;
+
+/// Shared tick of out-of-combat health regeneration.
+#[derive(Resource)]
+pub struct HealthRegenTimer(Timer);
+
+impl Default for HealthRegenTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            HEALTH_REGEN_TICK_SECS,
+            TimerMode::Repeating,
+        ))
+    }
+}
 
 #[derive(EntityEvent)]
 pub struct EntityDeath(pub Entity);
 
+/// Components only living actors have, removed on death.
 #[derive(Bundle)]
-struct LivingBundle {
+pub struct LivingBundle {
     vitals: Vitals,
     movement_speed: MovementSpeedComponent,
+    auras: Auras,
+    crowd_control: CrowdControlState,
+    diminishing_returns: DiminishingReturns,
+}
+
+/// Brings a dead actor back to life with the given vitals, no auras and no crowd control.
+pub fn revive(entity_commands: &mut EntityCommands, vitals: Vitals) {
+    entity_commands.remove::<Dead>().insert(LivingBundle {
+        vitals,
+        movement_speed: MovementSpeedComponent(BASE_MOVEMENT_SPEED),
+        auras: Auras::default(),
+        crowd_control: CrowdControlState::default(),
+        diminishing_returns: DiminishingReturns::default(),
+    });
 }
 
 pub fn on_vitals_changed(
@@ -28,19 +65,65 @@ pub fn on_vitals_changed(
     }
 }
 
+/// Regenerates health of players out of combat and of idle mobs.
+#[allow(clippy::type_complexity)]
+pub fn regenerate_health(
+    time: Res<Time>,
+    mut regen_timer: ResMut<HealthRegenTimer>,
+    mut q_actors: Query<
+        (
+            &NetworkId,
+            &mut Vitals,
+            Option<&AiBrain>,
+            &InterestedClients,
+            Option<&ClientIdComponent>,
+        ),
+        (Without<InCombat>, Without<Dead>),
+    >,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    regen_timer.0.tick(time.delta());
+    let ticks = regen_timer.0.times_finished_this_tick() as i32;
+    if ticks == 0 {
+        return;
+    }
+
+    for (network_id, mut vitals, brain, interested, client_id) in q_actors.iter_mut() {
+        if vitals.hp >= vitals.max_hp || brain.is_some_and(|brain| !brain.state.is_at_rest()) {
+            continue;
+        }
+
+        let amount = ((vitals.max_hp as f32 * HEALTH_REGEN_FRACTION).round() as i32).max(1);
+        vitals.hp = (vitals.hp + amount * ticks).min(vitals.max_hp);
+
+        let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
+        recipients.extend(interested.clients.iter().copied());
+        if let Some(client_id) = client_id {
+            recipients.push(client_id.0);
+        }
+
+        writer.write(OutgoingMessage::new(
+            recipients,
+            OutgoingMessageData::VitalsChanged {
+                network_id: *network_id,
+                vitals: vitals.clone(),
+            },
+        ));
+    }
+}
+
 pub fn on_entity_death(
     event: On<EntityDeath>,
     mut commands: Commands,
     q_victim: Query<(&NetworkId, &InterestedClients, Option<&ClientIdComponent>)>,
     mut writer: MessageWriter<OutgoingMessage>,
+    mut combat_log: MessageWriter<CombatLogMessage>,
 ) {
     let entity = event.0;
     commands
         .entity(entity)
-        .remove::<LivingBundle>()
-        .insert(Dead {
-            despawn_timer: Timer::new(std::time::Duration::from_secs(150), TimerMode::Once),
-        });
+        .remove::<(LivingBundle, InCombat)>()
+        .insert(Dead::default());
 
     let Ok((network_id, interested, victim_client_id)) = q_victim.get(entity) else {
         return tracing::error!(?entity, "could not retrieve victim components");
@@ -55,6 +138,12 @@ pub fn on_entity_death(
         tracing::info!(?entity, client_id = %victim_client_id.0, "player died");
     }
 
+    combat_log.write(CombatLogMessage {
+        source: None,
+        target: entity,
+        event: CombatLogEvent::Death,
+    });
+
     let outgoing_msg = OutgoingMessageData::Death {
         network_id: *network_id,
     };
@@ -64,16 +153,22 @@ pub fn on_entity_death(
     });
 }
 
+/// Despawns mob corpses once their timer runs out. Player corpses stay until the player
+/// releases their spirit, is resurrected or disconnects.
+#[allow(clippy::type_complexity)]
 pub fn tick_corpse_despawn_timers(
     mut commands: Commands,
-    mut q_dead: Query<(Entity, &NetworkId, &mut Dead, &InterestedClients)>,
+    mut q_dead: Query<
+        (Entity, &NetworkId, &mut Dead, &InterestedClients),
+        Without<ClientIdComponent>,
+    >,
     time: Res<Time>,
     mut writer: MessageWriter<OutgoingMessage>,
 ) {
     for (entity, network_id, mut dead, interested) in q_dead.iter_mut() {
         dead.despawn_timer.tick(time.delta());
         if dead.despawn_timer.is_finished() {
-            let outgoing_msg = OutgoingMessageData::DespawnCorpse(*network_id);
+            let outgoing_msg = OutgoingMessageData::Despawn(*network_id);
             let recipients = interested.clients.iter().copied().collect();
             writer.write(OutgoingMessage {
                 recipients,
@@ -23,6 +23,8 @@ pub struct Settings {
     pub database: DatabaseSettings,
     pub tracing: TracingSettings,
     pub nats_url: Option<String>,
+    /// Appends the combat log to this file as JSON lines when set
+    pub combat_log_path: Option<String>,
 }
 
 #[derive(serde::Deserialize, Clone)]
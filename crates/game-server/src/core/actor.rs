@@ -1,4 +1,8 @@
 use super::components::*;
+use crate::{
+    assets::Faction,
+    combat::{Auras, DiminishingReturns},
+};
 use avian3d::prelude::*;
 use bevy::prelude::*;
 use game_core::{
@@ -6,6 +10,7 @@ use game_core::{
     collision::GameLayer,
     components::{LevelComponent, MovementSpeedComponent, Vitals},
     constants::{ACTOR_COLLIDER_LENGTH, ACTOR_COLLIDER_RADIUS, BASE_MOVEMENT_SPEED},
+    crowd_control::CrowdControlState,
     networking::NetworkId,
 };
 use std::sync::Arc;
@@ -18,6 +23,10 @@ pub struct ActorBundle {
     pub transform: Transform,
     pub vitals: Vitals,
     pub movement_speed: MovementSpeedComponent,
+    pub base_movement_speed: BaseMovementSpeed,
+    pub auras: Auras,
+    pub crowd_control: CrowdControlState,
+    pub diminishing_returns: DiminishingReturns,
     pub level: LevelComponent,
     pub interested_clients: InterestedClients,
     pub body: RigidBody,
@@ -42,6 +51,10 @@ impl ActorBundle {
             transform,
             vitals: vitals.clone(),
             movement_speed: MovementSpeedComponent(BASE_MOVEMENT_SPEED),
+            base_movement_speed: BaseMovementSpeed(BASE_MOVEMENT_SPEED),
+            auras: Auras::default(),
+            crowd_control: CrowdControlState::default(),
+            diminishing_returns: DiminishingReturns::default(),
             level: LevelComponent(level),
             interested_clients: InterestedClients::default(),
             body: RigidBody::Kinematic,
@@ -70,6 +83,7 @@ pub struct CharacterBundle {
     pub client_id: ClientIdComponent,
     pub visible_entities: VisibleEntities,
     pub last_client_tick: LastClientTick,
+    pub faction: Faction,
 }
 
 impl CharacterBundle {
@@ -80,6 +94,7 @@ impl CharacterBundle {
             client_id: ClientIdComponent(client_id),
             visible_entities: VisibleEntities::default(),
             last_client_tick: LastClientTick::default(),
+            faction: Faction::player(),
         }
     }
 }
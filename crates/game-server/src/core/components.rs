@@ -1,9 +1,12 @@
 use bevy::{platform::collections::HashSet, prelude::*};
 use bevy_renet::renet::ClientId;
-use game_core::networking::NetworkId;
+use game_core::{networking::NetworkId, stats::Stats};
 use std::sync::Arc;
 
-use crate::telemetry::SERVER_TICK_METRIC;
+use crate::{
+    assets::{ItemLibrary, WeaponDef},
+    telemetry::SERVER_TICK_METRIC,
+};
 
 /// Monotonically incrementing counter for assigning unique network IDs to entities.
 #[derive(Resource, Debug, Default)]
@@ -43,9 +46,43 @@ pub struct CharacterIdComponent(pub i32);
 #[derive(Debug, Component)]
 pub struct AssetIdComponent(pub u32);
 
+#[derive(Debug, Component, Clone)]
+pub struct GuildComponent {
+    pub id: i32,
+    pub name: Arc<str>,
+}
+
+/// Item IDs a character has equipped
+#[derive(Debug, Component, Clone, Default)]
+pub struct Equipment {
+    pub items: Vec<u32>,
+}
+
+impl Equipment {
+    /// Sum of the stat bonuses of all equipped items.
+    pub fn stat_bonus(&self, library: &ItemLibrary) -> Stats {
+        self.items
+            .iter()
+            .filter_map(|item_id| library.items.get(item_id))
+            .fold(Stats::default(), |total, item| total + item.stats)
+    }
+
+    /// First equipped item that is a weapon.
+    pub fn weapon(&self, library: &ItemLibrary) -> Option<WeaponDef> {
+        self.items
+            .iter()
+            .filter_map(|item_id| library.items.get(item_id))
+            .find_map(|item| item.weapon)
+    }
+}
+
 #[derive(Debug, Component)]
 pub struct GridCell(pub IVec2);
 
+/// Movement speed before aura modifiers, `MovementSpeedComponent` holds the modified speed.
+#[derive(Debug, Component, Clone, Copy)]
+pub struct BaseMovementSpeed(pub f32);
+
 #[derive(Debug, Component, Default)]
 pub struct InterestedClients {
     pub clients: HashSet<ClientId>,
@@ -59,12 +96,58 @@ pub struct VisibleEntities {
     pub entities: HashSet<Entity>,
 }
 
+/// Seconds before a mob corpse is despawned
+const CORPSE_DESPAWN_SECS: u64 = 150;
+
 #[derive(Component)]
 pub struct Dead {
     pub despawn_timer: Timer,
 }
 
+impl Default for Dead {
+    fn default() -> Self {
+        Self {
+            despawn_timer: Timer::new(
+                std::time::Duration::from_secs(CORPSE_DESPAWN_SECS),
+                TimerMode::Once,
+            ),
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct Tapped {
     pub owner_id: ClientId,
 }
+
+/// Pending duel challenge, on the challenged player until accepted or expired.
+#[derive(Component, Debug)]
+pub struct DuelRequest {
+    pub challenger: Entity,
+    pub expiry: Timer,
+}
+
+/// Duel in progress, on both duelists.
+#[derive(Component, Debug)]
+pub struct Duel {
+    pub opponent: Entity,
+    /// Kept to announce the result if the opponent disconnects
+    pub opponent_id: NetworkId,
+    /// Centre of the duel boundary
+    pub center: Vec3,
+    /// The duelists can't attack each other until it runs out
+    pub countdown: Timer,
+}
+
+impl Duel {
+    pub fn is_active(&self) -> bool {
+        self.countdown.is_finished()
+    }
+}
+
+/// Flagged for world PvP, flagged players are hostile to each other.
+#[derive(Component, Debug, Default)]
+pub struct PvpFlag {
+    /// Runs once the player turns the flag off, the flag drops when it finishes
+    pub timeout: Option<Timer>,
+}
@@ -1,3 +1,4 @@
+use protocol::models::PowerKind;
 use sqlx::{Pool, Postgres};
 use tracing::instrument;
 
@@ -12,7 +13,12 @@ pub struct CharacterRow {
     pub level: i32,
     pub hp: i32,
     pub max_hp: i32,
+    pub is_dead: bool,
+    pub power_kind: String,
+    /// Spell that summoned the pet the character logged out with
+    pub pet_spell_id: Option<i32>,
     pub guild_id: Option<i32>,
+    pub guild_name: Option<String>,
 }
 
 #[instrument(skip_all)]
@@ -23,11 +29,12 @@ pub async fn load_character_data(
     sqlx::query_as!(
         CharacterRow,
         r#"
-        SELECT id, guild_id, name, level, hp, max_hp,
-            position_x, position_y, position_z,
-            rotation_yaw
-        FROM characters
-        WHERE id = $1 
+        SELECT c.id, c.guild_id, g.name AS "guild_name?", c.name, c.level, c.hp, c.max_hp,
+            c.position_x, c.position_y, c.position_z,
+            c.rotation_yaw, c.is_dead, c.power_kind, c.pet_spell_id
+        FROM characters c
+        LEFT JOIN guilds g ON g.id = c.guild_id
+        WHERE c.id = $1
         "#,
         character_id,
     )
@@ -35,6 +42,15 @@ pub async fn load_character_data(
     .await
 }
 
+/// Power kinds are stored by name, unknown names fall back to mana.
+pub fn parse_power_kind(name: &str) -> PowerKind {
+    match name {
+        "energy" => PowerKind::Energy,
+        "rage" => PowerKind::Rage,
+        _ => PowerKind::Mana,
+    }
+}
+
 #[instrument(skip_all)]
 pub async fn load_character_abilities(
     pool: &Pool<Postgres>,
@@ -53,3 +69,22 @@ pub async fn load_character_abilities(
 
     Ok(rows)
 }
+
+#[instrument(skip_all)]
+pub async fn load_character_equipment(
+    pool: &Pool<Postgres>,
+    character_id: i32,
+) -> Result<Vec<i32>, sqlx::Error> {
+    let rows = sqlx::query_scalar!(
+        r#"
+        SELECT item_id
+        FROM character_equipment
+        WHERE character_id = $1
+        "#,
+        character_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
@@ -1,7 +1,8 @@
 use crate::{
+    ai::Pet,
     assets::{LootDb, LootTable, MonsterId},
     combat::EntityDeath,
-    core::Tapped,
+    core::{ClientIdComponent, Tapped},
     networking::OutgoingMessage,
     networking::OutgoingMessageData,
     telemetry::{LOOT_ITEMS_GENERATED_TOTAL_METRIC, MOB_KILLS_TOTAL_METRIC},
@@ -35,18 +36,35 @@ pub struct Loot {
     pub owner_id: ClientId,
 }
 
+#[allow(clippy::type_complexity)]
 pub fn reward_kill(
     event: On<EntityDeath>,
     mut commands: Commands,
-    q_victim: Query<(&NetworkId, Option<&MonsterId>, Option<&Tapped>)>,
+    q_victim: Query<(
+        &NetworkId,
+        Option<&MonsterId>,
+        Option<&Tapped>,
+        Has<ClientIdComponent>,
+        Has<Pet>,
+    )>,
     loot_db: LootDb,
     mut writer: MessageWriter<OutgoingMessage>,
 ) {
     let entity = event.0;
-    let Ok((network_id, monster_id, tapped)) = q_victim.get(entity) else {
+    let Ok((network_id, monster_id, tapped, victim_is_player, victim_is_pet)) =
+        q_victim.get(entity)
+    else {
         return tracing::error!(?entity, "could not retrieve victim components");
     };
 
+    // Killing flagged players is not rewarded, so PvP can't be farmed by trading kills
+    if victim_is_player {
+        return tracing::debug!(?entity, "player kills give no reward");
+    }
+    if victim_is_pet {
+        return tracing::debug!(?entity, "pet kills give no reward");
+    }
+
     let Some(killer_client_id) = tapped.map(|t| t.owner_id) else {
         return tracing::debug!("entity was not killed by a player");
     };
@@ -1,5 +1,9 @@
 use crate::{
-    combat::{CastSpellActionMessage, StartAttackMessage, StopAttackMessage},
+    ai::{DismissPetMessage, PetAttackMessage, SetPetStanceMessage},
+    combat::{
+        AcceptDuelMessage, CastSpellActionMessage, CastTarget, ReleaseSpiritMessage,
+        RequestDuelMessage, SetPvpFlagMessage, StartAttackMessage, StopAttackMessage,
+    },
     core::{ClientIdComponent, LastClientTick, ServerTick},
     social::IncomingChatMessage,
     telemetry::{NETWORK_BYTES_TOTAL_METRIC, NETWORK_PACKETS_TOTAL_METRIC},
@@ -8,7 +12,10 @@ use crate::{
 use bevy::prelude::*;
 use bevy_renet::{RenetServer, renet::DefaultChannel};
 use game_core::networking::NetworkId;
-use game_core::{components::MovementSpeedComponent, networking::NetworkIdMapping};
+use game_core::{
+    components::MovementSpeedComponent, crowd_control::CrowdControlState,
+    networking::NetworkIdMapping,
+};
 use protocol::client::{MoveAction, PlayerAction};
 use protocol::server::ServerEvent;
 
@@ -61,12 +68,17 @@ pub fn process_client_actions(
 pub fn process_client_movements(
     mut server: ResMut<RenetServer>,
     mut clients: Query<
-        (Entity, &ClientIdComponent, &mut LastClientTick),
+        (
+            Entity,
+            &ClientIdComponent,
+            &mut LastClientTick,
+            Option<&CrowdControlState>,
+        ),
         With<MovementSpeedComponent>,
     >,
     mut writer: MessageWriter<MoveActionMessage>,
 ) {
-    for (entity, client_id, mut last_client_tick) in clients.iter_mut() {
+    for (entity, client_id, mut last_client_tick, crowd_control) in clients.iter_mut() {
         let mut latest_action: Option<MoveAction> = None;
 
         while let Some(message) = server.receive_message(client_id.0, DefaultChannel::Unreliable) {
@@ -94,8 +106,18 @@ pub fn process_client_movements(
         }
 
         // NOTE: We only handle the latest move action to prevent flooding
-        if let Some(action) = latest_action {
+        if let Some(mut action) = latest_action {
             last_client_tick.0 = action.tick;
+
+            // Feared players are moved by `move_feared_actors`, stuns and roots only allow turning
+            match crowd_control {
+                Some(state) if state.feared => continue,
+                Some(state) if !state.can_move() => {
+                    action.forward = 0;
+                    action.sideways = 0;
+                }
+                _ => {}
+            }
             writer.write(MoveActionMessage { entity, action });
         }
     }
@@ -137,7 +159,18 @@ fn process_player_action(
             };
             commands.write_message(CastSpellActionMessage {
                 caster_entity: entity,
-                target_entity,
+                target: CastTarget::Entity(target_entity),
+                spell_id,
+            });
+        }
+        PlayerAction::CastSpellAtPosition { spell_id, position } => {
+            if !position.is_finite() {
+                tracing::warn!("client sent CastSpellAtPosition with invalid position");
+                return;
+            }
+            commands.write_message(CastSpellActionMessage {
+                caster_entity: entity,
+                target: CastTarget::Position(position),
                 spell_id,
             });
         }
@@ -160,6 +193,61 @@ fn process_player_action(
                 attacker_entity: entity,
             });
         }
+        PlayerAction::ReleaseSpirit => {
+            commands.write_message(ReleaseSpiritMessage {
+                player_entity: entity,
+            });
+        }
+        PlayerAction::RequestDuel { target_network_id } => {
+            let Some(target_entity) = net_entity_map.0.get(&NetworkId(target_network_id)).copied()
+            else {
+                tracing::warn!(
+                    %target_network_id,
+                    "client sent RequestDuel with unknown network ID"
+                );
+                return;
+            };
+            commands.write_message(RequestDuelMessage {
+                challenger_entity: entity,
+                target_entity,
+            });
+        }
+        PlayerAction::AcceptDuel => {
+            commands.write_message(AcceptDuelMessage {
+                player_entity: entity,
+            });
+        }
+        PlayerAction::SetPvpFlag { enabled } => {
+            commands.write_message(SetPvpFlagMessage {
+                player_entity: entity,
+                enabled,
+            });
+        }
+        PlayerAction::SetPetStance { stance } => {
+            commands.write_message(SetPetStanceMessage {
+                owner_entity: entity,
+                stance,
+            });
+        }
+        PlayerAction::PetAttack { target_network_id } => {
+            let Some(target_entity) = net_entity_map.0.get(&NetworkId(target_network_id)).copied()
+            else {
+                tracing::warn!(
+                    %target_network_id,
+                    "client sent PetAttack with unknown network ID"
+                );
+                return;
+            };
+            commands.write_message(PetAttackMessage {
+                owner_entity: entity,
+                target_entity,
+            });
+        }
+        PlayerAction::DismissPet => {
+            commands.write_message(DismissPetMessage {
+                owner_entity: entity,
+            });
+        }
         PlayerAction::Ping { client_tick } => {
             let pong = ServerEvent::Pong {
                 client_tick,
@@ -1,11 +1,15 @@
 use crate::{
-    combat::Abilities,
+    ai::{Pet, SummonPetMessage},
+    assets::{ItemLibrary, ItemLibraryHandle},
+    combat::{Abilities, LivingBundle, PowerRegen, SavedCooldowns},
     core::{
-        ActorBundle, CharacterBundle, CharacterIdComponent, ClientIdComponent, InterestedClients,
-        NetworkIdCounter, ServerTick,
+        ActorBundle, CharacterBundle, CharacterIdComponent, ClientIdComponent, Dead, Equipment,
+        GuildComponent, InterestedClients, NetworkIdCounter, ServerTick,
     },
     database::DatabasePool,
-    database::{load_character_abilities, load_character_data},
+    database::{
+        load_character_abilities, load_character_data, load_character_equipment, parse_power_kind,
+    },
 };
 use bevy::prelude::*;
 use bevy_renet::{
@@ -18,19 +22,21 @@ use game_core::{
     components::Vitals,
     constants::{ACTOR_HALF_HEIGHT, BASE_MOVEMENT_SPEED},
     networking::NetworkIdMapping,
+    power::Power,
     spells::{SpellLibrary, SpellLibraryHandle},
+    stats::Stats,
 };
 use protocol::{
-    models::{Actor, ActorAttributes},
+    models::{Actor, ActorAttributes, Reaction},
     primitives::Transform as NetTransform,
     server::{EnterGameResponse, TokenUserData},
 };
 use sqlx::PgPool;
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 use tracing::{Instrument, Level, instrument};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn on_connection_event(
     event: On<RenetServerEvent>,
     mut commands: Commands,
@@ -41,8 +47,12 @@ pub fn on_connection_event(
         &ClientIdComponent,
         &CharacterIdComponent,
         &Transform,
+        &Abilities,
+        Has<Dead>,
     )>,
+    pets: Query<&Pet, Without<Dead>>,
     mut interested: Query<&mut InterestedClients>,
+    mut saved_cooldowns: ResMut<SavedCooldowns>,
     runtime: Res<TokioTasksRuntime>,
     pool: Res<DatabasePool>,
 ) {
@@ -56,7 +66,9 @@ pub fn on_connection_event(
                 reason,
                 &mut commands,
                 players,
+                pets,
                 &mut interested,
+                &mut saved_cooldowns,
                 &pool,
                 &runtime,
             );
@@ -124,6 +136,9 @@ async fn handle_enter_game_task(
     let ability_ids = load_character_abilities(&pool, character_id)
         .await
         .expect("player abilities retrieved");
+    let item_ids = load_character_equipment(&pool, character_id)
+        .await
+        .expect("player equipment retrieved");
 
     ctx.run_on_main_thread(move |ctx| {
         // DB stores ground-level position (feet); offset Y to capsule center
@@ -133,10 +148,34 @@ async fn handle_enter_game_task(
             character.position_z,
         );
         transform.rotate_y(character.rotation_yaw);
+
+        // Stats are derived from level and equipment instead of persisted, auras start empty
+        let equipment = Equipment {
+            items: item_ids.iter().map(|&id| id as u32).collect(),
+        };
+        let equipment_bonus = ctx
+            .world
+            .get_resource::<ItemLibraryHandle>()
+            .and_then(|handle| {
+                ctx.world
+                    .get_resource::<Assets<ItemLibrary>>()
+                    .and_then(|assets| assets.get(&handle.0))
+            })
+            .map(|lib| equipment.stat_bonus(lib))
+            .unwrap_or_default();
+        let stats = Stats::base(character.level) + equipment_bonus;
+        let max_hp = stats.max_hp();
+        // Characters that logged out dead stay dead until they release or are resurrected
         let vitals = Vitals {
-            hp: character.hp,
-            max_hp: character.max_hp,
+            hp: if character.is_dead {
+                0
+            } else {
+                character.hp.clamp(1, max_hp)
+            },
+            max_hp,
         };
+        let power_kind = parse_power_kind(&character.power_kind);
+        let power = Power::new(power_kind, stats.max_power(power_kind));
 
         let spell_ids: Vec<u32> = ability_ids.iter().map(|&id| id as u32).collect();
 
@@ -161,29 +200,62 @@ async fn handle_enter_game_task(
             })
             .unwrap_or_default();
 
+        let cooldowns = ctx
+            .world
+            .get_resource_mut::<SavedCooldowns>()
+            .map(|mut saved| saved.take(character.id))
+            .unwrap_or_default();
+        let mut abilities = Abilities::new(&spell_ids, &spell_cooldowns);
+        abilities.restore_cooldowns(&cooldowns);
+
         let network_id = ctx
             .world
             .get_resource_mut::<NetworkIdCounter>()
             .unwrap()
             .allocate();
 
-        let entity = ctx
-            .world
-            .spawn((
-                CharacterBundle::new(
-                    ActorBundle::new(
-                        network_id,
-                        &character.name,
-                        transform,
-                        vitals.clone(),
-                        character.level,
-                    ),
-                    character.id,
-                    client_id,
+        let mut entity_commands = ctx.world.spawn((
+            CharacterBundle::new(
+                ActorBundle::new(
+                    network_id,
+                    &character.name,
+                    transform,
+                    vitals.clone(),
+                    character.level,
                 ),
-                Abilities::new(&spell_ids, &spell_cooldowns),
-            ))
-            .id();
+                character.id,
+                client_id,
+            ),
+            abilities,
+            stats,
+            equipment,
+            power,
+            PowerRegen::default(),
+        ));
+
+        if character.is_dead {
+            entity_commands
+                .remove::<LivingBundle>()
+                .insert(Dead::default());
+        }
+
+        if let (Some(guild_id), Some(guild_name)) = (character.guild_id, &character.guild_name) {
+            entity_commands.insert(GuildComponent {
+                id: guild_id,
+                name: Arc::from(guild_name.as_str()),
+            });
+        }
+
+        let entity = entity_commands.id();
+
+        if let Some(spell_id) = character.pet_spell_id
+            && !character.is_dead
+        {
+            ctx.world.write_message(SummonPetMessage {
+                owner_entity: entity,
+                spell_id: spell_id as u32,
+            });
+        }
 
         ctx.world
             .get_resource_mut::<NetworkIdMapping>()
@@ -193,8 +265,7 @@ async fn handle_enter_game_task(
 
         let attributes = ActorAttributes::Player {
             character_id: character.id,
-            // TODO: Handle guild fetching
-            guild_name: None,
+            guild_name: character.guild_name,
         };
 
         let player_actor = Actor {
@@ -205,6 +276,11 @@ async fn handle_enter_game_task(
             vitals: vitals.into(),
             level: character.level as u8,
             movement_speed: BASE_MOVEMENT_SPEED.into(),
+            auras: Vec::new(),
+            in_combat: false,
+            crowd_control: Default::default(),
+            reaction: Reaction::Friendly,
+            owner_id: None,
         };
 
         let server_tick = ctx
@@ -217,6 +293,8 @@ async fn handle_enter_game_task(
             player_actor,
             server_tick,
             known_abilities: spell_ids,
+            power: power.into(),
+            cooldowns,
         };
 
         let mut server = ctx.world.get_resource_mut::<RenetServer>().unwrap();
@@ -231,6 +309,7 @@ async fn handle_enter_game_task(
     tracing::info!("successfully sent EnterGameResponse");
 }
 
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 #[instrument(skip_all, fields(client_id = client_id))]
 fn process_client_disconnected(
     client_id: ClientId,
@@ -241,8 +320,12 @@ fn process_client_disconnected(
         &ClientIdComponent,
         &CharacterIdComponent,
         &Transform,
+        &Abilities,
+        Has<Dead>,
     )>,
+    pets: Query<&Pet, Without<Dead>>,
     interested: &mut Query<&mut InterestedClients>,
+    saved_cooldowns: &mut SavedCooldowns,
     pool: &DatabasePool,
     runtime: &TokioTasksRuntime,
 ) {
@@ -252,10 +335,16 @@ fn process_client_disconnected(
         interested_clients.clients.remove(&client_id);
     }
 
-    for (entity, player_client_id, character_id, transform) in players.iter() {
+    for (entity, player_client_id, character_id, transform, abilities, is_dead) in players.iter() {
         if player_client_id.0 == client_id {
             let db_pool = pool.0.clone();
             let character_id = character_id.0;
+            saved_cooldowns.save(character_id, abilities.running_cooldowns());
+            // The pet despawns with the character and is resummoned on the next login
+            let pet_spell_id = pets
+                .iter()
+                .find(|pet| pet.owner == entity)
+                .map(|pet| pet.spell_id as i32);
             let transform = *transform;
             commands.entity(entity).despawn();
             runtime.spawn_background_task(async move |_| {
@@ -266,7 +355,7 @@ fn process_client_disconnected(
                     r#"
                     UPDATE CHARACTERS
                     SET position_x = $2, position_y = $3, position_z = $4,
-                        rotation_yaw = $5
+                        rotation_yaw = $5, is_dead = $6, pet_spell_id = $7
                     WHERE id = $1 
                     "#,
                     character_id,
@@ -274,6 +363,8 @@ fn process_client_disconnected(
                     pos.y - ACTOR_HALF_HEIGHT,
                     pos.z,
                     yaw,
+                    is_dead,
+                    pet_spell_id,
                 )
                 .execute(&db_pool)
                 .await
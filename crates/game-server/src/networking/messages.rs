@@ -1,12 +1,16 @@
 use bevy::prelude::*;
 use bevy_renet::renet::ClientId;
-use game_core::networking::NetworkId;
+use game_core::{
+    components::Vitals, crowd_control::CrowdControlState, networking::NetworkId, power::Power,
+};
 
 use crate::economy::LootEntry;
 
 #[derive(Message, Debug)]
 pub struct VisibilityChangedMessage {
     pub client_id: ClientId,
+    /// Character of the client
+    pub viewer: Entity,
     pub added: Vec<Entity>,
     pub removed: Vec<Entity>,
 }
@@ -36,7 +40,11 @@ pub enum OutgoingMessageData {
     Death {
         network_id: NetworkId,
     },
-    DespawnCorpse(NetworkId),
+    Despawn(NetworkId),
+    Revived {
+        network_id: NetworkId,
+        vitals: Vitals,
+    },
     KillReward {
         victim_network_id: NetworkId,
         loot: Vec<LootEntry>,
@@ -44,11 +52,102 @@ pub enum OutgoingMessageData {
     StartCasting {
         network_id: NetworkId,
         spell_id: u32,
+        duration: f32,
+    },
+    CastPushback {
+        network_id: NetworkId,
+        remaining: f32,
+    },
+    CastFailed {
+        spell_id: u32,
+        reason: protocol::models::CastFailedReason,
+    },
+    ProjectileLaunched {
+        caster_network_id: NetworkId,
+        target_network_id: NetworkId,
+        spell_id: u32,
+        travel_time: f32,
     },
     SpellImpact {
         target_network_id: NetworkId,
         spell_id: u32,
-        impact_amount: i32,
+        impact: protocol::models::Impact,
+    },
+    AreaEffect {
+        network_id: NetworkId,
+        spell_id: u32,
+        center: Vec3,
+        shape: protocol::models::AreaShape,
+    },
+    CooldownStarted {
+        spell_id: u32,
+        duration: f32,
+    },
+    AuraApplied {
+        network_id: NetworkId,
+        aura_id: u32,
+        stacks: u32,
+        duration: f32,
+    },
+    AuraRemoved {
+        network_id: NetworkId,
+        aura_id: u32,
+    },
+    MovementSpeedChanged {
+        network_id: NetworkId,
+        movement_speed: f32,
+    },
+    VitalsChanged {
+        network_id: NetworkId,
+        vitals: Vitals,
+    },
+    PowerChanged {
+        network_id: NetworkId,
+        power: Power,
+    },
+    CombatStateChanged {
+        network_id: NetworkId,
+        in_combat: bool,
+    },
+    CrowdControlChanged {
+        network_id: NetworkId,
+        state: CrowdControlState,
+    },
+    ReactionChanged {
+        network_id: NetworkId,
+        reaction: protocol::models::Reaction,
+    },
+    DuelRequested {
+        challenger_network_id: NetworkId,
+    },
+    DuelStarting {
+        opponent_network_id: NetworkId,
+        center: Vec3,
+        radius: f32,
+        countdown: f32,
+    },
+    DuelEnded {
+        winner_network_id: NetworkId,
+        loser_network_id: NetworkId,
+        reason: protocol::models::DuelEndReason,
+    },
+    PvpFlagChanged {
+        flagged: bool,
+        expires_in: Option<f32>,
+    },
+    EncounterStarted {
+        boss_network_id: NetworkId,
+        name: String,
+    },
+    EncounterEnded {
+        boss_network_id: NetworkId,
+        name: String,
+        outcome: protocol::models::EncounterOutcome,
+    },
+    CombatLog(Vec<protocol::models::CombatLogEntry>),
+    GuildChanged {
+        network_id: NetworkId,
+        guild_name: Option<String>,
     },
 }
 
@@ -58,7 +157,13 @@ impl From<OutgoingMessageData> for protocol::server::ServerEvent {
             OutgoingMessageData::Death { network_id } => {
                 protocol::server::ServerEvent::ActorDeath(network_id.0)
             }
-            OutgoingMessageData::DespawnCorpse(network_id) => {
+            OutgoingMessageData::Revived { network_id, vitals } => {
+                protocol::server::ServerEvent::ActorRevived {
+                    actor_id: network_id.0,
+                    vitals: vitals.into(),
+                }
+            }
+            OutgoingMessageData::Despawn(network_id) => {
                 protocol::server::ServerEvent::ActorDespawn(network_id.0)
             }
             OutgoingMessageData::KillReward {
@@ -83,18 +188,170 @@ impl From<OutgoingMessageData> for protocol::server::ServerEvent {
             OutgoingMessageData::StartCasting {
                 network_id,
                 spell_id,
+                duration,
             } => protocol::server::ServerEvent::StartCasting {
                 actor_id: network_id.0,
                 spell_id,
+                duration,
+            },
+            OutgoingMessageData::CastPushback {
+                network_id,
+                remaining,
+            } => protocol::server::ServerEvent::CastPushback {
+                actor_id: network_id.0,
+                remaining,
+            },
+            OutgoingMessageData::CastFailed { spell_id, reason } => {
+                protocol::server::ServerEvent::CastFailed { spell_id, reason }
+            }
+            OutgoingMessageData::ProjectileLaunched {
+                caster_network_id,
+                target_network_id,
+                spell_id,
+                travel_time,
+            } => protocol::server::ServerEvent::ProjectileLaunched {
+                caster_id: caster_network_id.0,
+                target_id: target_network_id.0,
+                spell_id,
+                travel_time,
             },
             OutgoingMessageData::SpellImpact {
                 target_network_id,
                 spell_id,
-                impact_amount,
+                impact,
             } => protocol::server::ServerEvent::SpellImpact {
                 target_id: target_network_id.0,
                 spell_id,
-                impact_amount,
+                impact,
+            },
+            OutgoingMessageData::AreaEffect {
+                network_id,
+                spell_id,
+                center,
+                shape,
+            } => protocol::server::ServerEvent::AreaEffect {
+                caster_id: network_id.0,
+                spell_id,
+                center,
+                shape,
+            },
+            OutgoingMessageData::CooldownStarted { spell_id, duration } => {
+                protocol::server::ServerEvent::CooldownStarted { spell_id, duration }
+            }
+            OutgoingMessageData::AuraApplied {
+                network_id,
+                aura_id,
+                stacks,
+                duration,
+            } => protocol::server::ServerEvent::AuraApplied {
+                actor_id: network_id.0,
+                aura_id,
+                stacks: stacks.min(u8::MAX as u32) as u8,
+                duration,
+            },
+            OutgoingMessageData::AuraRemoved {
+                network_id,
+                aura_id,
+            } => protocol::server::ServerEvent::AuraRemoved {
+                actor_id: network_id.0,
+                aura_id,
+            },
+            OutgoingMessageData::MovementSpeedChanged {
+                network_id,
+                movement_speed,
+            } => protocol::server::ServerEvent::MovementSpeedChanged {
+                actor_id: network_id.0,
+                movement_speed: movement_speed.into(),
+            },
+            OutgoingMessageData::VitalsChanged { network_id, vitals } => {
+                protocol::server::ServerEvent::VitalsChanged {
+                    actor_id: network_id.0,
+                    vitals: vitals.into(),
+                }
+            }
+            OutgoingMessageData::PowerChanged { network_id, power } => {
+                protocol::server::ServerEvent::PowerChanged {
+                    actor_id: network_id.0,
+                    power: power.into(),
+                }
+            }
+            OutgoingMessageData::CombatStateChanged {
+                network_id,
+                in_combat,
+            } => protocol::server::ServerEvent::CombatStateChanged {
+                actor_id: network_id.0,
+                in_combat,
+            },
+            OutgoingMessageData::CrowdControlChanged { network_id, state } => {
+                protocol::server::ServerEvent::CrowdControlChanged {
+                    actor_id: network_id.0,
+                    state: state.into(),
+                }
+            }
+            OutgoingMessageData::ReactionChanged {
+                network_id,
+                reaction,
+            } => protocol::server::ServerEvent::ReactionChanged {
+                actor_id: network_id.0,
+                reaction,
+            },
+            OutgoingMessageData::DuelRequested {
+                challenger_network_id,
+            } => protocol::server::ServerEvent::DuelRequested {
+                challenger_id: challenger_network_id.0,
+            },
+            OutgoingMessageData::DuelStarting {
+                opponent_network_id,
+                center,
+                radius,
+                countdown,
+            } => protocol::server::ServerEvent::DuelStarting {
+                opponent_id: opponent_network_id.0,
+                center,
+                radius,
+                countdown,
+            },
+            OutgoingMessageData::DuelEnded {
+                winner_network_id,
+                loser_network_id,
+                reason,
+            } => protocol::server::ServerEvent::DuelEnded {
+                winner_id: winner_network_id.0,
+                loser_id: loser_network_id.0,
+                reason,
+            },
+            OutgoingMessageData::PvpFlagChanged {
+                flagged,
+                expires_in,
+            } => protocol::server::ServerEvent::PvpFlagChanged {
+                flagged,
+                expires_in,
+            },
+            OutgoingMessageData::EncounterStarted {
+                boss_network_id,
+                name,
+            } => protocol::server::ServerEvent::EncounterStarted {
+                boss_id: boss_network_id.0,
+                name,
+            },
+            OutgoingMessageData::EncounterEnded {
+                boss_network_id,
+                name,
+                outcome,
+            } => protocol::server::ServerEvent::EncounterEnded {
+                boss_id: boss_network_id.0,
+                name,
+                outcome,
+            },
+            OutgoingMessageData::CombatLog(entries) => {
+                protocol::server::ServerEvent::CombatLog(entries)
+            }
+            OutgoingMessageData::GuildChanged {
+                network_id,
+                guild_name,
+            } => protocol::server::ServerEvent::ActorGuildChanged {
+                actor_id: network_id.0,
+                guild_name,
             },
         }
     }
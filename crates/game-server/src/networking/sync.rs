@@ -1,7 +1,10 @@
 use crate::{
+    ai::Pet,
+    assets::Reactions,
+    combat::{Auras, InCombat},
     core::{
-        AssetIdComponent, CharacterIdComponent, ClientIdComponent, InterestedClients,
-        LastClientTick, NameComponent,
+        AssetIdComponent, CharacterIdComponent, ClientIdComponent, GuildComponent,
+        InterestedClients, LastClientTick, NameComponent,
     },
     networking::messages::{OutgoingMessage, VisibilityChangedMessage},
     telemetry::{NETWORK_BYTES_TOTAL_METRIC, NETWORK_PACKETS_TOTAL_METRIC},
@@ -9,13 +12,14 @@ use crate::{
 use bevy::{platform::collections::HashMap, prelude::*};
 use bevy_renet::{RenetServer, renet::DefaultChannel};
 use game_core::components::{LevelComponent, MovementSpeedComponent, Vitals};
+use game_core::crowd_control::CrowdControlState;
 use game_core::networking::NetworkId;
 use protocol::{
     models::Actor,
     server::{ActorTransformUpdate, ServerEvent, ServerMovementPayload},
 };
 use protocol::{
-    models::{ActorAttributes, Vitals as NetVitals},
+    models::{ActorAttributes, Reaction, Vitals as NetVitals},
     primitives::Transform as NetTransform,
 };
 
@@ -125,14 +129,20 @@ type SpawnableComponents<'a> = (
     &'a MovementSpeedComponent,
     Option<&'a CharacterIdComponent>,
     Option<&'a AssetIdComponent>,
+    Option<&'a GuildComponent>,
+    Option<&'a Auras>,
+    Has<InCombat>,
+    Option<&'a CrowdControlState>,
+    Option<&'a Pet>,
 );
 
 pub fn sync_visibility(
     mut server: ResMut<RenetServer>,
     q_spawnables: Query<SpawnableComponents>,
+    reactions: Reactions,
     mut reader: MessageReader<VisibilityChangedMessage>,
     mut encode_buffer: Local<bitcode::Buffer>,
-    mut spawn_cache: Local<HashMap<Entity, Vec<u8>>>,
+    mut spawn_cache: Local<HashMap<(Entity, Reaction), Vec<u8>>>,
 ) {
     spawn_cache.clear();
 
@@ -149,7 +159,9 @@ pub fn sync_visibility(
         }
 
         for &entity in &msg.added {
-            if let Some(cached_spawn) = spawn_cache.get(&entity) {
+            // Spawns differ per reaction only, so viewers with the same reaction share them
+            let reaction = reactions.between(msg.viewer, entity);
+            if let Some(cached_spawn) = spawn_cache.get(&(entity, reaction)) {
                 server.send_message(
                     msg.client_id,
                     DefaultChannel::ReliableOrdered,
@@ -158,14 +170,26 @@ pub fn sync_visibility(
                 continue;
             }
 
-            if let Ok((network_id, name, transform, vitals, level, speed, char_id, asset_id)) =
-                q_spawnables.get(entity)
+            if let Ok((
+                network_id,
+                name,
+                transform,
+                vitals,
+                level,
+                speed,
+                char_id,
+                asset_id,
+                guild,
+                auras,
+                in_combat,
+                crowd_control,
+                pet,
+            )) = q_spawnables.get(entity)
             {
                 let attributes = if let Some(cid) = char_id {
                     ActorAttributes::Player {
                         character_id: cid.0,
-                        // TODO: Correctly handle guild
-                        guild_name: None,
+                        guild_name: guild.map(|g| g.name.to_string()),
                     }
                 } else if let Some(aid) = asset_id {
                     ActorAttributes::Npc { asset_id: aid.0 }
@@ -182,6 +206,11 @@ pub fn sync_visibility(
                     vitals: NetVitals::from(vitals.clone()),
                     movement_speed: speed.0.into(),
                     level: level.0 as u8,
+                    auras: auras.map(Auras::to_net).unwrap_or_default(),
+                    in_combat,
+                    crowd_control: crowd_control.copied().unwrap_or_default().into(),
+                    reaction,
+                    owner_id: pet.map(|pet| pet.owner_id.0),
                 };
 
                 let data = encode_buffer
@@ -189,7 +218,7 @@ pub fn sync_visibility(
                     .to_vec();
                 server.send_message(msg.client_id, DefaultChannel::ReliableOrdered, data.clone());
 
-                spawn_cache.insert(entity, data);
+                spawn_cache.insert((entity, reaction), data);
             }
         }
     }
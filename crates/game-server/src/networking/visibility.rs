@@ -71,6 +71,7 @@ pub fn update_player_visibility(
         if !added_entities.is_empty() || !removed_entities.is_empty() {
             writer.write(VisibilityChangedMessage {
                 client_id: client_id.0,
+                viewer: player_entity,
                 added: added_entities,
                 removed: removed_entities,
             });
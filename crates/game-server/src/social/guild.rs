@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+use futures_util::{FutureExt, StreamExt};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::core::{CharacterIdComponent, ClientIdComponent, GuildComponent, InterestedClients};
+use crate::networking::{OutgoingMessage, OutgoingMessageData};
+use game_core::networking::NetworkId;
+
+#[derive(Deserialize, Debug)]
+pub struct GuildUpdate {
+    pub guild_id: Option<i32>,
+    pub guild_name: Option<String>,
+}
+
+#[derive(Resource)]
+pub struct GuildSubscription(pub async_nats::Subscriber);
+
+pub fn process_guild_updates(
+    subscription: Option<ResMut<GuildSubscription>>,
+    mut commands: Commands,
+    characters: Query<(
+        Entity,
+        &CharacterIdComponent,
+        &NetworkId,
+        &ClientIdComponent,
+        &InterestedClients,
+    )>,
+    mut writer: MessageWriter<OutgoingMessage>,
+) {
+    let Some(mut subscription) = subscription else {
+        return;
+    };
+
+    while let Some(msg) = subscription.0.next().now_or_never().flatten() {
+        let subject = msg.subject.as_str();
+        let Some(character_id_str) = subject.strip_prefix("guild.update.") else {
+            tracing::warn!(%subject, "unexpected guild update subject");
+            continue;
+        };
+        let Ok(character_id) = character_id_str.parse::<i32>() else {
+            tracing::warn!(%character_id_str, "invalid character_id in guild update subject");
+            continue;
+        };
+
+        let update = match serde_json::from_slice::<GuildUpdate>(&msg.payload) {
+            Ok(u) => u,
+            Err(err) => {
+                tracing::warn!(?err, "invalid guild update payload");
+                continue;
+            }
+        };
+
+        let Some((entity, _, network_id, client_id, interested)) =
+            characters.iter().find(|(_, id, ..)| id.0 == character_id)
+        else {
+            continue;
+        };
+
+        let guild_name = match (update.guild_id, update.guild_name) {
+            (Some(guild_id), Some(guild_name)) => {
+                commands.entity(entity).insert(GuildComponent {
+                    id: guild_id,
+                    name: Arc::from(guild_name.as_str()),
+                });
+                Some(guild_name)
+            }
+            _ => {
+                commands.entity(entity).remove::<GuildComponent>();
+                None
+            }
+        };
+
+        tracing::debug!(character_id, ?guild_name, "character guild changed");
+
+        let mut recipients = Vec::with_capacity(interested.clients.len() + 1);
+        recipients.extend(interested.clients.iter().copied());
+        recipients.push(client_id.0);
+
+        writer.write(OutgoingMessage::new(
+            recipients,
+            OutgoingMessageData::GuildChanged {
+                network_id: *network_id,
+                guild_name,
+            },
+        ));
+    }
+}
@@ -1,7 +1,9 @@
 mod chat;
+mod guild;
 mod party;
 
-pub use party::PartySubscription;
+pub use guild::GuildSubscription;
+pub use party::{PartyId, PartySubscription};
 
 use crate::configuration::Settings;
 use bevy::prelude::*;
@@ -9,7 +11,7 @@ use bevy_tokio_tasks::TokioTasksRuntime;
 
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SocialSet {
-    /// Poll external updates (NATS party subscription).
+    /// Poll external updates (NATS party and guild subscriptions).
     ReceiveUpdates,
     /// Process chat messages.
     ProcessChat,
@@ -22,7 +24,7 @@ pub struct IncomingChatMessage {
     pub text: String,
 }
 
-/// NATS client for receiving cross-service messages (party updates, etc.)
+/// NATS client for receiving cross-service messages (party and guild updates, etc.)
 #[derive(Resource, Clone)]
 pub struct NatsClient(pub async_nats::Client);
 
@@ -34,7 +36,8 @@ impl Plugin for SocialPlugin {
 
         app.add_systems(
             FixedPreUpdate,
-            party::process_party_updates.in_set(SocialSet::ReceiveUpdates),
+            (party::process_party_updates, guild::process_guild_updates)
+                .in_set(SocialSet::ReceiveUpdates),
         );
         app.add_systems(
             FixedPreUpdate,
@@ -69,6 +72,17 @@ fn setup_nats(mut commands: Commands, runtime: Res<TokioTasksRuntime>, settings:
                     error!(?err, "failed to subscribe to party updates");
                 }
             }
+            match runtime
+                .runtime()
+                .block_on(async { client.subscribe("guild.update.*").await })
+            {
+                Ok(subscriber) => {
+                    commands.insert_resource(GuildSubscription(subscriber));
+                }
+                Err(err) => {
+                    error!(?err, "failed to subscribe to guild updates");
+                }
+            }
             commands.insert_resource(NatsClient(client));
         }
         Err(err) => {
@@ -12,7 +12,6 @@ pub struct PartyUpdate {
 }
 
 #[derive(Component, Debug)]
-#[allow(dead_code)]
 pub struct PartyId(pub i32);
 
 #[derive(Resource)]
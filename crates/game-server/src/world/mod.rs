@@ -1,5 +1,6 @@
 pub mod messages;
 mod movement;
+mod navmesh;
 mod spatial_grid;
 mod spawner;
 
@@ -7,6 +8,8 @@ use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
 
 pub use messages::*;
+pub use navmesh::{NavMesh, ZoneNavMesh};
+pub use spawner::{Spawned, insert_monster_ai, spawn_monster};
 
 #[derive(Debug, Resource, Default)]
 pub struct SpatialGrid {
@@ -49,7 +52,12 @@ impl Plugin for WorldPlugin {
                 .in_set(WorldSet::ProcessMovement),
         );
 
-        app.add_systems(Startup, spawner::setup_spawners);
+        app.add_systems(
+            Update,
+            navmesh::bake_navmesh.run_if(not(resource_exists::<ZoneNavMesh>)),
+        );
+
+        app.add_systems(Update, spawner::setup_spawners);
         app.add_systems(FixedUpdate, (spawner::spawn_mobs, spatial_grid::update_spatial_grid));
     }
 }
@@ -7,6 +7,7 @@ use bevy::prelude::*;
 use game_core::{
     character_controller::{self, CharacterVelocityY},
     components::{GroundedComponent, MovementSpeedComponent},
+    crowd_control::CrowdControlState,
     movement::MoveInput,
 };
 
@@ -61,10 +62,15 @@ pub fn process_move_action_messages(
 
 pub fn process_jump_action_messages(
     mut reader: MessageReader<JumpActionMessage>,
-    mut q_velocity: Query<&mut CharacterVelocityY, With<GroundedComponent>>,
+    mut q_velocity: Query<
+        (&mut CharacterVelocityY, Option<&CrowdControlState>),
+        With<GroundedComponent>,
+    >,
 ) {
     reader.read().for_each(|msg| {
-        if let Ok(mut vel_y) = q_velocity.get_mut(msg.entity) {
+        if let Ok((mut vel_y, crowd_control)) = q_velocity.get_mut(msg.entity)
+            && crowd_control.is_none_or(CrowdControlState::can_move)
+        {
             vel_y.0 = character_controller::try_jump(vel_y.0, true);
         }
     })
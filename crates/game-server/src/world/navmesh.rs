@@ -0,0 +1,474 @@
+use std::{cmp::Ordering, collections::BinaryHeap, f32::consts::SQRT_2, sync::Arc, time::Instant};
+
+use avian3d::prelude::{
+    Collider, ColliderAabb, ColliderConstructorHierarchy, ColliderOf, SpatialQuery,
+    SpatialQueryFilter,
+};
+use bevy::{platform::collections::HashMap, prelude::*};
+use game_core::{
+    character_controller::MAX_SLOPE_ANGLE,
+    collision::GameLayer,
+    constants::{ACTOR_COLLIDER_LENGTH, ACTOR_COLLIDER_RADIUS, ACTOR_HALF_HEIGHT},
+};
+
+use crate::assets::{ZoneProp, ZoneTerrain};
+
+/// Width of a navmesh cell in meters
+const NAV_CELL_SIZE: f32 = 1.0;
+/// Larger zones are baked with coarser cells to bound the bake time and memory
+const MAX_NAV_CELLS: f32 = 1_048_576.0;
+/// Height actors step over on top of the slope limit, e.g. roots and curbs
+const NAV_STEP_HEIGHT: f32 = 0.5;
+/// Searches that expand more cells than this give up and report no path
+const MAX_SEARCH_NODES: usize = 50_000;
+/// Start and goal positions on blocked cells snap to a walkable cell this many cells away
+const SNAP_RADIUS_CELLS: i32 = 3;
+
+const NEIGHBOURS: [IVec2; 8] = [
+    IVec2::new(1, 0),
+    IVec2::new(-1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(0, -1),
+    IVec2::new(1, 1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, -1),
+];
+
+/// Walkable ground of a zone as a grid of cells on the XZ plane. Walkable cells store their
+/// ground height, cells covered by props, walls, steep slopes or holes are blocked.
+#[derive(Debug)]
+pub struct NavMesh {
+    origin: Vec2,
+    cell_size: f32,
+    width: usize,
+    depth: usize,
+    heights: Vec<Option<f32>>,
+}
+
+/// Navmesh of the loaded zone, shared with the pathfinding tasks.
+#[derive(Resource, Clone)]
+pub struct ZoneNavMesh(pub Arc<NavMesh>);
+
+impl NavMesh {
+    /// `heights` holds the ground height of every cell, row by row along the x axis starting at
+    /// `origin`, and `None` for blocked cells.
+    pub fn new(
+        origin: Vec2,
+        cell_size: f32,
+        width: usize,
+        depth: usize,
+        heights: Vec<Option<f32>>,
+    ) -> Self {
+        assert_eq!(heights.len(), width * depth, "navmesh size mismatch");
+        Self {
+            origin,
+            cell_size,
+            width,
+            depth,
+            heights,
+        }
+    }
+
+    pub fn walkable_cells(&self) -> usize {
+        self.heights.iter().flatten().count()
+    }
+
+    /// Shortest walkable path from `start` to `goal` as waypoints at capsule height, the last
+    /// waypoint is `goal` itself. Positions on blocked cells, e.g. an actor hugging a wall, snap
+    /// to the closest walkable cell. Returns `None` if the goal can't be reached.
+    pub fn find_path(&self, start: Vec3, goal: Vec3) -> Option<Vec<Vec3>> {
+        let start_cell = self.snap(self.cell_of(start))?;
+        let goal_cell = self.snap(self.cell_of(goal))?;
+        if start_cell == goal_cell {
+            return Some(vec![goal]);
+        }
+
+        // Octile distance, the exact cost of an unobstructed path
+        let heuristic = |cell: IVec2| {
+            let delta = (goal_cell - cell).abs();
+            let diagonal = delta.min_element() as f32;
+            let straight = delta.max_element() as f32 - diagonal;
+            straight + diagonal * SQRT_2
+        };
+
+        let mut open = BinaryHeap::new();
+        let mut costs: HashMap<IVec2, f32> = HashMap::new();
+        let mut came_from: HashMap<IVec2, IVec2> = HashMap::new();
+        costs.insert(start_cell, 0.0);
+        open.push(OpenCell {
+            estimate: heuristic(start_cell),
+            cell: start_cell,
+        });
+
+        let mut expanded = 0;
+        while let Some(OpenCell { estimate, cell }) = open.pop() {
+            if cell == goal_cell {
+                return Some(self.waypoints(&came_from, goal_cell, goal));
+            }
+
+            let cost = costs[&cell];
+            // Cells are pushed again when a cheaper route is found, skip the outdated entries
+            if estimate > cost + heuristic(cell) + f32::EPSILON {
+                continue;
+            }
+
+            expanded += 1;
+            if expanded > MAX_SEARCH_NODES {
+                return None;
+            }
+
+            for offset in NEIGHBOURS {
+                let next = cell + offset;
+                if !self.can_step(cell, next) {
+                    continue;
+                }
+                let step_cost = if offset.x != 0 && offset.y != 0 {
+                    SQRT_2
+                } else {
+                    1.0
+                };
+                let next_cost = cost + step_cost;
+                if costs.get(&next).is_some_and(|&known| known <= next_cost) {
+                    continue;
+                }
+                costs.insert(next, next_cost);
+                came_from.insert(next, cell);
+                open.push(OpenCell {
+                    estimate: next_cost + heuristic(next),
+                    cell: next,
+                });
+            }
+        }
+
+        None
+    }
+
+    fn cell_of(&self, position: Vec3) -> IVec2 {
+        ((Vec2::new(position.x, position.z) - self.origin) / self.cell_size)
+            .floor()
+            .as_ivec2()
+    }
+
+    fn height(&self, cell: IVec2) -> Option<f32> {
+        if cell.x < 0
+            || cell.y < 0
+            || cell.x as usize >= self.width
+            || cell.y as usize >= self.depth
+        {
+            return None;
+        }
+        self.heights[cell.y as usize * self.width + cell.x as usize]
+    }
+
+    /// Center of a walkable cell at the height of an actor's capsule center.
+    fn waypoint(&self, cell: IVec2) -> Vec3 {
+        let center = self.origin + (cell.as_vec2() + 0.5) * self.cell_size;
+        let ground = self.height(cell).unwrap_or_default();
+        Vec3::new(center.x, ground + ACTOR_HALF_HEIGHT, center.y)
+    }
+
+    /// Closest walkable cell to `cell`.
+    fn snap(&self, cell: IVec2) -> Option<IVec2> {
+        if self.height(cell).is_some() {
+            return Some(cell);
+        }
+        (-SNAP_RADIUS_CELLS..=SNAP_RADIUS_CELLS)
+            .flat_map(|y| (-SNAP_RADIUS_CELLS..=SNAP_RADIUS_CELLS).map(move |x| IVec2::new(x, y)))
+            .filter(|offset| self.height(cell + *offset).is_some())
+            .min_by_key(|offset| offset.length_squared())
+            .map(|offset| cell + offset)
+    }
+
+    /// Whether an actor can walk from `from` into the neighbouring cell `to`. The height
+    /// difference has to fit the slope limit and diagonal steps can't cut blocked corners.
+    fn can_step(&self, from: IVec2, to: IVec2) -> bool {
+        let (Some(from_height), Some(to_height)) = (self.height(from), self.height(to)) else {
+            return false;
+        };
+        let offset = to - from;
+        let distance = if offset.x != 0 && offset.y != 0 {
+            if !self.can_step(from, from + IVec2::new(offset.x, 0))
+                || !self.can_step(from, from + IVec2::new(0, offset.y))
+            {
+                return false;
+            }
+            self.cell_size * SQRT_2
+        } else {
+            self.cell_size
+        };
+        (from_height - to_height).abs() <= distance * MAX_SLOPE_ANGLE.tan() + NAV_STEP_HEIGHT
+    }
+
+    /// Whether the straight line between two cell centers only crosses walkable steps.
+    fn has_straight_path(&self, from: IVec2, to: IVec2) -> bool {
+        let start = from.as_vec2() + 0.5;
+        let end = to.as_vec2() + 0.5;
+        // Sampling at quarter cells never skips a cell the line passes through diagonally
+        let samples = (start.distance(end) * 4.0).ceil() as i32;
+        let mut current = from;
+        for sample in 1..=samples {
+            let cell = start
+                .lerp(end, sample as f32 / samples as f32)
+                .floor()
+                .as_ivec2();
+            if cell != current {
+                if !self.can_step(current, cell) {
+                    return false;
+                }
+                current = cell;
+            }
+        }
+        true
+    }
+
+    /// Walks the search tree back from the goal and drops every cell that is in a straight
+    /// walkable line between its neighbours, leaving only the corners of the path.
+    fn waypoints(
+        &self,
+        came_from: &HashMap<IVec2, IVec2>,
+        goal_cell: IVec2,
+        goal: Vec3,
+    ) -> Vec<Vec3> {
+        let mut cells = vec![goal_cell];
+        while let Some(&previous) = came_from.get(cells.last().unwrap()) {
+            cells.push(previous);
+        }
+        cells.reverse();
+
+        let mut waypoints = Vec::new();
+        let mut corner = cells[0];
+        for window in cells.windows(2).skip(1) {
+            let (cell, next) = (window[0], window[1]);
+            if !self.has_straight_path(corner, next) {
+                waypoints.push(self.waypoint(cell));
+                corner = cell;
+            }
+        }
+        waypoints.push(goal);
+        waypoints
+    }
+}
+
+/// Open set entry of the A* search, ordered so the heap pops the lowest estimate first.
+#[derive(PartialEq)]
+struct OpenCell {
+    estimate: f32,
+    cell: IVec2,
+}
+
+impl Eq for OpenCell {}
+
+impl Ord for OpenCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+impl PartialOrd for OpenCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Bakes the navmesh once the colliders of the zone's terrain and props are built. Every cell
+/// casts a ray down onto the ground and is walkable if the ground isn't too steep and an actor's
+/// capsule fits on top of it.
+#[allow(clippy::type_complexity)]
+pub fn bake_navmesh(
+    mut commands: Commands,
+    q_zone: Query<Has<ColliderConstructorHierarchy>, Or<(With<ZoneTerrain>, With<ZoneProp>)>>,
+    q_terrain: Query<(), With<ZoneTerrain>>,
+    q_colliders: Query<(&ColliderAabb, &ColliderOf)>,
+    spatial_query: SpatialQuery,
+) {
+    if q_zone.is_empty() || q_zone.iter().any(|building| building) {
+        return;
+    }
+
+    let Some((min, max)) = q_colliders
+        .iter()
+        .filter(|(_, collider_of)| q_terrain.contains(collider_of.body))
+        .map(|(aabb, _)| (aabb.min, aabb.max))
+        .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b)))
+    else {
+        return;
+    };
+
+    let size = Vec2::new(max.x - min.x, max.z - min.z);
+    let cell_size = NAV_CELL_SIZE.max((size.x * size.y / MAX_NAV_CELLS).sqrt());
+    let width = ((size.x / cell_size).ceil() as usize).max(1);
+    let depth = ((size.y / cell_size).ceil() as usize).max(1);
+    let origin = Vec2::new(min.x, min.z);
+
+    let started = Instant::now();
+    let filter = SpatialQueryFilter::from_mask([GameLayer::Default, GameLayer::Ground]);
+    let capsule = Collider::capsule(ACTOR_COLLIDER_RADIUS, ACTOR_COLLIDER_LENGTH);
+    let ray_top = max.y + 1.0;
+    let ray_length = max.y - min.y + 2.0;
+    let min_normal_y = MAX_SLOPE_ANGLE.cos();
+
+    let mut heights = Vec::with_capacity(width * depth);
+    for z in 0..depth {
+        for x in 0..width {
+            let center = origin + (Vec2::new(x as f32, z as f32) + 0.5) * cell_size;
+            let height = spatial_query
+                .cast_ray(
+                    Vec3::new(center.x, ray_top, center.y),
+                    Dir3::NEG_Y,
+                    ray_length,
+                    true,
+                    &filter,
+                )
+                .filter(|hit| hit.normal.y >= min_normal_y)
+                .map(|hit| ray_top - hit.distance)
+                .filter(|&ground| {
+                    // Lifted by the step height so the capsule clears the slope it stands on
+                    let position = Vec3::new(
+                        center.x,
+                        ground + ACTOR_HALF_HEIGHT + NAV_STEP_HEIGHT,
+                        center.y,
+                    );
+                    spatial_query
+                        .shape_intersections(&capsule, position, Quat::IDENTITY, &filter)
+                        .is_empty()
+                });
+            heights.push(height);
+        }
+    }
+
+    let navmesh = NavMesh::new(origin, cell_size, width, depth, heights);
+    let walkable = navmesh.walkable_cells();
+    // New colliders reach the spatial query pipeline with the next physics step, until then
+    // every ray misses and the bake is retried
+    if walkable == 0 {
+        return;
+    }
+
+    tracing::info!(
+        width,
+        depth,
+        cell_size,
+        walkable,
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "navmesh baked"
+    );
+    commands.insert_resource(ZoneNavMesh(Arc::new(navmesh)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flat 10x10 navmesh with blocked cells marked as `#`, rows run along z.
+    fn navmesh(rows: [&str; 10]) -> NavMesh {
+        let heights = rows
+            .iter()
+            .flat_map(|row| row.chars().map(|c| (c != '#').then_some(0.0)))
+            .collect();
+        NavMesh::new(Vec2::ZERO, 1.0, 10, 10, heights)
+    }
+
+    fn position(x: f32, z: f32) -> Vec3 {
+        Vec3::new(x, ACTOR_HALF_HEIGHT, z)
+    }
+
+    #[test]
+    fn open_ground_is_a_straight_line() {
+        let navmesh = navmesh([".........."; 10]);
+        let goal = position(8.5, 8.5);
+
+        let path = navmesh.find_path(position(0.5, 0.5), goal).unwrap();
+
+        assert_eq!(path, vec![goal]);
+    }
+
+    #[test]
+    fn paths_go_around_walls() {
+        let navmesh = navmesh([
+            "..........",
+            "..........",
+            "..........",
+            "..........",
+            "#########.",
+            "..........",
+            "..........",
+            "..........",
+            "..........",
+            "..........",
+        ]);
+        let goal = position(0.5, 8.5);
+
+        let path = navmesh.find_path(position(0.5, 0.5), goal).unwrap();
+
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.len() > 1);
+        // The path has to pass through the gap at the end of the wall
+        assert!(
+            path.iter()
+                .any(|waypoint| waypoint.x > 9.0 && (3.0..=6.0).contains(&waypoint.z))
+        );
+        for waypoint in &path[..path.len() - 1] {
+            assert!(navmesh.height(navmesh.cell_of(*waypoint)).is_some());
+        }
+    }
+
+    #[test]
+    fn enclosed_goals_have_no_path() {
+        let navmesh = navmesh([
+            "..........",
+            "..........",
+            "..........",
+            "..........",
+            "##########",
+            "..........",
+            "..........",
+            "..........",
+            "..........",
+            "..........",
+        ]);
+
+        assert!(
+            navmesh
+                .find_path(position(0.5, 0.5), position(0.5, 8.5))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn cliffs_block_paths() {
+        // The far half is 5m higher than the near half
+        let heights = (0..100)
+            .map(|i| Some(if i / 10 >= 5 { 5.0 } else { 0.0 }))
+            .collect();
+        let navmesh = NavMesh::new(Vec2::ZERO, 1.0, 10, 10, heights);
+
+        assert!(
+            navmesh
+                .find_path(position(0.5, 0.5), position(0.5, 8.5))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn blocked_start_snaps_to_walkable_ground() {
+        let navmesh = navmesh([
+            "#.........",
+            "..........",
+            "..........",
+            "..........",
+            "..........",
+            "..........",
+            "..........",
+            "..........",
+            "..........",
+            "..........",
+        ]);
+        let goal = position(5.5, 5.5);
+
+        assert_eq!(
+            navmesh.find_path(position(0.5, 0.5), goal),
+            Some(vec![goal])
+        );
+    }
+}
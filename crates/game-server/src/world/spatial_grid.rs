@@ -12,6 +12,25 @@ fn pos_to_grid_coords(pos: &Vec3) -> IVec2 {
     }
 }
 
+impl SpatialGrid {
+    /// Entities in every cell overlapping the square around `center`.
+    /// Callers still need to filter the candidates by their exact distance.
+    pub fn query_radius(&self, center: Vec3, radius: f32) -> Vec<Entity> {
+        let min = pos_to_grid_coords(&(center - Vec3::splat(radius)));
+        let max = pos_to_grid_coords(&(center + Vec3::splat(radius)));
+
+        let mut entities = Vec::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                if let Some(cell_entities) = self.cells.get(&IVec2::new(x, y)) {
+                    entities.extend(cell_entities.iter().copied());
+                }
+            }
+        }
+        entities
+    }
+}
+
 pub fn update_spatial_grid(
     mut grid: ResMut<SpatialGrid>,
     mut commands: Commands,
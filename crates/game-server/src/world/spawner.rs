@@ -1,12 +1,14 @@
 use crate::{
     ai::{
-        AggroRadius, AiAbilityConfig, AiBehavior, AiBrain, AiMovement, LeashAnchor, ThreatTable,
-        Wander,
+        AggroRadius, AiAbilityConfig, AiBehavior, AiBrain, AiMovement, AiPath, AiState,
+        CallForHelpRadius, Encounter, LeashAnchor, Pack, PackMember, Patrol, PatrolRoute,
+        ThreatTable, Wander,
     },
     assets::{
-        AiBehaviorDef, ContentId, MonsterDef, MonsterId, MonsterLibrary, MonsterLibraryHandle,
+        AiBehaviorDef, AiDef, ContentId, EncounterLibrary, EncounterLibraryHandle, Faction,
+        MonsterDef, MonsterId, MonsterLibrary, MonsterLibraryHandle,
     },
-    combat::Abilities,
+    combat::{Abilities, DamageImmunities, Weapon},
     core::{ActorBundle, AssetIdComponent, NetworkIdCounter},
 };
 use bevy::prelude::*;
@@ -15,9 +17,10 @@ use game_core::{
     constants::ACTOR_HALF_HEIGHT,
     networking::NetworkIdMapping,
     spells::{SpellLibrary, SpellLibraryHandle},
+    zone::{Formation, ZoneDef, ZoneDefHandle},
 };
 use rand::Rng;
-use std::time::Duration;
+use std::{collections::HashMap, sync::Arc};
 
 #[derive(Component)]
 pub struct MobSpawner {
@@ -26,6 +29,11 @@ pub struct MobSpawner {
     pub timer: Timer,
     pub spawn_radius: f32,
     pub level_range: std::ops::Range<i32>,
+    /// Route the spawned mobs patrol instead of wandering
+    pub patrol: Option<Arc<PatrolRoute>>,
+    pub formation: Option<Formation>,
+    /// Pack entity the spawned mobs are linked to
+    pub pack: Option<Entity>,
 }
 
 #[derive(Component)]
@@ -33,17 +41,66 @@ pub struct Spawned {
     pub spawner: Entity,
 }
 
-pub fn setup_spawners(mut commands: Commands) {
-    commands.spawn((
-        Transform::from_xyz(0., 0., 0.),
-        MobSpawner {
-            mob_id: ContentId::from("skeleton-warrior"),
-            max_mobs: 10,
-            spawn_radius: 25.,
-            level_range: std::ops::Range { start: 1, end: 4 },
-            timer: Timer::new(Duration::from_secs(5), TimerMode::Repeating),
-        },
-    ));
+/// Creates a spawner for every spawn point of the zone once the zone definition is loaded,
+/// along with an entity for every pack linking their mobs.
+pub fn setup_spawners(
+    mut commands: Commands,
+    zone_handle: Option<Res<ZoneDefHandle>>,
+    zone_assets: Res<Assets<ZoneDef>>,
+    mut created: Local<bool>,
+) {
+    if *created {
+        return;
+    }
+    let Some(zone) = zone_handle.and_then(|handle| zone_assets.get(&handle.0)) else {
+        return;
+    };
+    *created = true;
+
+    let mut spawn_point_packs: HashMap<&str, Entity> = HashMap::new();
+    for pack in &zone.packs {
+        let pack_entity = commands
+            .spawn(Pack {
+                id: pack.id.clone(),
+            })
+            .id();
+        for spawn_point_id in &pack.spawn_points {
+            if !zone
+                .spawn_points
+                .iter()
+                .any(|point| &point.id == spawn_point_id)
+            {
+                tracing::warn!(pack = %pack.id, %spawn_point_id, "pack references an unknown spawn point");
+            }
+            spawn_point_packs.insert(spawn_point_id, pack_entity);
+        }
+    }
+
+    for spawn_point in &zone.spawn_points {
+        let patrol = spawn_point.patrol.as_ref().and_then(|patrol_id| {
+            let path = zone.patrol_path(patrol_id);
+            if path.is_none() {
+                tracing::warn!(spawn_point = %spawn_point.id, %patrol_id, "spawn point references an unknown patrol path");
+            }
+            path.map(|path| Arc::new(PatrolRoute::from(path)))
+        });
+
+        commands.spawn((
+            Transform::from_translation(Vec3::from(spawn_point.position)),
+            MobSpawner {
+                mob_id: ContentId::from(&spawn_point.monster_id),
+                max_mobs: spawn_point.max_count,
+                spawn_radius: spawn_point.radius,
+                level_range: spawn_point.level_range.0..spawn_point.level_range.1 + 1,
+                timer: Timer::from_seconds(spawn_point.respawn_secs, TimerMode::Repeating),
+                patrol,
+                formation: spawn_point.formation,
+                pack: spawn_point_packs.get(spawn_point.id.as_str()).copied(),
+            },
+        ));
+    }
+
+    tracing::info!(zone_id = %zone.id, spawners = zone.spawn_points.len(), "zone spawners created");
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -56,6 +113,8 @@ pub fn spawn_mobs(
     monster_assets: Res<Assets<MonsterLibrary>>,
     spell_library_handle: Res<SpellLibraryHandle>,
     spell_assets: Res<Assets<SpellLibrary>>,
+    encounter_library_handle: Res<EncounterLibraryHandle>,
+    encounter_assets: Res<Assets<EncounterLibrary>>,
     mut net_id_counter: ResMut<NetworkIdCounter>,
     mut net_entity_map: ResMut<NetworkIdMapping>,
 ) {
@@ -65,6 +124,7 @@ pub fn spawn_mobs(
     };
 
     let spell_library = spell_assets.get(&spell_library_handle.0);
+    let encounter_library = encounter_assets.get(&encounter_library_handle.0);
 
     for (spawner_entity, mut spawner, transform) in q_spawners.iter_mut() {
         spawner.timer.tick(time.delta());
@@ -85,18 +145,18 @@ pub fn spawn_mobs(
                 let z = rng.gen_range(-spawner.spawn_radius..spawner.spawn_radius);
                 let level = rng.gen_range(spawner.level_range.clone());
                 // Spawner position is ground-level; offset Y to capsule center
-                let ground_y = transform.translation.y;
-                let spawn_transform =
-                    transform.with_translation(Vec3::new(x, ground_y + ACTOR_HALF_HEIGHT, z));
+                let spawn_transform = transform
+                    .with_translation(transform.translation + Vec3::new(x, ACTOR_HALF_HEIGHT, z));
 
                 spawn_monster_entity(
                     &mut commands,
-                    &spawner.mob_id,
-                    blueprint,
                     spawner_entity,
+                    &spawner,
+                    blueprint,
                     spawn_transform,
                     level,
                     spell_library,
+                    encounter_library,
                     &mut net_id_counter,
                     &mut net_entity_map,
                 );
@@ -114,15 +174,114 @@ pub fn spawn_mobs(
 #[allow(clippy::too_many_arguments)]
 fn spawn_monster_entity(
     commands: &mut Commands,
-    monster_id: &ContentId,
+    spawner_entity: Entity,
+    spawner: &MobSpawner,
     blueprint: &MonsterDef,
-    spawner: Entity,
     transform: Transform,
     level: i32,
     spell_library: Option<&SpellLibrary>,
+    encounter_library: Option<&EncounterLibrary>,
     net_id_counter: &mut NetworkIdCounter,
     net_entity_map: &mut NetworkIdMapping,
 ) {
+    let entity = spawn_monster(
+        commands,
+        &spawner.mob_id,
+        blueprint,
+        transform,
+        level,
+        spell_library,
+        net_id_counter,
+        net_entity_map,
+    );
+    let mut entity_commands = commands.entity(entity);
+    entity_commands.insert(Spawned {
+        spawner: spawner_entity,
+    });
+    if let Some(pack) = spawner.pack {
+        entity_commands.insert(PackMember { pack });
+    }
+
+    // Attach AI components if AI is configured
+    if let Some(ai_def) = &blueprint.ai {
+        let patrol = spawner
+            .patrol
+            .as_ref()
+            .map(|route| Patrol::new(route.clone(), spawner.formation, transform.translation));
+        insert_monster_ai(&mut entity_commands, ai_def, &transform, patrol);
+    }
+
+    if let Some(encounter_id) = blueprint.encounter {
+        match encounter_library.and_then(|library| library.encounters.get(&encounter_id)) {
+            Some(def) => {
+                entity_commands
+                    .insert(Encounter::new(Arc::new(def.clone()), transform.translation));
+            }
+            None => {
+                tracing::warn!(name = %blueprint.name, ?encounter_id, "monster references an unknown encounter");
+            }
+        }
+    }
+}
+
+/// Attaches the AI components of a monster. Used for spawner mobs and encounter adds.
+pub fn insert_monster_ai(
+    entity_commands: &mut EntityCommands,
+    ai_def: &AiDef,
+    transform: &Transform,
+    patrol: Option<Patrol>,
+) {
+    let behavior = match ai_def.behavior {
+        AiBehaviorDef::Aggressive => AiBehavior::Aggressive,
+        AiBehaviorDef::Neutral => AiBehavior::Neutral,
+    };
+
+    entity_commands.insert((
+        AiBrain {
+            behavior,
+            state: if patrol.is_some() {
+                AiState::Patrol
+            } else {
+                AiState::Idle
+            },
+        },
+        ThreatTable::default(),
+        AggroRadius(ai_def.aggro_radius),
+        LeashAnchor {
+            position: transform.translation,
+            max_range: ai_def.leash_range,
+        },
+        AiAbilityConfig {
+            priorities: ai_def.ability_priorities.clone(),
+        },
+        AiMovement::default(),
+        AiPath::default(),
+    ));
+
+    // Patrolling mobs don't wander
+    if let Some(patrol) = patrol {
+        entity_commands.insert(patrol);
+    } else if let Some(wander_def) = &ai_def.wander {
+        entity_commands.insert(Wander::new(wander_def.radius, wander_def.pause_duration));
+    }
+    if ai_def.call_for_help_radius > 0.0 {
+        entity_commands.insert(CallForHelpRadius(ai_def.call_for_help_radius));
+    }
+}
+
+/// Spawns the actor and combat components of a monster, without any AI. Used for spawner
+/// mobs, encounter adds and pets.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_monster(
+    commands: &mut Commands,
+    monster_id: &ContentId,
+    blueprint: &MonsterDef,
+    transform: Transform,
+    level: i32,
+    spell_library: Option<&SpellLibrary>,
+    net_id_counter: &mut NetworkIdCounter,
+    net_entity_map: &mut NetworkIdMapping,
+) -> Entity {
     let network_id = net_id_counter.allocate();
     let vitals = Vitals {
         hp: blueprint.hp,
@@ -149,41 +308,23 @@ fn spawn_monster_entity(
 
     let mut entity_commands = commands.spawn((
         MonsterId(*monster_id),
-        Spawned { spawner },
+        Faction(blueprint.faction),
         actor_bundle,
         AssetIdComponent(blueprint.asset_id),
         abilities,
     ));
 
+    if let Some(weapon) = blueprint.weapon {
+        entity_commands.insert(Weapon(weapon));
+    }
+    if let Some(stats) = blueprint.stats {
+        entity_commands.insert(stats);
+    }
+    if !blueprint.immunities.is_empty() {
+        entity_commands.insert(DamageImmunities(blueprint.immunities.clone()));
+    }
+
     let entity = entity_commands.id();
     net_entity_map.0.insert(network_id, entity);
-
-    // Attach AI components if AI is configured
-    if let Some(ai_def) = &blueprint.ai {
-        let behavior = match ai_def.behavior {
-            AiBehaviorDef::Aggressive => AiBehavior::Aggressive,
-            AiBehaviorDef::Neutral => AiBehavior::Neutral,
-        };
-
-        entity_commands.insert((
-            AiBrain {
-                behavior,
-                ..default()
-            },
-            ThreatTable::default(),
-            AggroRadius(ai_def.aggro_radius),
-            LeashAnchor {
-                position: transform.translation,
-                max_range: ai_def.leash_range,
-            },
-            AiAbilityConfig {
-                priorities: ai_def.ability_priorities.clone(),
-            },
-            AiMovement::default(),
-        ));
-
-        if let Some(wander_def) = &ai_def.wander {
-            entity_commands.insert(Wander::new(wander_def.radius, wander_def.pause_duration));
-        }
-    }
+    entity
 }
@@ -1,5 +1,5 @@
 use crate::{
-    models::ChatChannel,
+    models::{ChatChannel, PetStance},
     primitives::{MOVEMENT_QUANTIZATION_FACTOR, YAW_QUANTIZATION_FACTOR},
 };
 use bitcode::{Decode, Encode};
@@ -44,10 +44,35 @@ pub enum PlayerAction {
         spell_id: u32,
         target_network_id: u32,
     },
+    /// Ground-targeted cast, only valid for area spells
+    CastSpellAtPosition {
+        spell_id: u32,
+        position: glam::Vec3,
+    },
     StartAttack {
         target_network_id: u32,
     },
     StopAttack,
+    /// Sent by a dead player to respawn at the nearest graveyard
+    ReleaseSpirit,
+    /// Challenges another player to a duel, they have to accept it with `AcceptDuel`
+    RequestDuel {
+        target_network_id: u32,
+    },
+    /// Accepts the pending duel request sent to the player
+    AcceptDuel,
+    /// Flags the player for world PvP. Unflagging only takes effect after a timeout
+    SetPvpFlag {
+        enabled: bool,
+    },
+    SetPetStance {
+        stance: PetStance,
+    },
+    /// Orders the player's pet to attack the target, regardless of its stance
+    PetAttack {
+        target_network_id: u32,
+    },
+    DismissPet,
     Chat {
         channel: ChatChannel,
         text: String,
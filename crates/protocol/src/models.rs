@@ -1,5 +1,6 @@
 use crate::primitives::{MovementSpeed, Transform};
 use bitcode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
 
 #[derive(Encode, Decode, Debug, Clone)]
 pub enum ActorAttributes {
@@ -18,6 +19,60 @@ pub struct Vitals {
     pub max_hp: i32,
 }
 
+/// Resource spent by abilities.
+#[derive(Encode, Decode, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PowerKind {
+    #[default]
+    Mana,
+    Energy,
+    Rage,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct Power {
+    pub kind: PowerKind,
+    pub current: i32,
+    pub max: i32,
+}
+
+/// Crowd-control effects currently on an actor.
+#[derive(Encode, Decode, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CrowdControlState {
+    pub stunned: bool,
+    pub rooted: bool,
+    pub silenced: bool,
+    pub feared: bool,
+}
+
+/// When a pet attacks on its own, it always attacks the target its owner orders it to.
+#[derive(Encode, Decode, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PetStance {
+    /// Only follows its owner
+    Passive,
+    /// Attacks whatever its owner attacks or is attacked by
+    #[default]
+    Defensive,
+    /// Also attacks hostile actors that come close
+    Aggressive,
+}
+
+/// How an actor regards another, decides which actions are allowed between them.
+#[derive(Encode, Decode, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reaction {
+    /// Can be attacked and attacks on sight
+    Hostile,
+    /// Can be attacked, but doesn't attack on its own
+    Neutral,
+    /// Can be healed and buffed, but not attacked
+    Friendly,
+}
+
+impl Reaction {
+    pub fn can_attack(self) -> bool {
+        self != Self::Friendly
+    }
+}
+
 #[derive(Encode, Decode)]
 pub struct ItemDrop {
     pub item_id: u32,
@@ -33,6 +88,21 @@ pub struct Actor {
     pub vitals: Vitals,
     pub level: u8,
     pub movement_speed: MovementSpeed,
+    pub auras: Vec<ActorAura>,
+    pub in_combat: bool,
+    pub crowd_control: CrowdControlState,
+    /// Reaction of the receiving player's character towards the actor
+    pub reaction: Reaction,
+    /// Network ID of the player or mob that summoned the actor, set for pets
+    pub owner_id: Option<u32>,
+}
+
+#[derive(Encode, Decode, Debug, Clone)]
+pub struct ActorAura {
+    pub aura_id: u32,
+    pub stacks: u8,
+    /// Remaining duration in seconds
+    pub remaining: f32,
 }
 
 #[repr(u8)]
@@ -42,3 +112,162 @@ pub enum ChatChannel {
     Yell,
     Zone,
 }
+
+/// Outcome of the hit table roll for an attack or damaging spell.
+#[derive(Encode, Decode, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitResult {
+    Hit,
+    Critical,
+    /// Damage was partially blocked
+    Block,
+    Miss,
+    Dodge,
+    Parry,
+}
+
+/// Kind of damage. Armor mitigates physical damage, resistances the magic schools.
+#[derive(
+    Encode, Decode, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default,
+)]
+pub enum DamageSchool {
+    #[default]
+    Physical,
+    Fire,
+    Frost,
+    Nature,
+    Shadow,
+    Holy,
+}
+
+/// What a spell or attack did to its target.
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq)]
+pub enum Impact {
+    /// `amount` is the damage taken after mitigation, 0 for avoided attacks.
+    /// `absorbed` was soaked by shields and `resisted` by the target's resistances
+    Damage {
+        amount: i32,
+        result: HitResult,
+        school: DamageSchool,
+        absorbed: i32,
+        resisted: i32,
+    },
+    /// Effective amount healed, overhealing is not included
+    Heal {
+        amount: i32,
+    },
+    AuraApplied {
+        aura_id: u32,
+    },
+    Dispel {
+        count: u32,
+    },
+    Teleport,
+    Knockback,
+    /// The target was immune, e.g. to crowd control after diminishing returns or to the
+    /// damage school
+    Immune,
+}
+
+/// One line of the server combat log. `source_id` is missing for environmental effects and
+/// auras whose caster has despawned.
+#[derive(Encode, Decode, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct CombatLogEntry {
+    pub source_id: Option<u32>,
+    pub target_id: u32,
+    pub event: CombatLogEvent,
+}
+
+/// What happened in a combat log entry. Melee swings use `AUTO_ATTACK_VISUAL_ID` as `spell_id`.
+#[derive(Encode, Decode, Serialize, Debug, Clone, PartialEq, Eq)]
+pub enum CombatLogEvent {
+    /// `periodic` is set for damage over time ticks from an aura
+    Damage {
+        spell_id: u32,
+        amount: i32,
+        result: HitResult,
+        school: DamageSchool,
+        absorbed: i32,
+        resisted: i32,
+        periodic: bool,
+    },
+    /// Effective amount healed, `periodic` is set for heal over time ticks
+    Heal {
+        spell_id: u32,
+        amount: i32,
+        periodic: bool,
+    },
+    /// The attack was avoided entirely
+    Miss {
+        spell_id: u32,
+        result: HitResult,
+    },
+    Immune {
+        spell_id: u32,
+    },
+    AuraApplied {
+        aura_id: u32,
+    },
+    /// The aura expired, was dispelled or was cancelled
+    AuraRemoved {
+        aura_id: u32,
+    },
+    CastStarted {
+        spell_id: u32,
+    },
+    Death,
+}
+
+/// Why the server rejected a spell cast.
+#[derive(Encode, Decode, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastFailedReason {
+    AlreadyCasting,
+    UnknownSpell,
+    /// Ability cooldown or global cooldown is still running
+    OnCooldown,
+    InvalidTarget,
+    OutOfRange,
+    LineOfSight,
+    /// The caster is dead
+    Dead,
+    /// Not enough mana, energy or rage for the spell cost
+    NotEnoughPower,
+    Stunned,
+    Silenced,
+    Feared,
+}
+
+/// How a duel was decided.
+#[derive(Encode, Decode, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuelEndReason {
+    /// The loser was brought down to 1 hp by the winner
+    Defeated,
+    /// The loser left the duel boundary
+    LeftBoundary,
+    /// The loser died to something else or disconnected
+    Forfeited,
+}
+
+/// How a boss encounter ended.
+#[derive(Encode, Decode, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncounterOutcome {
+    /// The boss was killed
+    Defeated,
+    /// Every player on the boss' threat table died or left
+    Wipe,
+    /// The boss evaded, e.g. after being pulled out of the encounter boundary
+    Reset,
+}
+
+/// Resolved area of an area-of-effect spell, for client visuals.
+#[derive(Encode, Decode, Debug, Clone, PartialEq)]
+pub enum AreaShape {
+    Circle {
+        radius: f32,
+    },
+    Cone {
+        radius: f32,
+        /// Full opening angle in radians
+        angle: f32,
+        yaw: f32,
+    },
+}
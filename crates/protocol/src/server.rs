@@ -1,6 +1,9 @@
 use crate::{
-    models::{Actor, ChatChannel, ItemDrop},
-    primitives::Transform,
+    models::{
+        Actor, AreaShape, CastFailedReason, ChatChannel, CombatLogEntry, CrowdControlState,
+        DuelEndReason, EncounterOutcome, Impact, ItemDrop, Power, Reaction, Vitals,
+    },
+    primitives::{MovementSpeed, Transform},
 };
 use bitcode::{Decode, Encode};
 
@@ -8,6 +11,9 @@ use bitcode::{Decode, Encode};
 /// rather than a spell cast. Clients can check this to differentiate visual feedback.
 pub const AUTO_ATTACK_VISUAL_ID: u32 = u32::MAX;
 
+/// Sentinel spell_id used in CooldownStarted events for the global cooldown.
+pub const GLOBAL_COOLDOWN_SPELL_ID: u32 = u32::MAX - 1;
+
 #[derive(Encode, Decode, Clone)]
 pub struct ActorTransformUpdate {
     pub actor_id: u32,
@@ -25,6 +31,12 @@ pub enum ServerEvent {
     ActorSpawn(Box<Actor>),
     ActorDespawn(u32),
     ActorDeath(u32),
+    /// A dead player was resurrected or released their spirit, the new position follows
+    /// through movement updates
+    ActorRevived {
+        actor_id: u32,
+        vitals: Vitals,
+    },
     KillReward {
         victim_id: u32,
         loot: Vec<ItemDrop>,
@@ -32,13 +44,125 @@ pub enum ServerEvent {
     StartCasting {
         actor_id: u32,
         spell_id: u32,
+        /// Cast time in seconds after haste modifiers
+        duration: f32,
+    },
+    /// A hit delayed a cast or shortened a channel
+    CastPushback {
+        actor_id: u32,
+        /// Seconds left on the cast or channel
+        remaining: f32,
+    },
+    /// Sent to the caster when the server rejects a spell cast
+    CastFailed {
+        spell_id: u32,
+        reason: CastFailedReason,
+    },
+    /// Homing projectile heading for its target, the `SpellImpact` follows on arrival
+    ProjectileLaunched {
+        caster_id: u32,
+        target_id: u32,
+        spell_id: u32,
+        /// Expected flight time in seconds, based on the distance at launch
+        travel_time: f32,
     },
     SpellImpact {
-        // TODO: How will we do spells that do not have a target, or do not deal damage?
-        // We probably want to implement impact type, like damage, heal, etc...
         target_id: u32,
         spell_id: u32,
-        impact_amount: i32,
+        impact: Impact,
+    },
+    AreaEffect {
+        caster_id: u32,
+        spell_id: u32,
+        center: glam::Vec3,
+        shape: AreaShape,
+    },
+    /// Sent to the caster whenever the server starts an ability cooldown or the global cooldown
+    CooldownStarted {
+        spell_id: u32,
+        duration: f32,
+    },
+    AuraApplied {
+        actor_id: u32,
+        aura_id: u32,
+        stacks: u8,
+        duration: f32,
+    },
+    AuraRemoved {
+        actor_id: u32,
+        aura_id: u32,
+    },
+    MovementSpeedChanged {
+        actor_id: u32,
+        movement_speed: MovementSpeed,
+    },
+    /// Sent when stat changes alter an actor's maximum health, or out-of-combat regeneration
+    /// restores health
+    VitalsChanged {
+        actor_id: u32,
+        vitals: Vitals,
+    },
+    /// Sent to the actor's own client and their party members
+    PowerChanged {
+        actor_id: u32,
+        power: Power,
+    },
+    /// Crowd-control effects on the actor changed
+    CrowdControlChanged {
+        actor_id: u32,
+        state: CrowdControlState,
+    },
+    /// The actor entered combat or its combat timeout ran out
+    CombatStateChanged {
+        actor_id: u32,
+        in_combat: bool,
+    },
+    /// Sent to a viewer whose reaction towards the actor changed, e.g. when a duel starts
+    ReactionChanged {
+        actor_id: u32,
+        reaction: Reaction,
+    },
+    /// Sent to the challenged player
+    DuelRequested {
+        challenger_id: u32,
+    },
+    /// Sent to both duelists once the request is accepted. They become hostile to each other
+    /// when the countdown ends, and lose when they leave the boundary.
+    DuelStarting {
+        opponent_id: u32,
+        center: glam::Vec3,
+        radius: f32,
+        /// Seconds until the duel starts
+        countdown: f32,
+    },
+    /// Sent to the duelists and the players around them
+    DuelEnded {
+        winner_id: u32,
+        loser_id: u32,
+        reason: DuelEndReason,
+    },
+    /// Sent to the players around a boss when it is pulled
+    EncounterStarted {
+        boss_id: u32,
+        name: String,
+    },
+    /// Sent to the players around a boss when its encounter ends
+    EncounterEnded {
+        boss_id: u32,
+        name: String,
+        outcome: EncounterOutcome,
+    },
+    /// Sent to the player when their PvP flag changes
+    PvpFlagChanged {
+        flagged: bool,
+        /// Seconds until the flag drops, if the player turned it off
+        expires_in: Option<f32>,
+    },
+    /// Combat log entries the client can see, batched once per server tick
+    CombatLog(Vec<CombatLogEntry>),
+    ActorGuildChanged {
+        actor_id: u32,
+        guild_name: Option<String>,
     },
     Chat {
         channel: ChatChannel,
@@ -56,6 +180,16 @@ pub struct EnterGameResponse {
     pub player_actor: Actor,
     pub server_tick: u32,
     pub known_abilities: Vec<u32>,
+    pub power: Power,
+    /// Cooldowns still running from a previous session
+    pub cooldowns: Vec<AbilityCooldown>,
+}
+
+#[derive(Encode, Decode, Debug, Clone)]
+pub struct AbilityCooldown {
+    pub spell_id: u32,
+    pub remaining: f32,
+    pub duration: f32,
 }
 
 #[derive(Encode, Decode)]
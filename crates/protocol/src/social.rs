@@ -35,6 +35,12 @@ pub enum SocialAction {
     PartyKick {
         target_id: i32,
     },
+    GuildInviteByName {
+        target_name: String,
+    },
+    GuildAccept,
+    GuildDecline,
+    GuildLeave,
 }
 
 /// Events sent from server to client over the social WebSocket
@@ -72,6 +78,11 @@ pub enum SocialEvent {
         members: Vec<PartyMember>,
     },
     PartyDisbanded,
+    GuildInvite {
+        from_id: i32,
+        from_name: String,
+        guild_name: String,
+    },
 }
 
 #[derive(Debug, Clone, Encode, Decode)]
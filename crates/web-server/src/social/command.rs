@@ -42,5 +42,11 @@ pub enum HubCommand {
     PartyKick {
         target_id: i32,
     },
+    GuildInvite {
+        target: Recipient,
+    },
+    GuildAccept,
+    GuildDecline,
+    GuildLeave,
     Disconnect,
 }
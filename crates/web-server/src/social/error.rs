@@ -18,5 +18,7 @@ pub enum HubError {
     NoPendingInvite,
     NotInParty,
     NotPartyLeader,
+    TargetAlreadyInGuild,
+    NoPendingGuildInvite,
     Unexpected,
 }
@@ -9,8 +9,8 @@ use crate::social::{
     command::{HubMessage, Recipient},
     error::HubError,
     nats::{
-        NatsBridge, NatsEnvelope, PartyUpdate, guild_subject, party_chat_subject,
-        party_update_subject, whisper_subject,
+        GuildUpdate, NatsBridge, NatsEnvelope, PartyUpdate, guild_subject, guild_update_subject,
+        party_chat_subject, party_update_subject, whisper_subject,
     },
 };
 
@@ -36,6 +36,12 @@ struct PartyInvite {
     pub party_id: Option<i32>, // None if inviter has no party yet (will create on accept)
 }
 
+/// A pending guild invitation.
+struct GuildInvite {
+    pub from_id: i32,
+    pub guild_id: i32,
+}
+
 struct Party {
     leader_id: i32,
     members: Vec<i32>,
@@ -67,6 +73,8 @@ pub struct Hub {
     parties: HashMap<i32, Party>,
     /// character_id → pending invite
     pending_invites: HashMap<i32, PartyInvite>,
+    /// character_id → pending guild invite
+    pending_guild_invites: HashMap<i32, GuildInvite>,
     /// Counter for generating party IDs
     next_party_id: i32,
     db_pool: PgPool,
@@ -90,6 +98,7 @@ impl Hub {
             guilds: HashMap::new(),
             parties: HashMap::new(),
             pending_invites: HashMap::new(),
+            pending_guild_invites: HashMap::new(),
             next_party_id: 1,
             db_pool,
             nats,
@@ -179,14 +188,7 @@ impl Hub {
                 );
 
                 if let Some(guild_id) = guild_id {
-                    let members = self.guilds.entry(guild_id).or_default();
-                    let is_first = members.is_empty();
-                    members.push(msg.sender_id);
-
-                    if is_first {
-                        gauge!("social_guilds_active").increment(1.0);
-                        self.spawn_guild_sub(guild_id);
-                    }
+                    self.add_guild_member(msg.sender_id, guild_id);
                 }
             }
             HubCommand::Disconnect => {
@@ -197,6 +199,7 @@ impl Hub {
 
                 // Remove pending invites for this player
                 self.pending_invites.remove(&msg.sender_id);
+                self.pending_guild_invites.remove(&msg.sender_id);
 
                 // Leave party on disconnect
                 let party_id = self.clients.get(&msg.sender_id).and_then(|c| c.party_id);
@@ -206,16 +209,8 @@ impl Hub {
 
                 if let Some(client) = self.clients.remove(&msg.sender_id)
                     && let Some(gid) = client.guild_id
-                    && let Some(members) = self.guilds.get_mut(&gid)
                 {
-                    members.retain(|&id| id != msg.sender_id);
-                    if members.is_empty() {
-                        self.guilds.remove(&gid);
-                        gauge!("social_guilds_active").decrement(1.0);
-                        if let Some(handle) = self.guild_sub_handles.remove(&gid) {
-                            handle.abort();
-                        }
-                    }
+                    self.remove_guild_member(msg.sender_id, gid);
                 }
             }
             HubCommand::ChatMessage { channel, text } => {
@@ -257,6 +252,22 @@ impl Hub {
                 counter!("social_party_actions_total", "action" => "kick").increment(1);
                 self.handle_party_kick(msg.sender_id, target_id).await;
             }
+            HubCommand::GuildInvite { target } => {
+                counter!("social_guild_actions_total", "action" => "invite").increment(1);
+                self.handle_guild_invite(msg.sender_id, target).await;
+            }
+            HubCommand::GuildAccept => {
+                counter!("social_guild_actions_total", "action" => "accept").increment(1);
+                self.handle_guild_accept(msg.sender_id).await;
+            }
+            HubCommand::GuildDecline => {
+                counter!("social_guild_actions_total", "action" => "decline").increment(1);
+                self.handle_guild_decline(msg.sender_id).await;
+            }
+            HubCommand::GuildLeave => {
+                counter!("social_guild_actions_total", "action" => "leave").increment(1);
+                self.handle_guild_leave(msg.sender_id).await;
+            }
         };
     }
 
@@ -479,6 +490,8 @@ impl Hub {
             HubError::NoPendingInvite => "no_pending_invite",
             HubError::NotInParty => "not_in_party",
             HubError::NotPartyLeader => "not_party_leader",
+            HubError::TargetAlreadyInGuild => "target_already_in_guild",
+            HubError::NoPendingGuildInvite => "no_pending_guild_invite",
             HubError::Unexpected => "unexpected",
         };
         counter!("social_errors_total", "error" => error_label).increment(1);
@@ -491,6 +504,8 @@ impl Hub {
             HubError::NoPendingInvite => "You have no pending party invite",
             HubError::NotInParty => "You are not in a party",
             HubError::NotPartyLeader => "Only the party leader can do that",
+            HubError::TargetAlreadyInGuild => "That player is already in a guild",
+            HubError::NoPendingGuildInvite => "You have no pending guild invite",
             HubError::Unexpected => "An unexpected error occured, please try re-logging",
         };
 
@@ -522,6 +537,229 @@ impl Hub {
         }
     }
 
+    // ─── Guild handling ───────────────────────────────────────────────
+
+    fn add_guild_member(&mut self, character_id: i32, guild_id: i32) {
+        let members = self.guilds.entry(guild_id).or_default();
+        let is_first = members.is_empty();
+        members.push(character_id);
+
+        if is_first {
+            gauge!("social_guilds_active").increment(1.0);
+            self.spawn_guild_sub(guild_id);
+        }
+    }
+
+    fn remove_guild_member(&mut self, character_id: i32, guild_id: i32) {
+        let Some(members) = self.guilds.get_mut(&guild_id) else {
+            return;
+        };
+
+        members.retain(|&id| id != character_id);
+        if members.is_empty() {
+            self.guilds.remove(&guild_id);
+            gauge!("social_guilds_active").decrement(1.0);
+            if let Some(handle) = self.guild_sub_handles.remove(&guild_id) {
+                handle.abort();
+            }
+        }
+    }
+
+    async fn fetch_guild_name(&self, guild_id: i32) -> Result<String, HubError> {
+        let row = sqlx::query!("SELECT name FROM guilds WHERE id = $1", guild_id)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|err| {
+                tracing::error!(?err, guild_id, "failed to fetch guild");
+                HubError::Unexpected
+            })?;
+
+        Ok(row.name)
+    }
+
+    async fn handle_guild_invite(&mut self, sender_id: i32, target: Recipient) {
+        let sender_client = self.get_client_unchecked(&sender_id);
+        let sender_name = sender_client.character_name.clone();
+        let sender_tx = sender_client.tx.clone();
+        let Some(guild_id) = sender_client.guild_id else {
+            return self
+                .write_error(HubError::SenderNotInGuild, sender_tx)
+                .await;
+        };
+
+        let target_id = match self.resolve_recipient_id(target).await {
+            Ok(id) => id,
+            Err(err) => return self.write_error(err, sender_tx).await,
+        };
+
+        // Check target is online on this instance
+        let Some(target_client) = self.clients.get(&target_id) else {
+            // TODO: Publish invite via NATS for cross-instance invites
+            return self
+                .write_error(HubError::RecipientNotFound, sender_tx)
+                .await;
+        };
+
+        if target_client.guild_id.is_some() {
+            return self
+                .write_error(HubError::TargetAlreadyInGuild, sender_tx)
+                .await;
+        }
+
+        let guild_name = match self.fetch_guild_name(guild_id).await {
+            Ok(name) => name,
+            Err(err) => return self.write_error(err, sender_tx).await,
+        };
+
+        self.pending_guild_invites.insert(
+            target_id,
+            GuildInvite {
+                from_id: sender_id,
+                guild_id,
+            },
+        );
+
+        let Some(target_client) = self.clients.get(&target_id) else {
+            return;
+        };
+        let event = SocialEvent::GuildInvite {
+            from_id: sender_id,
+            from_name: sender_name,
+            guild_name,
+        };
+        let msg: Arc<[u8]> = Arc::from(bitcode::encode(&event));
+        if let Err(err) = target_client.tx.send(msg).await {
+            tracing::error!(?err, target_id, "failed to send guild invite");
+        }
+    }
+
+    async fn handle_guild_accept(&mut self, character_id: i32) {
+        let tx = self.get_client_unchecked(&character_id).tx.clone();
+        let Some(invite) = self.pending_guild_invites.remove(&character_id) else {
+            return self.write_error(HubError::NoPendingGuildInvite, tx).await;
+        };
+
+        let guild_name = match self.fetch_guild_name(invite.guild_id).await {
+            Ok(name) => name,
+            Err(err) => return self.write_error(err, tx).await,
+        };
+
+        if let Err(err) = sqlx::query!(
+            "UPDATE characters SET guild_id = $2 WHERE id = $1",
+            character_id,
+            invite.guild_id,
+        )
+        .execute(&self.db_pool)
+        .await
+        {
+            tracing::error!(?err, character_id, "failed to store guild membership");
+            return self.write_error(HubError::Unexpected, tx).await;
+        }
+
+        // Update local state
+        if let Some(client) = self.clients.get_mut(&character_id) {
+            client.guild_id = Some(invite.guild_id);
+        }
+        self.add_guild_member(character_id, invite.guild_id);
+
+        // Publish membership update to NATS for game server
+        self.publish_guild_update(
+            character_id,
+            GuildUpdate {
+                guild_id: Some(invite.guild_id),
+                guild_name: Some(guild_name.clone()),
+            },
+        )
+        .await;
+
+        let character_name = self
+            .get_client_unchecked(&character_id)
+            .character_name
+            .clone();
+        self.broadcast_guild_system_message(
+            invite.guild_id,
+            &format!("{character_name} has joined {guild_name}"),
+        )
+        .await;
+    }
+
+    async fn handle_guild_decline(&mut self, character_id: i32) {
+        let Some(invite) = self.pending_guild_invites.remove(&character_id) else {
+            let tx = self.get_client_unchecked(&character_id).tx.clone();
+            return self.write_error(HubError::NoPendingGuildInvite, tx).await;
+        };
+
+        let character_name = self
+            .get_client_unchecked(&character_id)
+            .character_name
+            .clone();
+        self.write_system_message(
+            invite.from_id,
+            &format!("{character_name} declined your guild invite"),
+        )
+        .await;
+    }
+
+    async fn handle_guild_leave(&mut self, character_id: i32) {
+        let client = self.get_client_unchecked(&character_id);
+        let tx = client.tx.clone();
+        let character_name = client.character_name.clone();
+        let Some(guild_id) = client.guild_id else {
+            return self.write_error(HubError::SenderNotInGuild, tx).await;
+        };
+
+        if let Err(err) = sqlx::query!(
+            "UPDATE characters SET guild_id = NULL WHERE id = $1",
+            character_id,
+        )
+        .execute(&self.db_pool)
+        .await
+        {
+            tracing::error!(?err, character_id, "failed to clear guild membership");
+            return self.write_error(HubError::Unexpected, tx).await;
+        }
+
+        // Update local state
+        if let Some(client) = self.clients.get_mut(&character_id) {
+            client.guild_id = None;
+        }
+        self.remove_guild_member(character_id, guild_id);
+
+        // Publish membership update to NATS for game server
+        self.publish_guild_update(
+            character_id,
+            GuildUpdate {
+                guild_id: None,
+                guild_name: None,
+            },
+        )
+        .await;
+
+        self.write_system_message(character_id, "You have left the guild")
+            .await;
+        self.broadcast_guild_system_message(
+            guild_id,
+            &format!("{character_name} has left the guild"),
+        )
+        .await;
+    }
+
+    async fn publish_guild_update(&self, character_id: i32, update: GuildUpdate) {
+        let Some(nats) = &self.nats else { return };
+        nats.publish_json(&guild_update_subject(character_id), &update)
+            .await;
+    }
+
+    /// Only reaches members connected to this instance.
+    async fn broadcast_guild_system_message(&self, guild_id: i32, text: &str) {
+        let Some(members) = self.guilds.get(&guild_id) else {
+            return;
+        };
+        for &member_id in members {
+            self.write_system_message(member_id, text).await;
+        }
+    }
+
     // ─── Party handling ───────────────────────────────────────────────
 
     /// Handle a party chat message arriving from NATS.
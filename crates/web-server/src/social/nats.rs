@@ -36,6 +36,19 @@ pub struct PartyUpdate {
     pub members: Vec<i32>,
 }
 
+/// Subject for guild membership updates (consumed by game server).
+pub fn guild_update_subject(character_id: i32) -> String {
+    format!("guild.update.{character_id}")
+}
+
+/// Guild membership update published to NATS for game server consumption.
+/// Both fields are `None` when the character left their guild.
+#[derive(Serialize, Deserialize)]
+pub struct GuildUpdate {
+    pub guild_id: Option<i32>,
+    pub guild_name: Option<String>,
+}
+
 /// Extract the subject prefix (e.g. "social.guild.123" → "social.guild").
 fn subject_prefix(subject: &str) -> &str {
     // Find the second-to-last dot or return the whole subject
@@ -91,6 +91,12 @@ impl SocketReader {
             SocialAction::PartyDecline => HubCommand::PartyDecline,
             SocialAction::PartyLeave => HubCommand::PartyLeave,
             SocialAction::PartyKick { target_id } => HubCommand::PartyKick { target_id },
+            SocialAction::GuildInviteByName { target_name } => HubCommand::GuildInvite {
+                target: Recipient::Name(target_name),
+            },
+            SocialAction::GuildAccept => HubCommand::GuildAccept,
+            SocialAction::GuildDecline => HubCommand::GuildDecline,
+            SocialAction::GuildLeave => HubCommand::GuildLeave,
         };
 
         self.hub_tx
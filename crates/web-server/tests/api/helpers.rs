@@ -73,7 +73,7 @@ impl TestCharacter {
 }
 
 pub struct TestApp {
-    // pub db_pool: PgPool,
+    pub db_pool: PgPool,
     pub client: WebClient,
     pub account: TestAccount,
     pub character: TestCharacter,
@@ -129,7 +129,7 @@ pub async fn spawn_app() -> TestApp {
         .url;
 
     TestApp {
-        // db_pool: pool,
+        db_pool: pool,
         // jwt_signing_key: settings.application.jwt_signing_key,
         client: WebClient::new(format!("http://localhost:{application_port}")),
         account,
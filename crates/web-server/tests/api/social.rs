@@ -1,9 +1,11 @@
 use crate::helpers::spawn_app;
+use futures::StreamExt;
 use protocol::social::{ChannelType, SocialEvent};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc::channel;
 use tokio::time::timeout;
+use web_server::social::nats::{GuildUpdate, guild_update_subject};
 use web_server::social::{Hub, HubCommand, HubMessage, NatsBridge, Recipient};
 
 const TIMEOUT_DURATION: Duration = Duration::from_secs(2);
@@ -718,3 +720,91 @@ async fn leave_without_party_returns_error() {
     let event = decode_event(&msg);
     assert!(matches!(event, SocialEvent::Error { .. }));
 }
+
+// ─── Guild Membership Tests ──────────────────────────────────────────────────
+
+#[tokio::test]
+async fn guild_join_and_leave_publish_guild_updates() {
+    let app = spawn_app().await;
+    let pool = app.db_pool.clone();
+
+    let guild_id: i32 = sqlx::query_scalar("INSERT INTO guilds (name) VALUES ($1) RETURNING id")
+        .bind(format!("Guild {}", app.character.id))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let officer_id: i32 = sqlx::query_scalar(
+        "INSERT INTO characters (name, account_id, guild_id)
+        SELECT $1, account_id, $2 FROM characters WHERE id = $3
+        RETURNING id",
+    )
+    .bind(format!("Officer{}", app.character.id))
+    .bind(guild_id)
+    .bind(app.character.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let recruit_id = app.character.id;
+
+    // The game server listens for the recruit's guild changes
+    let nats = NatsBridge::connect(&app.nats_url)
+        .await
+        .expect("failed to connect to NATS");
+    let mut updates = nats
+        .subscribe(&guild_update_subject(recruit_id))
+        .await
+        .unwrap();
+
+    let hub_tx = spawn_hub_with_nats(pool.clone(), &app.nats_url).await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut _officer_rx = connect_character(&hub_tx, officer_id, "Officer", Some(guild_id)).await;
+    let mut recruit_rx = connect_character(&hub_tx, recruit_id, "Recruit", None).await;
+
+    tokio::time::sleep(SETTLE_DURATION).await;
+
+    hub_tx
+        .send(HubMessage::new(
+            officer_id,
+            HubCommand::GuildInvite {
+                target: Recipient::Id(recruit_id),
+            },
+        ))
+        .await
+        .unwrap();
+
+    let msg = timeout(TIMEOUT_DURATION, recruit_rx.recv())
+        .await
+        .expect("timed out waiting for guild invite")
+        .expect("channel closed");
+    match decode_event(&msg) {
+        SocialEvent::GuildInvite { from_id, .. } => assert_eq!(from_id, officer_id),
+        other => panic!("expected GuildInvite, got {other:?}"),
+    }
+
+    hub_tx
+        .send(HubMessage::new(recruit_id, HubCommand::GuildAccept))
+        .await
+        .unwrap();
+
+    let msg = timeout(TIMEOUT_DURATION, updates.next())
+        .await
+        .expect("timed out waiting for guild update")
+        .expect("subscription closed");
+    let update: GuildUpdate = serde_json::from_slice(&msg.payload).unwrap();
+    assert_eq!(update.guild_id, Some(guild_id));
+    assert_eq!(update.guild_name, Some(format!("Guild {recruit_id}")));
+
+    hub_tx
+        .send(HubMessage::new(recruit_id, HubCommand::GuildLeave))
+        .await
+        .unwrap();
+
+    let msg = timeout(TIMEOUT_DURATION, updates.next())
+        .await
+        .expect("timed out waiting for guild update")
+        .expect("subscription closed");
+    let update: GuildUpdate = serde_json::from_slice(&msg.payload).unwrap();
+    assert_eq!(update.guild_id, None);
+    assert_eq!(update.guild_name, None);
+}
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS character_equipment (
+    character_id INT NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    item_id INT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (character_id, item_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_character_equipment_character_id ON character_equipment(character_id);
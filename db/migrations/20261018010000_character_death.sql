@@ -0,0 +1 @@
+ALTER TABLE characters ADD COLUMN IF NOT EXISTS is_dead BOOLEAN NOT NULL DEFAULT FALSE;
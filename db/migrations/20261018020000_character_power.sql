@@ -0,0 +1,2 @@
+ALTER TABLE characters ADD COLUMN IF NOT EXISTS power_kind VARCHAR(16) NOT NULL DEFAULT 'mana'
+    CHECK (power_kind IN ('mana', 'energy', 'rage'));
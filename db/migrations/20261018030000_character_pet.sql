@@ -0,0 +1 @@
+ALTER TABLE characters ADD COLUMN IF NOT EXISTS pet_spell_id INTEGER;